@@ -24,7 +24,7 @@ use triblespace::prelude::inlineencodings::{GenId, ShortString, R256};
 use triblespace::prelude::{entity, MetaDescribe, TribleSet, View};
 
 use GORBIE::prelude::*;
-use GORBIE::widgets::triblespace::{id_short, EntityInspectorWidget};
+use GORBIE::widgets::triblespace::{id_short, EntityInspectorWidget, QueryCardWidget};
 
 mod demo {
     use triblespace::prelude::*;
@@ -291,6 +291,7 @@ fn build_demo_space() -> (TribleSet, TribleSet, MemoryRepo, Id) {
 #[derive(Debug)]
 struct InspectorState {
     selected: Id,
+    compared: Vec<Id>,
     columns: usize,
     node_count: usize,
 }
@@ -300,6 +301,7 @@ impl Default for InspectorState {
         use triblespace::macros::id_hex;
         Self {
             selected: id_hex!("11111111111111111111111111111111"),
+            compared: Vec::new(),
             columns: 0,
             node_count: 0,
         }
@@ -312,7 +314,7 @@ fn main(nb: &mut NotebookCtx) {
     nb.view(move |ui| {
         md!(
             ui,
-            "# Hi Triblespace entity inspector (prototype)\n\nTables-first tiled layout, with orthogonal “subway” routing through gutters.\n\nClick a table to select."
+            "# Hi Triblespace entity inspector (prototype)\n\nTables-first tiled layout, with orthogonal “subway” routing through gutters.\n\nClick a table to select, ctrl+click to compare several."
         );
     });
 
@@ -325,6 +327,7 @@ fn main(nb: &mut NotebookCtx) {
         "inspector",
         InspectorState {
             selected: default_selected,
+            compared: Vec::new(),
             columns: 0,
             node_count: 0,
         },
@@ -350,6 +353,8 @@ fn main(nb: &mut NotebookCtx) {
                     &formatter_cache,
                     &mut state.selected,
                 )
+                .multi_select(&mut state.compared)
+                .thumbnails(true)
                 .columns(state.columns)
                 .show(ctx);
 
@@ -382,12 +387,29 @@ _Routing: {:.1} turns avg (max {}) • span {:.1} cols (max {}) • {} left •
                 );
                 ctx.markdown(&metrics);
                 ctx.add_space(8.0);
+
+                // Picking a result selects it in the inspector above.
+                let query = QueryCardWidget::new(&data, &metadata, &name_cache, &formatter_cache)
+                    .show(ctx);
+                if let Some(id) = query.clicked {
+                    state.selected = id;
+                    state.compared.clear();
+                }
+                ctx.add_space(8.0);
             });
         },
     );
 
     nb.view(move |ui| {
-        let selected = inspector.read(ui).selected;
-        md!(ui, "Selected entity: `{}`", id_short(selected));
+        let state = inspector.read(ui);
+        md!(ui, "Selected entity: `{}`", id_short(state.selected));
+        if state.compared.len() > 1 {
+            let compared: Vec<String> = state
+                .compared
+                .iter()
+                .map(|id| format!("`{}`", id_short(*id)))
+                .collect();
+            md!(ui, "Comparing: {}", compared.join(", "));
+        }
     });
 }
@@ -0,0 +1,95 @@
+#!/usr/bin/env -S watchexec -r rust-script
+//! ```cargo
+//! [dependencies]
+//! GORBIE = { path = ".." }
+//! egui = "0.33"
+//! ```
+//!
+//! Re-reads a file whenever it changes on disk. A watcher thread polls the
+//! file's modification time and bumps a `Signal`; the card recomputes when
+//! the signal's generation moves. Pass a path as the first argument
+//! (defaults to `Cargo.toml`).
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use GORBIE::cards::DEFAULT_CARD_PADDING;
+use GORBIE::prelude::*;
+
+struct Watch {
+    signal: Option<Signal>,
+    seen: Option<u64>,
+    contents: ComputedState<Result<String, String>>,
+}
+
+fn watched_path() -> PathBuf {
+    std::env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("Cargo.toml"))
+}
+
+fn spawn_watcher(path: PathBuf) -> Signal {
+    let signal = Signal::new();
+    let remote = signal.clone();
+    std::thread::spawn(move || {
+        let modified = |path: &PathBuf| -> Option<SystemTime> {
+            std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+        };
+        let mut last = modified(&path);
+        loop {
+            std::thread::sleep(Duration::from_millis(500));
+            let current = modified(&path);
+            if current != last {
+                last = current;
+                remote.bump();
+            }
+        }
+    });
+    signal
+}
+
+#[notebook]
+fn main(nb: &mut NotebookCtx) {
+    let init = Watch {
+        signal: None,
+        seen: None,
+        contents: ComputedState::new(Ok(String::new())),
+    };
+    nb.state("file_watch", init, |ctx, watch| {
+        let path = watched_path();
+        let signal = watch
+            .signal
+            .get_or_insert_with(|| spawn_watcher(path.clone()))
+            .clone();
+        signal.attach(ctx.ctx());
+
+        if signal.changed(&mut watch.seen) {
+            let path = path.clone();
+            watch.contents.spawn(move || {
+                std::fs::read_to_string(&path).map_err(|err| err.to_string())
+            });
+        }
+        watch.contents.poll();
+
+        ctx.with_padding(DEFAULT_CARD_PADDING, |ctx| {
+            widgets::markdown(
+                ctx,
+                &format!(
+                    "Watching `{}` — {} change(s) seen.",
+                    path.display(),
+                    signal.generation()
+                ),
+            );
+            match watch.contents.value() {
+                Ok(text) => {
+                    let preview: String = text.lines().take(40).collect::<Vec<_>>().join("\n");
+                    ctx.label(egui::RichText::new(preview).monospace());
+                }
+                Err(err) => {
+                    widgets::markdown(ctx, &format!("_Could not read file: {err}_"));
+                }
+            }
+        });
+    });
+}
@@ -10,17 +10,16 @@
 use cubecl::prelude::*;
 use cubecl::server::Handle;
 use cubecl::wgpu::{WgpuDevice, WgpuRuntime};
-use egui::{Color32, ColorImage, DragValue, Vec2};
-use egui_plot::{Legend, Line, Plot, PlotPoints};
+use egui::{Color32, ColorImage, Vec2};
+use egui_plot::{Line, PlotPoints};
 use std::collections::{hash_map::DefaultHasher, HashSet};
 use std::f32::consts::TAU;
 use std::hash::{Hash, Hasher};
-use std::sync::{
-    mpsc::{self, TryRecvError},
-    Mutex,
-};
+use std::sync::mpsc;
+use std::time::Duration;
 
 use GORBIE::cards::DEFAULT_CARD_PADDING;
+use GORBIE::dataflow::{RingHistory, Worker};
 use GORBIE::prelude::*;
 use GORBIE::themes::{self, GorbieToggleButtonStyle};
 
@@ -68,6 +67,7 @@ const CHAIN_TUNE_INTERVAL: u32 = 4;
 const CHAIN_TUNE_TOLERANCE: f64 = 0.02;
 const CHAIN_TUNE_STEP_MAX: u32 = 64;
 const MAX_HISTORY: usize = 200;
+const MAX_BATCH_RATE: u32 = 60;
 const STRESS_HISTORY: usize = 360;
 const STRESS_MAX_WIDTH: usize = 512;
 const STRESS_VIEW_HEIGHT: f32 = STRESS_HISTORY as f32;
@@ -75,6 +75,22 @@ const AUTO_RUN_PULSE_HZ: f32 = 0.7;
 const AUTO_RUN_PULSE_BASE: f32 = 0.14;
 const AUTO_RUN_PULSE_RANGE: f32 = 0.08;
 
+/// Minimum time between auto-run batches for a cap of `rate` batches per
+/// second; `0` leaves auto-run unpaced.
+fn batch_interval(rate: u32) -> Duration {
+    if rate == 0 {
+        Duration::ZERO
+    } else {
+        Duration::from_secs_f64(1.0 / f64::from(rate))
+    }
+}
+
+/// Batches/sec cap next to an auto-run toggle; `0` means uncapped.
+fn rate_cap_field(ui: &mut egui::Ui, rate: &mut u32) {
+    widgets::drag_value(ui, rate, 0..=MAX_BATCH_RATE, " batches/s")
+        .on_hover_text("Auto-run cap. 0 runs batches back to back.");
+}
+
 fn auto_run_pulse(ui: &egui::Ui) -> (Color32, Color32) {
     let style = GorbieToggleButtonStyle::from(ui.style().as_ref());
     let time = ui.input(|input| input.time) as f32;
@@ -290,96 +306,6 @@ struct AnnealRequest {
     graph: GraphData,
 }
 
-struct BatchRunner {
-    sender: mpsc::Sender<BatchRequest>,
-    receiver: Mutex<mpsc::Receiver<BatchResult>>,
-    in_flight: bool,
-}
-
-impl BatchRunner {
-    fn new_wgpu() -> Self {
-        spawn_worker(wgpu_worker_loop)
-    }
-
-    fn spawn(&mut self, request: BatchRequest) -> Result<(), BatchResult> {
-        if self.in_flight {
-            return Ok(());
-        }
-        if self.sender.send(request).is_err() {
-            return Err(BatchResult::failed("worker disconnected"));
-        }
-        self.in_flight = true;
-        Ok(())
-    }
-
-    fn poll(&mut self) -> Option<BatchResult> {
-        let receiver = self
-            .receiver
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        match receiver.try_recv() {
-            Ok(batch) => {
-                self.in_flight = false;
-                Some(batch)
-            }
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => {
-                self.in_flight = false;
-                Some(BatchResult::failed("worker disconnected"))
-            }
-        }
-    }
-
-    fn is_running(&self) -> bool {
-        self.in_flight
-    }
-}
-
-struct AnnealRunner {
-    sender: mpsc::Sender<AnnealRequest>,
-    receiver: Mutex<mpsc::Receiver<AnnealResult>>,
-    in_flight: bool,
-}
-
-impl AnnealRunner {
-    fn new_wgpu() -> Self {
-        spawn_anneal_worker(wgpu_anneal_worker_loop)
-    }
-
-    fn spawn(&mut self, request: AnnealRequest) -> Result<(), AnnealResult> {
-        if self.in_flight {
-            return Ok(());
-        }
-        if self.sender.send(request).is_err() {
-            return Err(AnnealResult::failed("worker disconnected"));
-        }
-        self.in_flight = true;
-        Ok(())
-    }
-
-    fn poll(&mut self) -> Option<AnnealResult> {
-        let receiver = self
-            .receiver
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        match receiver.try_recv() {
-            Ok(batch) => {
-                self.in_flight = false;
-                Some(batch)
-            }
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => {
-                self.in_flight = false;
-                Some(AnnealResult::failed("worker disconnected"))
-            }
-        }
-    }
-
-    fn is_running(&self) -> bool {
-        self.in_flight
-    }
-}
-
 struct BatchRunnerState<R: Runtime> {
     _device: R::Device,
     client: ComputeClient<R>,
@@ -1021,32 +947,6 @@ impl<R: Runtime> AnnealRunnerState<R> {
     }
 }
 
-fn spawn_worker(
-    worker: fn(mpsc::Receiver<BatchRequest>, mpsc::Sender<BatchResult>),
-) -> BatchRunner {
-    let (request_tx, request_rx) = mpsc::channel();
-    let (result_tx, result_rx) = mpsc::channel();
-    std::thread::spawn(move || worker(request_rx, result_tx));
-    BatchRunner {
-        sender: request_tx,
-        receiver: Mutex::new(result_rx),
-        in_flight: false,
-    }
-}
-
-fn spawn_anneal_worker(
-    worker: fn(mpsc::Receiver<AnnealRequest>, mpsc::Sender<AnnealResult>),
-) -> AnnealRunner {
-    let (request_tx, request_rx) = mpsc::channel();
-    let (result_tx, result_rx) = mpsc::channel();
-    std::thread::spawn(move || worker(request_rx, result_tx));
-    AnnealRunner {
-        sender: request_tx,
-        receiver: Mutex::new(result_rx),
-        in_flight: false,
-    }
-}
-
 fn build_wgpu_runner(graph: &GraphData) -> BatchRunnerState<WgpuRuntime> {
     let device = WgpuDevice::default();
     BatchRunnerState::new(device, graph)
@@ -1144,11 +1044,12 @@ impl BestResult {
 }
 
 struct MinlaState {
-    runner_wgpu: BatchRunner,
+    runner_wgpu: Worker<BatchRequest, BatchResult>,
     last: BatchResult,
     best: BestResult,
-    history: Vec<BatchHistory>,
+    history: RingHistory<BatchHistory>,
     auto_run: bool,
+    rate_cap: u32,
     total_samples: usize,
     runs: usize,
     graph_id: u64,
@@ -1157,11 +1058,12 @@ struct MinlaState {
 impl MinlaState {
     fn new() -> Self {
         Self {
-            runner_wgpu: BatchRunner::new_wgpu(),
+            runner_wgpu: Worker::new(wgpu_worker_loop),
             last: BatchResult::idle(),
             best: BestResult::new(),
-            history: Vec::new(),
+            history: RingHistory::new(MAX_HISTORY),
             auto_run: false,
+            rate_cap: 0,
             total_samples: 0,
             runs: 0,
             graph_id: 0,
@@ -1169,7 +1071,8 @@ impl MinlaState {
     }
 
     fn poll_runners(&mut self) {
-        if let Some(batch) = self.runner_wgpu.poll() {
+        if let Some(result) = self.runner_wgpu.poll() {
+            let batch = result.unwrap_or_else(|error| BatchResult::failed(error.to_string()));
             if batch.error.is_some() {
                 self.last = batch;
                 return;
@@ -1197,10 +1100,6 @@ impl MinlaState {
             best_cost: self.best.cost,
             elapsed_ms: batch.elapsed_ms,
         });
-        if self.history.len() > MAX_HISTORY {
-            let drop = self.history.len() - MAX_HISTORY;
-            self.history.drain(0..drop);
-        }
     }
 
     fn sync_graph(&mut self, graph: &GraphData) {
@@ -1823,11 +1722,12 @@ struct AnnealHistory {
 
 struct AnnealState {
     config: AnnealConfig,
-    runner: AnnealRunner,
+    runner: Worker<AnnealRequest, AnnealResult>,
     last: AnnealResult,
     best_cost: u32,
-    history: Vec<AnnealHistory>,
+    history: RingHistory<AnnealHistory>,
     auto_run: bool,
+    rate_cap: u32,
     runs: usize,
     total_steps: usize,
     total_chains: usize,
@@ -1857,11 +1757,12 @@ impl AnnealState {
         auto_tune_anneal_config(&mut config, &graph);
         Self {
             config,
-            runner: AnnealRunner::new_wgpu(),
+            runner: Worker::new(wgpu_anneal_worker_loop),
             last: AnnealResult::idle(),
             best_cost: u32::MAX,
-            history: Vec::new(),
+            history: RingHistory::new(MAX_HISTORY),
             auto_run: false,
+            rate_cap: 0,
             runs: 0,
             total_steps: 0,
             total_chains: 0,
@@ -1938,7 +1839,8 @@ impl AnnealState {
     }
 
     fn poll_runner(&mut self, target_ms: u32, ctx: &egui::Context) {
-        if let Some(batch) = self.runner.poll() {
+        if let Some(result) = self.runner.poll() {
+            let batch = result.unwrap_or_else(|error| AnnealResult::failed(error.to_string()));
             if batch.error.is_some() {
                 self.last = batch;
                 return;
@@ -1976,10 +1878,6 @@ impl AnnealState {
             reseeded: batch.reseeded,
             elapsed_ms: batch.elapsed_ms,
         });
-        if self.history.len() > MAX_HISTORY {
-            let drop = self.history.len() - MAX_HISTORY;
-            self.history.drain(0..drop);
-        }
         if batch.elapsed_ms > 0 && batch.steps > 0 {
             self.config.steps_per_batch =
                 adjust_steps_per_batch(batch.steps, batch.elapsed_ms, target_ms);
@@ -2532,6 +2430,7 @@ The first run can be slow while CubeCL builds shaders.
             ctx.add_enabled(
                 !config.auto_batch,
                 widgets::Slider::new(&mut config.batch_size, MIN_BATCH_SIZE..=MAX_BATCH_SIZE)
+                    .logarithmic(true)
                     .text("batch"),
             );
             if config.auto_batch {
@@ -2543,10 +2442,9 @@ The first run can be slow while CubeCL builds shaders.
             ctx.label("backend: wgpu");
             ctx.horizontal(|ctx| {
                 ctx.label("seed");
-                ctx.add(DragValue::new(&mut config.seed).speed(1));
+                widgets::drag_value(ctx, &mut config.seed, 0..=u64::MAX, "");
             });
-            ctx.separator();
-            ctx.label("Graph");
+            widgets::section_header(ctx, "Graph");
             ctx.horizontal(|ctx| {
                 ctx.label("difficulty");
                 ctx.add(widgets::Slider::new(&mut config.difficulty, 0.0..=1.0).text("level"));
@@ -2575,6 +2473,7 @@ The first run can be slow while CubeCL builds shaders.
                 ctx.add_enabled(
                     !preset,
                     widgets::Slider::new(&mut config.graph.node_count, 2..=MAX_NODE_COUNT)
+                        .logarithmic(true)
                         .text("nodes"),
                 );
                 let max_edges = max_edges(config.graph.node_count).min(MAX_EDGE_COUNT);
@@ -2583,27 +2482,32 @@ The first run can be slow while CubeCL builds shaders.
                 }
                 ctx.add_enabled(
                     !preset,
-                    widgets::Slider::new(&mut config.graph.edge_count, 0..=max_edges).text("edges"),
+                    widgets::Slider::new(&mut config.graph.edge_count, 0..=max_edges)
+                        .logarithmic(true)
+                        .text("edges"),
                 );
             } else {
                 ctx.label("Pattern, nodes, and edges follow the difficulty curve.");
             }
 
+            let preset = manual && config.graph.pattern == GraphPattern::Preset;
             ctx.horizontal(|ctx| {
                 ctx.label("seed");
-                ctx.add(DragValue::new(&mut config.graph.seed).speed(1));
-                if ctx
-                    .add_enabled(
-                        !(manual && config.graph.pattern == GraphPattern::Preset),
-                        widgets::Button::new("Regenerate"),
-                    )
-                    .clicked()
-                {
-                    config.graph.seed = config.graph.seed.wrapping_add(1);
-                }
+                ctx.add_enabled_ui(!preset, |ui| {
+                    widgets::seed_control(ui, &mut config.graph.seed)
+                });
             });
-            if manual && config.graph.pattern == GraphPattern::Preset {
+            // R regenerates with the next seed unless a field is being edited.
+            if !preset
+                && ctx.memory(|memory| memory.focused().is_none())
+                && ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::R))
+            {
+                config.graph.seed = config.graph.seed.wrapping_add(1);
+            }
+            if preset {
                 ctx.label("Preset keeps the original 10-node example graph.");
+            } else {
+                ctx.label("Press R to regenerate with the next seed.");
             }
 
             config.sync_graph();
@@ -2629,7 +2533,7 @@ The first run can be slow while CubeCL builds shaders.
         });
     });
 
-    nb.state("minla", MinlaState::new(), move |ui, state| {
+    nb.lazy_state("minla", MinlaState::new, move |ui, state| {
         let graph = { config.read(ui).graph_data.clone() };
 
         state.sync_graph(&graph);
@@ -2677,15 +2581,26 @@ The first run can be slow while CubeCL builds shaders.
                     toggle = toggle.fill(fill).light(light);
                 }
                 ctx.add(toggle);
+                rate_cap_field(ctx, &mut state.rate_cap);
+                state.runner_wgpu.set_min_interval(batch_interval(state.rate_cap));
+                let cooldown = state.runner_wgpu.cooldown();
                 if !state.auto_run
                     && ctx
-                        .add_enabled(!running, widgets::Button::new("Run once"))
+                        .add_enabled(
+                            !running && cooldown.is_none(),
+                            widgets::Button::new("Run once"),
+                        )
                         .clicked()
                 {
                     spawn_requested = true;
                 }
-                if running || state.auto_run {
+                if running {
                     ctx.ctx().request_repaint();
+                } else if state.auto_run {
+                    match cooldown {
+                        Some(wait) => ctx.ctx().request_repaint_after(wait),
+                        None => ctx.ctx().request_repaint(),
+                    }
                 }
             });
             let last = &state.last;
@@ -2723,8 +2638,7 @@ The first run can be slow while CubeCL builds shaders.
             }
 
             if !state.history.is_empty() {
-                ctx.separator();
-                ctx.label("Performance and improvement");
+                widgets::section_header(ctx, "Performance and improvement");
                 ctx.columns(2, |columns| {
                     let perf_points: Vec<[f64; 2]> = state
                         .history
@@ -2732,14 +2646,12 @@ The first run can be slow while CubeCL builds shaders.
                         .map(|entry| [entry.run as f64, entry.elapsed_ms as f64])
                         .collect();
 
-                    Plot::new("minla_perf")
-                        .height(320.0)
-                        .legend(Legend::default())
-                        .show(&mut columns[0], |plot_ui| {
-                            if !perf_points.is_empty() {
-                                plot_ui.line(Line::new("wgpu", PlotPoints::from(perf_points)));
-                            }
-                        });
+                    let perf_plot = widgets::styled_plot("minla_perf").height(320.0);
+                    widgets::show_styled_plot(&mut columns[0], perf_plot, |plot_ui| {
+                        if !perf_points.is_empty() {
+                            plot_ui.line(Line::new("wgpu", PlotPoints::from(perf_points)));
+                        }
+                    });
 
                     let best_points: Vec<[f64; 2]> = state
                         .history
@@ -2752,17 +2664,15 @@ The first run can be slow while CubeCL builds shaders.
                         .map(|entry| [entry.run as f64, entry.batch_cost as f64])
                         .collect();
 
-                    Plot::new("minla_cost")
-                        .height(320.0)
-                        .legend(Legend::default())
-                        .show(&mut columns[1], |plot_ui| {
-                            if !best_points.is_empty() {
-                                plot_ui.line(Line::new("best", PlotPoints::from(best_points)));
-                            }
-                            if !batch_points.is_empty() {
-                                plot_ui.line(Line::new("batch", PlotPoints::from(batch_points)));
-                            }
-                        });
+                    let cost_plot = widgets::styled_plot("minla_cost").height(320.0);
+                    widgets::show_styled_plot(&mut columns[1], cost_plot, |plot_ui| {
+                        if !best_points.is_empty() {
+                            plot_ui.line(Line::new("best", PlotPoints::from(best_points)));
+                        }
+                        if !batch_points.is_empty() {
+                            plot_ui.line(Line::new("batch", PlotPoints::from(batch_points)));
+                        }
+                    });
                 });
             }
         });
@@ -2770,7 +2680,7 @@ The first run can be slow while CubeCL builds shaders.
         if !running && state.auto_run {
             spawn_requested = true;
         }
-        if spawn_requested && !running {
+        if spawn_requested && !running && state.runner_wgpu.cooldown().is_none() {
             bump_seed = true;
             let request = BatchRequest {
                 batch_size,
@@ -2778,7 +2688,7 @@ The first run can be slow while CubeCL builds shaders.
                 graph: graph.clone(),
             };
             if let Err(error) = state.runner_wgpu.spawn(request) {
-                state.last = error;
+                state.last = BatchResult::failed(error.to_string());
             }
             ui.ctx().request_repaint();
         }
@@ -2788,7 +2698,7 @@ The first run can be slow while CubeCL builds shaders.
         }
     });
 
-    nb.state("anneal", AnnealState::new(), move |ui, state| {
+    nb.lazy_state("anneal", AnnealState::new, move |ui, state| {
         let (graph, target_ms) = {
             let config = config.read(ui);
             (config.graph_data.clone(), config.target_batch_ms)
@@ -2837,22 +2747,36 @@ The first run can be slow while CubeCL builds shaders.
                     toggle = toggle.fill(fill).light(light);
                 }
                 ctx.add(toggle);
+                rate_cap_field(ctx, &mut state.rate_cap);
+                state.runner.set_min_interval(batch_interval(state.rate_cap));
+                let cooldown = state.runner.cooldown();
                 if !state.auto_run
                     && ctx
-                        .add_enabled(!running, widgets::Button::new("Run once"))
+                        .add_enabled(
+                            !running && cooldown.is_none(),
+                            widgets::Button::new("Run once"),
+                        )
                         .clicked()
                 {
                     spawn_requested = true;
                 }
                 if !state.auto_run
                     && ctx
-                        .add_enabled(!running, widgets::Button::new("Reset"))
+                        .add_enabled(
+                            !running,
+                            widgets::Button::new("Reset").danger().require_confirm(),
+                        )
                         .clicked()
                 {
                     reset_requested = true;
                 }
-                if running || state.auto_run {
+                if running {
                     ctx.ctx().request_repaint();
+                } else if state.auto_run {
+                    match cooldown {
+                        Some(wait) => ctx.ctx().request_repaint_after(wait),
+                        None => ctx.ctx().request_repaint(),
+                    }
                 }
             });
 
@@ -2948,26 +2872,22 @@ The first run can be slow while CubeCL builds shaders.
                     .map(|entry| [entry.run as f64, entry.reseeded as f64])
                     .collect();
 
-                Plot::new("anneal_cost")
-                    .height(320.0)
-                    .legend(Legend::default())
-                    .show(ctx, move |plot_ui| {
-                        if !best_points.is_empty() {
-                            plot_ui.line(Line::new("best", PlotPoints::from(best_points)));
-                        }
-                    });
+                let cost_plot = widgets::styled_plot("anneal_cost").height(320.0);
+                widgets::show_styled_plot(ctx, cost_plot, move |plot_ui| {
+                    if !best_points.is_empty() {
+                        plot_ui.line(Line::new("best", PlotPoints::from(best_points)));
+                    }
+                });
 
                 if !reseed_points.is_empty() {
-                    Plot::new("anneal_reseeds")
-                        .height(120.0)
-                        .show(ctx, move |plot_ui| {
-                            plot_ui.line(Line::new("reseeds", PlotPoints::from(reseed_points)));
-                        });
+                    let reseed_plot = widgets::styled_plot("anneal_reseeds").height(120.0);
+                    widgets::show_styled_plot(ctx, reseed_plot, move |plot_ui| {
+                        plot_ui.line(Line::new("reseeds", PlotPoints::from(reseed_points)));
+                    });
                 }
             }
 
-            ctx.separator();
-            ctx.label("Stress over time");
+            widgets::section_header(ctx, "Stress over time");
             ctx.horizontal(|ctx| {
                 ctx.label("View");
                 let prev_view = state.stress_view;
@@ -2990,7 +2910,7 @@ The first run can be slow while CubeCL builds shaders.
         if !running && state.auto_run {
             spawn_requested = true;
         }
-        if spawn_requested && !running {
+        if spawn_requested && !running && state.runner.cooldown().is_none() {
             let batch_size = state.chain_count.max(1) as usize;
             let steps = state
                 .config
@@ -3013,7 +2933,7 @@ The first run can be slow while CubeCL builds shaders.
                 graph: graph.clone(),
             };
             if let Err(error) = state.runner.spawn(request) {
-                state.last = error;
+                state.last = AnnealResult::failed(error.to_string());
             }
             state.force_reinit = false;
             ui.ctx().request_repaint();
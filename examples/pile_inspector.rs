@@ -1026,6 +1026,7 @@ fn main(nb: &mut NotebookCtx) {
                 &mut state.entity_selection,
             )
             .cache_id(ctx.id().with("commit_checkout_graph"))
+            .blob_limits(widgets::BlobLoadLimits::default(), &snapshot.reader)
             .show(ctx);
             state.commit_checkout = Some(checkout);
             let stats = response.stats;
@@ -0,0 +1,238 @@
+#!/usr/bin/env -S watchexec -r rust-script
+//! ```cargo
+//! [dependencies]
+//! GORBIE = { path = "..", features = ["plots", "triblespace"] }
+//! egui = "0.34"
+//! triblespace = { path = "../../triblespace-rs", features = ["wasm"] }
+//! ```
+//!
+//! Theme gallery: one card per widget family in a single scroll, so the
+//! light and dark themes can be compared by flipping the theme toggle (or
+//! the palette's theme entries). The place to check contrast fixes. Uses
+//! only public widget APIs.
+
+use triblespace::core::blob::encodings::wasmcode::WasmCode;
+use triblespace::core::blob::BlobCache;
+use triblespace::core::id::{ExclusiveId, Id};
+use triblespace::core::repo::memoryrepo::MemoryRepo;
+use triblespace::core::repo::{BlobStore, BlobStorePut};
+use triblespace::core::value_formatter::WasmValueFormatter;
+use triblespace::prelude::blobencodings::LongString;
+use triblespace::prelude::inlineencodings::{GenId, ShortString};
+use triblespace::prelude::{entity, MetaDescribe, TribleSet, View};
+
+use GORBIE::cards::{self, PlotData};
+use GORBIE::prelude::*;
+use GORBIE::widgets::triblespace::EntityInspectorWidget;
+
+mod gallery {
+    use triblespace::prelude::*;
+
+    attributes! {
+        "3E7A0C52D1B94F68A2C5E01B7D4F9A31" as pub name: inlineencodings::ShortString;
+        "3E7A0C52D1B94F68A2C5E01B7D4F9A32" as pub part_of: inlineencodings::GenId;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Idle,
+    Run,
+    Hold,
+}
+
+struct Controls {
+    gain: f32,
+    cycles: u32,
+    armed: bool,
+    mode: Mode,
+    label: String,
+    tab: usize,
+}
+
+/// A three-level part hierarchy with named attributes, enough to show
+/// tiles, rows and reference edges.
+fn build_space() -> (TribleSet, TribleSet, MemoryRepo, Id) {
+    let mut data = TribleSet::new();
+    let mut metadata = TribleSet::new();
+    let mut storage = MemoryRepo::default();
+
+    for (attr, label, schema) in [
+        (
+            gallery::name.id(),
+            "name",
+            <ShortString as MetaDescribe>::id(),
+        ),
+        (
+            gallery::part_of.id(),
+            "part_of",
+            <GenId as MetaDescribe>::id(),
+        ),
+    ] {
+        let label = storage
+            .put::<LongString, _>(label.to_string())
+            .expect("name handle");
+        metadata += entity! { ExclusiveId::force_ref(&attr) @
+            triblespace::core::metadata::name: label,
+            triblespace::core::metadata::value_encoding: schema,
+        };
+    }
+    metadata += GenId::describe();
+    metadata += ShortString::describe();
+
+    fn part_id(seed: u16) -> Id {
+        let mut raw = [0u8; 16];
+        raw[14..16].copy_from_slice(&seed.to_be_bytes());
+        Id::new(raw).expect("gallery ids are non-zero")
+    }
+
+    let root = part_id(1);
+    data += entity! { ExclusiveId::force_ref(&root) @ gallery::name: "notebook" };
+    for (idx, (parent, label)) in [
+        (1, "card"),
+        (1, "theme"),
+        (2, "widget"),
+        (2, "chrome"),
+        (3, "palette"),
+        (4, "slider"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let id = part_id(idx as u16 + 2);
+        data += entity! { ExclusiveId::force_ref(&id) @
+            gallery::name: label,
+            gallery::part_of: part_id(parent),
+        };
+    }
+
+    (data, metadata, storage, root)
+}
+
+#[notebook]
+fn main(nb: &mut NotebookCtx) {
+    let padding = GORBIE::cards::DEFAULT_CARD_PADDING;
+
+    nb.view(move |ctx| {
+        md!(
+            ctx,
+            "# Theme gallery\n\nEvery widget family on one page. Flip the theme toggle in \
+             the header and scroll through: text, strokes and fills should read well in both."
+        );
+    });
+
+    nb.view(move |ctx| {
+        note!(
+            ctx,
+            "Notes use the post-it fill in both themes; their text should stay dark."
+        );
+    });
+
+    let controls = nb.state(
+        "controls",
+        Controls {
+            gain: 0.6,
+            cycles: 3,
+            armed: true,
+            mode: Mode::Run,
+            label: "gallery".to_owned(),
+            tab: 0,
+        },
+        move |ctx, state| {
+            ctx.with_padding(padding, |ctx| {
+                widgets::section_header(ctx, "Inputs");
+                ctx.add(widgets::Slider::new(&mut state.gain, 0.0..=1.0).text("gain"));
+                ctx.horizontal(|ctx| {
+                    widgets::row_label(ctx, "Cycles");
+                    ctx.add(widgets::NumberField::new(&mut state.cycles).range(1..=8));
+                    ctx.add(widgets::TextField::singleline(&mut state.label));
+                });
+                ctx.horizontal(|ctx| {
+                    ctx.add(widgets::Button::new("Armed").on(&mut state.armed));
+                    ctx.add(widgets::Button::new("Plain"));
+                    ctx.add(widgets::RadioButton::new(
+                        &mut state.mode,
+                        Mode::Hold,
+                        "Hold",
+                    ));
+                });
+                ctx.add(
+                    widgets::ChoiceToggle::new(&mut state.mode)
+                        .choice(Mode::Idle, "Idle")
+                        .choice(Mode::Run, "Run")
+                        .choice(Mode::Hold, "Hold"),
+                );
+
+                widgets::section_header(ctx, "Readouts");
+                ctx.add(
+                    widgets::ProgressBar::new(state.gain)
+                        .text("gain")
+                        .scale_percent(),
+                );
+                widgets::lcd_readout(ctx, &format!("{:.3}", state.gain), None);
+                widgets::tabs(
+                    ctx,
+                    &mut state.tab,
+                    &["Table", "Id"],
+                    |ctx, tab| match tab {
+                        0 => {
+                            widgets::kv_table(
+                                ctx,
+                                &[("label", state.label.as_str()), ("theme", "current")],
+                            );
+                        }
+                        _ => {
+                            widgets::copyable_label(ctx, "3E7A0C52D1B94F68A2C5E01B7D4F9A31");
+                        }
+                    },
+                );
+            });
+        },
+    );
+
+    nb.view(move |ctx| {
+        ctx.with_padding(padding, |ctx| {
+            widgets::markdown(
+                ctx,
+                "## Markdown\n\nBody text, **strong**, _emphasis_, `inline code` and a \
+                 [link](https://trible.space).\n\n> A block quote.\n\n```rust\nfn main() {\n    \
+                 println!(\"code block\");\n}\n```",
+            );
+        });
+    });
+
+    cards::plot_card(
+        nb,
+        "signal",
+        move |ctx| {
+            let controls = controls.read(ctx);
+            (controls.gain, controls.cycles)
+        },
+        |&(gain, cycles)| {
+            let wave = |phase: f64| -> Vec<[f64; 2]> {
+                (0..=200)
+                    .map(|i| {
+                        let x = i as f64 / 200.0;
+                        let y = (x * cycles as f64 * std::f64::consts::TAU + phase).sin();
+                        [x, y * gain as f64]
+                    })
+                    .collect()
+            };
+            PlotData::new()
+                .line("sine", wave(0.0))
+                .points("cosine", wave(std::f64::consts::FRAC_PI_2))
+        },
+    );
+
+    let (data, metadata, mut storage, root) = build_space();
+    let reader = storage.reader().expect("gallery blob store reader");
+    let formatter_cache: BlobCache<_, WasmCode, WasmValueFormatter> =
+        BlobCache::new(reader.clone());
+    let name_cache: BlobCache<_, LongString, View<str>> = BlobCache::new(reader);
+    nb.state("inspector", root, move |ctx, selected| {
+        ctx.with_padding(padding, |ctx| {
+            EntityInspectorWidget::new(&data, &metadata, &name_cache, &formatter_cache, selected)
+                .show(ctx);
+        });
+    });
+}
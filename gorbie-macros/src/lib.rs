@@ -53,10 +53,13 @@ pub fn notebook(attr: TokenStream, item: TokenStream) -> TokenStream {
     } else {
         setup_stmts.push(syn::parse_quote!(let __gorbie_notebook_file = file!();));
         setup_stmts.push(syn::parse_quote!(
+            // `file!()` can be empty or root-like in unusual build setups;
+            // never fail startup over the title.
             let __gorbie_notebook_name = std::path::Path::new(__gorbie_notebook_file)
-                .file_name()
+                .file_stem()
                 .and_then(|s| s.to_str())
-                .unwrap_or(__gorbie_notebook_file);
+                .filter(|s| !s.is_empty())
+                .unwrap_or("notebook");
         ));
         setup_stmts.push(syn::parse_quote!(
             let mut __gorbie_notebook_owner =
@@ -23,10 +23,14 @@ use triblespace::prelude::View;
 use GORBIE::dataflow::ComputedState;
 use GORBIE::themes;
 use GORBIE::widgets;
+use GORBIE::util::{fmt_count, fmt_duration_ns};
+use GORBIE::widgets::hotspot_bars::Hotspot;
 use GORBIE::widgets::triblespace::{PileRepoState, PileRepoWidget};
+use GORBIE::search::contains_case_insensitive_ascii;
 use GORBIE::NotebookCtx;
 
 use GORBIE::telemetry::schema as t;
+use GORBIE::telemetry::{export_collapsed_csv, CollapsedSpan};
 
 type CommitHandle = Inline<Handle<SimpleArchive>>;
 
@@ -125,22 +129,6 @@ fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
-fn fmt_duration_ns(ns: u64) -> String {
-    const US: u64 = 1_000;
-    const MS: u64 = 1_000_000;
-    const S: u64 = 1_000_000_000;
-
-    if ns < US {
-        format!("{ns}ns")
-    } else if ns < MS {
-        format!("{:.1}us", ns as f64 / US as f64)
-    } else if ns < S {
-        format!("{:.3}ms", ns as f64 / MS as f64)
-    } else {
-        format!("{:.3}s", ns as f64 / S as f64)
-    }
-}
-
 fn nice_time_step_ns(target_ns: u64) -> u64 {
     let target = target_ns.max(1) as f64;
     let exp = target.log10().floor();
@@ -158,27 +146,40 @@ fn nice_time_step_ns(target_ns: u64) -> u64 {
     (nice * base).round().max(1.0) as u64
 }
 
-fn contains_case_insensitive_ascii(haystack: &str, needle_lc: &[u8]) -> bool {
-    if needle_lc.is_empty() {
-        return true;
-    }
-    let hay = haystack.as_bytes();
-    if needle_lc.len() > hay.len() {
-        return false;
-    }
-    for start in 0..=hay.len().saturating_sub(needle_lc.len()) {
-        let mut ok = true;
-        for (offset, &needle) in needle_lc.iter().enumerate() {
-            if hay[start + offset].to_ascii_lowercase() != needle {
-                ok = false;
-                break;
-            }
+/// Right-click menu for a span's `file:line` source: copy it, or open it via
+/// the `GORBIE_EDITOR` command template.
+fn source_context_menu(response: &egui::Response, source: Option<&str>) {
+    let Some(source) = source else {
+        return;
+    };
+    response.context_menu(|ui| {
+        if ui.button("Copy source location").clicked() {
+            ui.ctx().copy_text(source.to_owned());
+            ui.close();
         }
-        if ok {
-            return true;
+        if ui
+            .button("Open in editor")
+            .on_hover_text("Uses the GORBIE_EDITOR command template.")
+            .clicked()
+        {
+            GORBIE::telemetry::open_source(source);
+            ui.close();
         }
+    });
+}
+
+/// Monospace source label that copies the location when clicked.
+fn source_label(ui: &mut egui::Ui, source: &str) {
+    let response = ui
+        .add(
+            egui::Label::new(egui::RichText::new(source).monospace().small())
+                .sense(egui::Sense::click()),
+        )
+        .on_hover_text("Click to copy; right-click for more.");
+    if response.clicked() {
+        ui.ctx().copy_text(source.to_owned());
     }
-    false
+    source_context_menu(&response, Some(source));
 }
 
 fn u256be_to_u64(value: Inline<U256BE>) -> Option<u64> {
@@ -293,14 +294,6 @@ struct SpanRecord {
     duration_ns: Option<u64>,
 }
 
-#[derive(Clone, Debug)]
-struct Hotspot {
-    label: String,
-    count: u64,
-    total_ns: u64,
-    max_ns: u64,
-}
-
 #[derive(Clone, Debug)]
 struct FlameSpan {
     id: triblespace::core::id::Id,
@@ -312,14 +305,38 @@ struct FlameSpan {
     duration_ns: u64,
 }
 
-#[derive(Clone, Debug)]
-struct CollapsedSpan {
-    category: String,
-    name: String,
-    source: Option<String>,
-    self_ns: u64,
-    total_ns: u64,
-    children: Vec<CollapsedSpan>,
+/// Upper bound for every snapshot list. The slowest-span heap keeps this
+/// many entries, so raising a cap never requires reloading the session.
+const MAX_TOP_K: usize = 500;
+
+/// Deepest level the collapsed flamegraph depth slider can reach.
+const MAX_COLLAPSED_DEPTH: usize = 64;
+
+/// How many entries each snapshot list keeps, and how the collapsed
+/// flamegraph is shaped: how deep it is drawn before deeper frames are
+/// aggregated, and whether recursive frames fold into their ancestor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SnapshotLimits {
+    open: usize,
+    /// Keep every open span instead of the first `open`; for chasing hangs.
+    open_all: bool,
+    slowest: usize,
+    hotspots: usize,
+    collapsed_depth: usize,
+    merge_recursion: bool,
+}
+
+impl Default for SnapshotLimits {
+    fn default() -> Self {
+        Self {
+            open: 50,
+            open_all: false,
+            slowest: 60,
+            hotspots: 40,
+            collapsed_depth: 24,
+            merge_recursion: false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -331,6 +348,10 @@ struct SessionSnapshot {
     flame: Vec<FlameSpan>,
     collapsed: Vec<CollapsedSpan>,
     spans_open: Vec<SpanRecord>,
+    spans_open_total: usize,
+    /// Latest span begin/end seen at the head commit, on the span clock.
+    /// Open spans have no duration yet; their age is measured against this.
+    head_ns: u64,
     spans_slowest: Vec<SpanRecord>,
     hotspots: Vec<Hotspot>,
 }
@@ -419,7 +440,17 @@ impl SessionIndex {
         }
     }
 
-    fn snapshot(&self) -> SessionSnapshot {
+    fn snapshot(&self, limits: SnapshotLimits) -> SessionSnapshot {
+        let head_ns = self
+            .spans
+            .values()
+            .filter_map(|span| {
+                let begin = span.begin_ns?;
+                Some(begin.saturating_add(span.duration_ns.unwrap_or(0)))
+            })
+            .max()
+            .unwrap_or(0);
+
         let mut flame = Vec::new();
         for (span_id, span) in &self.spans {
             let (Some(begin_ns), Some(duration_ns)) = (span.begin_ns, span.duration_ns) else {
@@ -443,7 +474,10 @@ impl SessionIndex {
                 .then_with(|| a.name.cmp(&b.name))
         });
 
-        let collapsed = build_collapsed_flamegraph(&flame);
+        let collapsed = limit_collapsed_depth(
+            build_collapsed_flamegraph(&flame, limits.merge_recursion),
+            limits.collapsed_depth,
+        );
 
         let mut spans_open = Vec::new();
         for span_id in self.open.iter().copied() {
@@ -463,6 +497,10 @@ impl SessionIndex {
                 .cmp(&b.begin_ns)
                 .then_with(|| a.name.cmp(&b.name))
         });
+        let spans_open_total = spans_open.len();
+        if !limits.open_all {
+            spans_open.truncate(limits.open);
+        }
 
         let mut spans_slowest: Vec<SpanRecord> =
             self.slowest.iter().map(|e| e.record.clone()).collect();
@@ -487,14 +525,19 @@ impl SessionIndex {
             spans_total: self.spans.len(),
             flame,
             collapsed,
-            spans_open: spans_open.into_iter().take(50).collect(),
-            spans_slowest: spans_slowest.into_iter().take(60).collect(),
-            hotspots: hotspots.into_iter().take(40).collect(),
+            spans_open,
+            spans_open_total,
+            head_ns,
+            spans_slowest: spans_slowest.into_iter().take(limits.slowest).collect(),
+            hotspots: hotspots.into_iter().take(limits.hotspots).collect(),
         }
     }
 }
 
-fn build_collapsed_flamegraph(flame: &[FlameSpan]) -> Vec<CollapsedSpan> {
+/// Aggregates span self-time by call path. With `merge_recursion`, a frame
+/// whose key already appears on its path folds back into that ancestor, so
+/// recursive code yields one frame instead of a deep stack of copies.
+fn build_collapsed_flamegraph(flame: &[FlameSpan], merge_recursion: bool) -> Vec<CollapsedSpan> {
     if flame.is_empty() {
         return Vec::new();
     }
@@ -655,6 +698,7 @@ fn build_collapsed_flamegraph(flame: &[FlameSpan]) -> Vec<CollapsedSpan> {
 
     let mut root = Root::default();
     let mut path: Vec<usize> = Vec::new();
+    let mut merged: Vec<usize> = Vec::new();
     for idx in 0..flame.len() {
         let self_ns = self_time[idx];
         if self_ns == 0 {
@@ -672,10 +716,23 @@ fn build_collapsed_flamegraph(flame: &[FlameSpan]) -> Vec<CollapsedSpan> {
         }
         path.reverse();
 
-        let first = path[0];
+        let frames = if merge_recursion {
+            merged.clear();
+            for &pos in &path {
+                match merged.iter().position(|&kept| keys[kept] == keys[pos]) {
+                    Some(ancestor) => merged.truncate(ancestor + 1),
+                    None => merged.push(pos),
+                }
+            }
+            &merged
+        } else {
+            &path
+        };
+
+        let first = frames[0];
         let mut node = root.child_mut(keys[first]);
         node.total_ns = node.total_ns.saturating_add(self_ns);
-        for &pos in path.iter().skip(1) {
+        for &pos in frames.iter().skip(1) {
             let child = node.child_mut(keys[pos]);
             node = child;
             node.total_ns = node.total_ns.saturating_add(self_ns);
@@ -718,6 +775,76 @@ fn build_collapsed_flamegraph(flame: &[FlameSpan]) -> Vec<CollapsedSpan> {
     out
 }
 
+/// Writes the collapsed flamegraph CSV to a new
+/// `telemetry-collapsed-<unix ms>.csv` in the working directory, with a
+/// counter appended if that name is taken, so no export is overwritten.
+/// Returns the file name.
+fn save_collapsed_csv(nodes: &[CollapsedSpan]) -> std::io::Result<PathBuf> {
+    use std::io::Write as _;
+
+    let csv = export_collapsed_csv(nodes);
+    let stamp = now_ms();
+    let mut attempt = 0;
+    loop {
+        let path = PathBuf::from(match attempt {
+            0 => format!("telemetry-collapsed-{stamp}.csv"),
+            n => format!("telemetry-collapsed-{stamp}-{n}.csv"),
+        });
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => return file.write_all(csv.as_bytes()).map(|()| path),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Cuts the collapsed tree at `max_depth` (roots are depth 1). The children
+/// of a node at the cut are folded into a single "(N deeper)" child that
+/// carries their summed time, so every parent still covers its children.
+fn limit_collapsed_depth(nodes: Vec<CollapsedSpan>, max_depth: usize) -> Vec<CollapsedSpan> {
+    fn count(nodes: &[CollapsedSpan]) -> usize {
+        nodes.iter().map(|node| 1 + count(&node.children)).sum()
+    }
+
+    fn limit(mut node: CollapsedSpan, depth: usize, max_depth: usize) -> CollapsedSpan {
+        if node.children.is_empty() {
+            return node;
+        }
+        if depth >= max_depth {
+            let deeper = count(&node.children);
+            let total_ns = node
+                .children
+                .iter()
+                .fold(0u64, |acc, child| acc.saturating_add(child.total_ns));
+            node.children = vec![CollapsedSpan {
+                category: String::new(),
+                name: format!("({deeper} deeper)"),
+                source: None,
+                self_ns: total_ns,
+                total_ns,
+                children: Vec::new(),
+            }];
+        } else {
+            node.children = node
+                .children
+                .into_iter()
+                .map(|child| limit(child, depth + 1, max_depth))
+                .collect();
+        }
+        node
+    }
+
+    let max_depth = max_depth.max(1);
+    nodes
+        .into_iter()
+        .map(|node| limit(node, 1, max_depth))
+        .collect()
+}
+
 fn load_session(
     cache: &mut RepoCache,
     pile_path: PathBuf,
@@ -922,12 +1049,11 @@ fn load_session(
         };
 
         // Keep slowest spans (top-k).
-        const SLOWEST_K: usize = 60;
         let entry = SlowEntry {
             duration_ns: dur,
             record: record.clone(),
         };
-        if index.slowest.len() < SLOWEST_K {
+        if index.slowest.len() < MAX_TOP_K {
             index.slowest.push(entry);
         } else if let Some(keep) = index.slowest.peek() {
             if dur > keep.duration_ns {
@@ -943,6 +1069,7 @@ fn load_session(
         };
         let entry = index.hotspots.entry(label.clone()).or_insert(Hotspot {
             label,
+            source: record.source.clone(),
             count: 0,
             total_ns: 0,
             max_ns: 0,
@@ -1016,12 +1143,16 @@ struct ViewerState {
     min_duration_ms: u64,
     last_snapshot: Option<SessionSnapshot>,
     last_snapshot_head: Option<CommitHandle>,
+    last_snapshot_limits: SnapshotLimits,
+    limits: SnapshotLimits,
     session: ComputedState<SessionLoader>,
     last_repo_path: Option<PathBuf>,
     last_repo_open: bool,
     last_loaded_branch: Option<triblespace::core::id::Id>,
     last_loaded_meta: Option<CommitHandle>,
     last_session_filter: Option<triblespace::core::id::Id>,
+    /// Selected snapshot tab: flamegraph, open spans, slowest, hotspots.
+    snapshot_tab: usize,
     flame_mode: FlameMode,
     flame_zoom: f32,
     selected_span: Option<triblespace::core::id::Id>,
@@ -1040,12 +1171,15 @@ impl Default for ViewerState {
             min_duration_ms: 0,
             last_snapshot: None,
             last_snapshot_head: None,
+            last_snapshot_limits: SnapshotLimits::default(),
+            limits: SnapshotLimits::default(),
             session: ComputedState::default(),
             last_repo_path: None,
             last_repo_open: false,
             last_loaded_branch: None,
             last_loaded_meta: None,
             last_session_filter: None,
+            snapshot_tab: 0,
             flame_mode: FlameMode::default(),
             flame_zoom: 1.0,
             selected_span: None,
@@ -1174,6 +1308,9 @@ pub fn notebook(nb: &mut NotebookCtx) {
                             }
                         }
                     });
+                if let Some(branch) = state.selected.and_then(|idx| state.branches.get(idx)) {
+                    widgets::copyable_label(ui, &format!("{:x}", branch.id));
+                }
 
                 // Clear sessions when branch changes.
                 if state.selected != prev_branch {
@@ -1183,7 +1320,9 @@ pub fn notebook(nb: &mut NotebookCtx) {
                 }
 
                 if state.session.is_running() {
-                    ui.add(egui::Spinner::new());
+                    if !GORBIE::reduced_motion(ui.ctx()) {
+                        ui.add(egui::Spinner::new());
+                    }
                     ui.label("Loading…");
                 }
             });
@@ -1244,6 +1383,46 @@ pub fn notebook(nb: &mut NotebookCtx) {
                 )
                 .on_hover_text("Filters slowest spans + hotspots (by max span duration).");
             });
+            ctx.add_space(6.0);
+            ctx.horizontal_wrapped(|ui| {
+                ui.add(widgets::ChoiceToggle::binary(
+                    &mut state.limits.open_all,
+                    "TOP N",
+                    "ALL",
+                ))
+                .on_hover_text("Show every open span, not just the first N by begin time.");
+                ui.add_enabled(
+                    !state.limits.open_all,
+                    widgets::Slider::new(&mut state.limits.open, 10..=MAX_TOP_K)
+                        .text("OPEN SPANS")
+                        .integer(),
+                );
+            });
+            ctx.add(
+                widgets::Slider::new(&mut state.limits.slowest, 10..=MAX_TOP_K)
+                    .text("SLOWEST")
+                    .integer(),
+            );
+            ctx.add(
+                widgets::Slider::new(&mut state.limits.hotspots, 10..=MAX_TOP_K)
+                    .text("HOTSPOTS")
+                    .integer(),
+            );
+            ctx.add(
+                widgets::Slider::new(&mut state.limits.collapsed_depth, 1..=MAX_COLLAPSED_DEPTH)
+                    .text("FLAME DEPTH")
+                    .integer(),
+            )
+            .on_hover_text("Collapsed flamegraph: deeper frames fold into one \"(N deeper)\" frame.");
+            ctx.horizontal_wrapped(|ui| {
+                widgets::row_label(ui, "Recursion:");
+                ui.add(widgets::ChoiceToggle::binary(
+                    &mut state.limits.merge_recursion,
+                    "NEST",
+                    "MERGE",
+                ))
+                .on_hover_text("Collapsed flamegraph: fold recursive calls into their outermost frame.");
+            });
 
             if repo_state_guard.is_open() && !state.session.is_running() {
                 let selected = state
@@ -1269,9 +1448,13 @@ pub fn notebook(nb: &mut NotebookCtx) {
                     if needs_refresh {
                         if let Some(Ok(index)) = state.session.value().result.as_ref() {
                             let head = index.head;
-                            if state.last_snapshot_head != head || state.last_snapshot.is_none() {
-                                state.last_snapshot = Some(index.snapshot());
+                            if state.last_snapshot_head != head
+                                || state.last_snapshot_limits != state.limits
+                                || state.last_snapshot.is_none()
+                            {
+                                state.last_snapshot = Some(index.snapshot(state.limits));
                                 state.last_snapshot_head = head;
+                                state.last_snapshot_limits = state.limits;
                             }
                         } else if state.last_loaded_branch != Some(branch_id) {
                             // Avoid showing an unrelated snapshot while switching sessions.
@@ -1303,6 +1486,7 @@ pub fn notebook(nb: &mut NotebookCtx) {
                     if state.session.is_running() {
                         if let Some(snapshot) = state.last_snapshot.as_ref() {
                             let ViewerState {
+                                snapshot_tab,
                                 flame_mode,
                                 flame_zoom,
                                 selected_span,
@@ -1313,6 +1497,7 @@ pub fn notebook(nb: &mut NotebookCtx) {
                             } = state;
                             show_snapshot(
                                 ctx,
+                                snapshot_tab,
                                 flame_mode,
                                 flame_zoom,
                                 selected_span,
@@ -1340,12 +1525,17 @@ pub fn notebook(nb: &mut NotebookCtx) {
                 }
                 Some(Ok(index)) => {
                     let head = index.head;
-                    if state.last_snapshot_head != head || state.last_snapshot.is_none() {
-                        state.last_snapshot = Some(index.snapshot());
+                    if state.last_snapshot_head != head
+                        || state.last_snapshot_limits != state.limits
+                        || state.last_snapshot.is_none()
+                    {
+                        state.last_snapshot = Some(index.snapshot(state.limits));
                         state.last_snapshot_head = head;
+                        state.last_snapshot_limits = state.limits;
                     }
                     if let Some(snapshot) = state.last_snapshot.as_ref() {
                         let ViewerState {
+                            snapshot_tab,
                             flame_mode,
                             flame_zoom,
                             selected_span,
@@ -1356,6 +1546,7 @@ pub fn notebook(nb: &mut NotebookCtx) {
                         } = state;
                         show_snapshot(
                             ctx,
+                            snapshot_tab,
                             flame_mode,
                             flame_zoom,
                             selected_span,
@@ -1384,6 +1575,7 @@ pub fn notebook(nb: &mut NotebookCtx) {
 
 fn show_snapshot(
     ui: &mut egui::Ui,
+    tab: &mut usize,
     flame_mode: &mut FlameMode,
     flame_zoom: &mut f32,
     selected_span: &mut Option<triblespace::core::id::Id>,
@@ -1405,8 +1597,8 @@ fn show_snapshot(
     }
     ui.label(format!(
         "Spans: {} total, {} open",
-        snapshot.spans_total,
-        snapshot.spans_open.len()
+        fmt_count(snapshot.spans_total as u64),
+        fmt_count(snapshot.spans_open_total as u64)
     ));
     if let Some(dur) = snapshot.session_duration_ns {
         ui.label(format!("Session duration: {}", fmt_duration_ns(dur)));
@@ -1417,145 +1609,189 @@ fn show_snapshot(
     }
 
     ui.add_space(10.0);
-    ui.heading("Flamegraph");
-    ui.add_space(4.0);
-    ui.horizontal_wrapped(|ui| {
-        ui.label("View:");
-        ui.add(
-            widgets::ChoiceToggle::new(flame_mode)
-                .choice(FlameMode::Timeline, "Timeline")
-                .choice(FlameMode::Collapsed, "Collapsed"),
-        );
-    });
-    ui.add_space(4.0);
-
-    match *flame_mode {
-        FlameMode::Timeline => show_flamegraph_timeline(
-            ui,
-            flame_zoom,
-            selected_span,
-            snapshot,
-            filter,
-            &filter_lc,
-            min_duration_ns,
-        ),
-        FlameMode::Collapsed => show_flamegraph_collapsed(
-            ui,
-            flame_zoom,
-            selected_collapsed,
-            snapshot,
-            filter,
-            &filter_lc,
-            min_duration_ns,
-        ),
-    }
-
-    ui.add_space(10.0);
-    ui.heading("Open Spans");
-    ui.add_space(4.0);
-    if snapshot.spans_open.is_empty() {
-        ui.label(egui::RichText::new("<none>").italics().small());
-    } else {
-        egui::ScrollArea::vertical()
-            .id_salt("telemetry_open_spans")
-            .max_height(220.0)
-            .show(ui, |ui| {
-                for span in snapshot.spans_open.iter().take(50) {
-                    if !filter.is_empty() {
-                        let category = span.category.to_ascii_lowercase();
-                        let name = span.name.to_ascii_lowercase();
-                        let source = span.source.as_deref().unwrap_or("").to_ascii_lowercase();
-                        if !category.contains(&filter_lc)
-                            && !name.contains(&filter_lc)
-                            && !source.contains(&filter_lc)
+    widgets::tabs(
+        ui,
+        tab,
+        &["Flamegraph", "Open spans", "Slowest spans", "Hotspots"],
+        |ui, tab| match tab {
+            0 => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("View:");
+                    ui.add(
+                        widgets::ChoiceToggle::new(flame_mode)
+                            .choice(FlameMode::Timeline, "Timeline")
+                            .choice(FlameMode::Collapsed, "Collapsed"),
+                    );
+                    if *flame_mode == FlameMode::Collapsed && !snapshot.collapsed.is_empty() {
+                        let status_id = egui::Id::new("telemetry_collapsed_csv_status");
+                        if ui
+                            .add(widgets::Button::new("Download CSV"))
+                            .on_hover_text(
+                                "Write the collapsed flamegraph to a new \
+                                 telemetry-collapsed-<time>.csv in the working directory",
+                            )
+                            .clicked()
                         {
-                            continue;
+                            let status = match save_collapsed_csv(&snapshot.collapsed) {
+                                Ok(path) => format!("Saved {}.", path.display()),
+                                Err(err) => format!("Failed to save the CSV: {err}"),
+                            };
+                            ui.data_mut(|d| d.insert_temp(status_id, status));
                         }
-                    }
-
-                    let mut line = format!("{}  {}", span.category, span.name);
-                    if let Some(src) = span.source.as_deref() {
-                        line.push_str(&format!("  ({src})"));
-                    }
-                    ui.label(egui::RichText::new(line).monospace().small());
-                }
-            });
-    }
-
-    ui.add_space(10.0);
-    ui.heading("Slowest Spans");
-    ui.add_space(4.0);
-    if snapshot.spans_slowest.is_empty() {
-        ui.label(egui::RichText::new("<none>").italics().small());
-    } else {
-        egui::ScrollArea::vertical()
-            .id_salt("telemetry_slowest_spans")
-            .max_height(260.0)
-            .show(ui, |ui| {
-                for span in &snapshot.spans_slowest {
-                    let dur = span.duration_ns.unwrap_or(0);
-                    if dur < min_duration_ns {
-                        continue;
-                    }
-                    if !filter.is_empty() {
-                        let category = span.category.to_ascii_lowercase();
-                        let name = span.name.to_ascii_lowercase();
-                        let source = span.source.as_deref().unwrap_or("").to_ascii_lowercase();
-                        if !category.contains(&filter_lc)
-                            && !name.contains(&filter_lc)
-                            && !source.contains(&filter_lc)
-                        {
-                            continue;
+                        if let Some(status) = ui.data(|d| d.get_temp::<String>(status_id)) {
+                            ui.label(egui::RichText::new(status).small());
                         }
                     }
+                });
+                ui.add_space(4.0);
 
-                    let mut line = format!("{:>10}  {}", fmt_duration_ns(dur), span.name);
-                    if let Some(src) = span.source.as_deref() {
-                        line.push_str(&format!("  ({src})"));
-                    }
-                    ui.label(egui::RichText::new(line).monospace().small());
+                match *flame_mode {
+                    FlameMode::Timeline => show_flamegraph_timeline(
+                        ui,
+                        flame_zoom,
+                        selected_span,
+                        snapshot,
+                        filter,
+                        &filter_lc,
+                        min_duration_ns,
+                    ),
+                    FlameMode::Collapsed => show_flamegraph_collapsed(
+                        ui,
+                        flame_zoom,
+                        selected_collapsed,
+                        snapshot,
+                        filter,
+                        &filter_lc,
+                        min_duration_ns,
+                    ),
                 }
-            });
-    }
-
-    ui.add_space(10.0);
-    ui.heading("Hotspots (Total)");
-    ui.add_space(4.0);
-    if snapshot.hotspots.is_empty() {
-        ui.label(egui::RichText::new("<none>").italics().small());
-    } else {
-        egui::ScrollArea::vertical()
-            .id_salt("telemetry_hotspots")
-            .max_height(280.0)
-            .show(ui, |ui| {
-                for hot in &snapshot.hotspots {
-                    if hot.max_ns < min_duration_ns {
-                        continue;
-                    }
-                    if !filter.is_empty() && !hot.label.to_ascii_lowercase().contains(&filter_lc) {
-                        continue;
-                    }
-
-                    let avg = if hot.count == 0 {
-                        0
-                    } else {
-                        (hot.total_ns / hot.count).min(hot.max_ns)
-                    };
+            }
+            1 => {
+                if snapshot.spans_open.len() < snapshot.spans_open_total {
                     ui.label(
                         egui::RichText::new(format!(
-                            "{:>10} total  {:>10} max  {:>10} avg  x{:>4}  {}",
-                            fmt_duration_ns(hot.total_ns),
-                            fmt_duration_ns(hot.max_ns),
-                            fmt_duration_ns(avg),
-                            hot.count,
-                            hot.label
+                            "Showing the first {} of {} by begin time.",
+                            snapshot.spans_open.len(),
+                            snapshot.spans_open_total
                         ))
-                        .monospace()
                         .small(),
                     );
                 }
-            });
-    }
+                if !snapshot.spans_open.is_empty() {
+                    ui.label(
+                        egui::RichText::new("Elapsed since begin, as of the last commit.").small(),
+                    );
+                }
+                if snapshot.spans_open.is_empty() {
+                    ui.label(egui::RichText::new("<none>").italics().small());
+                } else {
+                    egui::ScrollArea::vertical()
+                        .id_salt("telemetry_open_spans")
+                        .max_height(220.0)
+                        .show(ui, |ui| {
+                            for span in &snapshot.spans_open {
+                                if !filter.is_empty() {
+                                    let category = span.category.to_ascii_lowercase();
+                                    let name = span.name.to_ascii_lowercase();
+                                    let source =
+                                        span.source.as_deref().unwrap_or("").to_ascii_lowercase();
+                                    if !category.contains(&filter_lc)
+                                        && !name.contains(&filter_lc)
+                                        && !source.contains(&filter_lc)
+                                    {
+                                        continue;
+                                    }
+                                }
+
+                                let elapsed = snapshot.head_ns.saturating_sub(span.begin_ns);
+                                let mut line = format!(
+                                    "{:>10}  {}  {}",
+                                    fmt_duration_ns(elapsed),
+                                    span.category,
+                                    span.name
+                                );
+                                if let Some(src) = span.source.as_deref() {
+                                    line.push_str(&format!("  ({src})"));
+                                }
+                                let response = ui.add(
+                                    egui::Label::new(egui::RichText::new(line).monospace().small())
+                                        .sense(egui::Sense::click()),
+                                );
+                                source_context_menu(&response, span.source.as_deref());
+                            }
+                        });
+                }
+            }
+            2 => {
+                if snapshot.spans_slowest.is_empty() {
+                    ui.label(egui::RichText::new("<none>").italics().small());
+                } else {
+                    egui::ScrollArea::vertical()
+                        .id_salt("telemetry_slowest_spans")
+                        .max_height(260.0)
+                        .show(ui, |ui| {
+                            for span in &snapshot.spans_slowest {
+                                let dur = span.duration_ns.unwrap_or(0);
+                                if dur < min_duration_ns {
+                                    continue;
+                                }
+                                if !filter.is_empty() {
+                                    let category = span.category.to_ascii_lowercase();
+                                    let name = span.name.to_ascii_lowercase();
+                                    let source =
+                                        span.source.as_deref().unwrap_or("").to_ascii_lowercase();
+                                    if !category.contains(&filter_lc)
+                                        && !name.contains(&filter_lc)
+                                        && !source.contains(&filter_lc)
+                                    {
+                                        continue;
+                                    }
+                                }
+
+                                let mut line =
+                                    format!("{:>10}  {}", fmt_duration_ns(dur), span.name);
+                                if let Some(src) = span.source.as_deref() {
+                                    line.push_str(&format!("  ({src})"));
+                                }
+                                let response = ui.add(
+                                    egui::Label::new(egui::RichText::new(line).monospace().small())
+                                        .sense(egui::Sense::click()),
+                                );
+                                source_context_menu(&response, span.source.as_deref());
+                            }
+                        });
+                }
+            }
+            _ => {
+                if snapshot.hotspots.is_empty() {
+                    ui.label(egui::RichText::new("<none>").italics().small());
+                } else {
+                    let visible: Vec<Hotspot> = snapshot
+                        .hotspots
+                        .iter()
+                        .filter(|hot| hot.max_ns >= min_duration_ns)
+                        .filter(|hot| {
+                            filter.is_empty() || hot.label.to_ascii_lowercase().contains(&filter_lc)
+                        })
+                        .cloned()
+                        .collect();
+                    egui::ScrollArea::vertical()
+                        .id_salt("telemetry_hotspots")
+                        .max_height(280.0)
+                        .show(ui, |ui| {
+                            for (hot, response) in widgets::hotspot_bars(ui, &visible) {
+                                if response.clicked() {
+                                    if let Some(src) = hot.source.as_deref() {
+                                        ui.ctx().copy_text(src.to_owned());
+                                    }
+                                }
+                                source_context_menu(&response, hot.source.as_deref());
+                            }
+                        });
+                }
+            }
+        },
+    );
 }
 
 fn show_flamegraph_timeline(
@@ -1822,6 +2058,7 @@ fn show_flamegraph_timeline(
                 if resp.clicked() {
                     *selected_span = Some(span.id);
                 }
+                source_context_menu(&resp, span.source.as_deref());
                 if resp.hovered() {
                     resp.on_hover_ui(|ui| {
                         ui.label(egui::RichText::new(&span.name).strong());
@@ -1868,7 +2105,7 @@ fn show_flamegraph_timeline(
                 .monospace(),
             );
             if let Some(src) = span.source.as_deref() {
-                ui.label(egui::RichText::new(src).monospace().small());
+                source_label(ui, src);
             }
         }
     }
@@ -2160,6 +2397,7 @@ fn show_flamegraph_collapsed(
                 if resp.clicked() {
                     *selected = Some(node.hash);
                 }
+                source_context_menu(&resp, node.node.source.as_deref());
                 if resp.hovered() {
                     resp.on_hover_ui(|ui| {
                         ui.label(egui::RichText::new(&node.node.name).strong());
@@ -2263,8 +2501,108 @@ fn show_flamegraph_collapsed(
                 .monospace(),
             );
             if let Some(src) = node.node.source.as_deref() {
-                ui.label(egui::RichText::new(src).monospace().small());
+                source_label(ui, src);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(depth: usize) -> CollapsedSpan {
+        // Every level spends 10ns itself; totals accumulate upwards.
+        let mut node = CollapsedSpan {
+            category: "test".to_owned(),
+            name: format!("level{depth}"),
+            source: None,
+            self_ns: 10,
+            total_ns: 10,
+            children: Vec::new(),
+        };
+        for level in (1..depth).rev() {
+            node = CollapsedSpan {
+                category: "test".to_owned(),
+                name: format!("level{level}"),
+                source: None,
+                self_ns: 10,
+                total_ns: node.total_ns + 10,
+                children: vec![node],
+            };
+        }
+        node
+    }
+
+    fn depth(node: &CollapsedSpan) -> usize {
+        1 + node.children.iter().map(depth).max().unwrap_or(0)
+    }
+
+    fn assert_children_within_parent(node: &CollapsedSpan) {
+        let children: u64 = node.children.iter().map(|child| child.total_ns).sum();
+        assert!(children <= node.total_ns, "{} children exceed parent", node.name);
+        node.children.iter().for_each(assert_children_within_parent);
+    }
+
+    fn recursive_trace(levels: u64) -> Vec<FlameSpan> {
+        // `fib` calls itself; every level spends 10ns outside its child.
+        let mut spans = Vec::new();
+        let mut parent = None;
+        for level in 0..levels {
+            let id = *triblespace::core::id::fucid();
+            spans.push(FlameSpan {
+                id,
+                parent,
+                category: "test".to_owned(),
+                name: "fib".to_owned(),
+                source: None,
+                begin_ns: level * 5,
+                duration_ns: (levels - level) * 10,
+            });
+            parent = Some(id);
+        }
+        spans
+    }
+
+    #[test]
+    fn merge_recursion_folds_self_calls_into_one_frame() {
+        let trace = recursive_trace(3);
+
+        let nested = build_collapsed_flamegraph(&trace, false);
+        assert_eq!(nested.len(), 1);
+        assert_eq!(depth(&nested[0]), 3);
+        assert_eq!(nested[0].total_ns, 30);
+        assert_eq!(nested[0].self_ns, 10);
+
+        let merged = build_collapsed_flamegraph(&trace, true);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].name, "fib");
+        assert!(merged[0].children.is_empty());
+        assert_eq!(merged[0].total_ns, 30);
+        assert_eq!(merged[0].self_ns, 30);
+    }
+
+    #[test]
+    fn collapsed_depth_limit_aggregates_deeper_frames() {
+        let tree = chain(5);
+        assert_eq!(tree.total_ns, 50);
+
+        let limited = limit_collapsed_depth(vec![tree.clone()], 3);
+        let root = &limited[0];
+        assert_eq!(root.total_ns, 50);
+        // Three real levels plus the aggregate frame.
+        assert_eq!(depth(root), 4);
+        let level3 = &root.children[0].children[0];
+        assert_eq!(level3.name, "level3");
+        assert_eq!(level3.children.len(), 1);
+        let deeper = &level3.children[0];
+        assert_eq!(deeper.name, "(2 deeper)");
+        assert_eq!(deeper.total_ns, 20);
+        assert!(deeper.children.is_empty());
+        assert_children_within_parent(root);
+
+        let unlimited = limit_collapsed_depth(vec![tree], 5);
+        assert_eq!(depth(&unlimited[0]), 5);
+        assert_children_within_parent(&unlimited[0]);
+    }
+}
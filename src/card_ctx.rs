@@ -182,6 +182,13 @@ impl<'a> CardCtx<'a> {
         crate::is_headless(self.ui.ctx())
     }
 
+    /// True when the notebook runs with reduced motion
+    /// ([`NotebookConfig::with_reduced_motion`](crate::NotebookConfig::with_reduced_motion)).
+    /// Convenience for [`crate::reduced_motion`].
+    pub fn reduced_motion(&self) -> bool {
+        crate::reduced_motion(self.ui.ctx())
+    }
+
     /// Set whether [`section`](Self::section) starts open (the
     /// built-in default) or collapsed, notebook-wide. Convenience for
     /// [`set_default_section_open`] — call from any card before the
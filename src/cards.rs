@@ -1,27 +1,62 @@
 /// Card types and helpers for building notebook content.
+/// Cards showing a texture scaled to fit the column.
+pub mod image_card;
+/// Prose cards rendering markdown.
+#[cfg(feature = "markdown")]
+pub mod markdown_card;
+/// Plot cards that recompute their series only when inputs change.
+#[cfg(feature = "plots")]
+pub mod plot_card;
 /// Cards with persistent state across frames.
 pub mod stateful_card;
 /// Cards redrawn from scratch each frame (no state).
 pub mod stateless_card;
 
+pub use image_card::*;
+#[cfg(feature = "markdown")]
+pub use markdown_card::*;
+#[cfg(feature = "plots")]
+pub use plot_card::*;
 pub use stateful_card::*;
 pub use stateless_card::*;
 
+use std::collections::{HashMap, HashSet};
+
+use crate::state::DependencyKey;
 use crate::CardCtx;
 
 /// Default inner margin applied to card frames.
 pub const DEFAULT_CARD_PADDING: egui::Margin = egui::Margin::symmetric(16, 12);
 
-/// Wraps content in a styled note frame with default padding and fill color.
+fn note_color_id() -> egui::Id {
+    egui::Id::new("gorbie_note_color")
+}
+
+/// Post-it color of the card currently being drawn: its
+/// [`Card::note_color`], or RAL 1003 signal yellow.
+pub fn note_color(ctx: &egui::Context) -> egui::Color32 {
+    ctx.data(|d| d.get_temp(note_color_id()))
+        .unwrap_or(crate::themes::ral(1003))
+}
+
+pub(crate) fn set_note_color(ctx: &egui::Context, color: Option<egui::Color32>) {
+    ctx.data_mut(|d| match color {
+        Some(color) => d.insert_temp(note_color_id(), color),
+        None => d.remove_temp::<egui::Color32>(note_color_id()),
+    });
+}
+
+/// Wraps content in a styled note frame with default padding and the
+/// card's [`note_color`].
 pub fn note_frame<R>(
     ui: &mut egui::Ui,
     add_contents: impl FnOnce(&mut egui::Ui) -> R,
 ) -> egui::InnerResponse<R> {
-    let fill = crate::themes::ral(1003);
+    let fill = note_color(ui.ctx());
     egui::Frame::new()
         .fill(fill)
         .stroke(egui::Stroke::NONE)
-        .corner_radius(0.0)
+        .corner_radius(crate::themes::corner_radius(ui.ctx()))
         .inner_margin(DEFAULT_CARD_PADDING)
         .show(ui, |ui| {
             ui.set_width(ui.available_width());
@@ -31,15 +66,18 @@ pub fn note_frame<R>(
 
 /// Render a formatted markdown string inside a styled note frame.
 ///
-/// Accepts `format!`-style arguments:
-/// ```ignore
-/// note!(ui, "Status: **{}**", status);
+/// Accepts the full `format!` syntax, like [`md!`](crate::md):
+/// ```no_run
+/// # fn card(ui: &mut egui::Ui) {
+/// let status = "ok";
+/// GORBIE::note!(ui, "Status: **{}**", status);
+/// # }
 /// ```
 #[cfg(feature = "markdown")]
 #[macro_export]
 macro_rules! note {
-    ($ui:expr, $fmt:expr $(, $args:expr)*) => {{
-        let text = format!($fmt $(, $args)*);
+    ($ui:expr, $($fmt:tt)+) => {{
+        let text = format!($($fmt)+);
         $crate::cards::note_frame($ui, |ui| {
             $crate::widgets::markdown(ui, &text);
         });
@@ -50,4 +88,173 @@ macro_rules! note {
 pub trait Card {
     /// Renders the card into the given drawing context.
     fn draw(&mut self, ctx: &mut CardCtx<'_>);
+
+    /// Estimates the card's height without drawing it.
+    ///
+    /// Used to reserve space for offscreen cards that haven't been drawn
+    /// yet when card virtualization is enabled (see
+    /// [`NotebookConfig::with_card_virtualization`](crate::NotebookConfig::with_card_virtualization));
+    /// once drawn, the card's last height is used instead. Cards that can
+    /// cheaply predict their height override this; with the default `None`
+    /// the card reserves its [`min_height`](Self::min_height), or is drawn
+    /// once to find out when it has none.
+    fn measure(&self, _ui: &egui::Ui) -> Option<f32> {
+        None
+    }
+
+    /// Height to reserve for the card even while its content is shorter,
+    /// e.g. a card that fills in a 200 px plot once its data has loaded,
+    /// so the notebook doesn't jump when it appears. `None` (the default)
+    /// sizes the card to its content.
+    fn min_height(&self) -> Option<f32> {
+        None
+    }
+
+    /// Adds card-specific entries to the card's right-click menu, below
+    /// the notebook's own (copy source location, open in editor, detach).
+    ///
+    /// Call `ui.close()` after handling a click to dismiss the menu.
+    fn context_menu(&mut self, _ui: &mut egui::Ui) {}
+
+    /// Color for this card's notes and post-it tooltips, e.g. to group
+    /// related cards visually. `None` (the default) keeps signal yellow.
+    fn note_color(&self) -> Option<egui::Color32> {
+        None
+    }
+
+    /// Snapshots the card's interactive state for a saved session.
+    ///
+    /// Cards without savable state return `None` (the default). See
+    /// [`NotebookCtx::save_session`](crate::NotebookCtx::save_session).
+    #[cfg(feature = "serde")]
+    fn save_state(&self, _store: &crate::state::StateStore) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Whether [`save_state`](Self::save_state) can return anything, checked
+    /// every frame without serializing. `false` (the default) for cards
+    /// without savable state.
+    #[cfg(feature = "serde")]
+    fn has_savable_state(&self) -> bool {
+        false
+    }
+
+    /// Restores state previously produced by [`save_state`](Self::save_state).
+    #[cfg(feature = "serde")]
+    fn load_state(&mut self, _store: &crate::state::StateStore, _value: &serde_json::Value) {}
+
+    /// The state this card owns; reads of it by other cards become edges
+    /// in the [`DependencyGraph`]. `None` (the default) for cards without
+    /// one.
+    fn state_key(&self) -> Option<DependencyKey> {
+        None
+    }
+
+    /// States this card depends on beyond the [`StateId`](crate::state::StateId)
+    /// reads recorded while it draws, e.g. values it only touches from a
+    /// background thread. Empty by default.
+    fn dependencies(&self) -> &[DependencyKey] {
+        &[]
+    }
+}
+
+/// A card in a [`DependencyGraph`].
+#[derive(Clone, Debug)]
+pub struct DependencyNode {
+    /// Where the card was added, as `file:line:column`, or `card N` when
+    /// the source is unknown.
+    pub label: String,
+    /// The state the card owns, see [`Card::state_key`].
+    pub state: Option<DependencyKey>,
+    /// States the card read on its last draw plus its declared
+    /// [`Card::dependencies`].
+    pub reads: Vec<DependencyKey>,
+}
+
+/// Which cards read state owned by which other cards; see [`NotebookCtx::dependency_graph`](crate::NotebookCtx::dependency_graph).
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+    /// One node per card, in notebook order.
+    pub cards: Vec<DependencyNode>,
+    /// `(owner, reader)` index pairs into [`cards`](Self::cards), one per
+    /// card pair.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn new(cards: Vec<DependencyNode>) -> Self {
+        let mut owners: HashMap<DependencyKey, Vec<usize>> = HashMap::new();
+        for (owner, node) in cards.iter().enumerate() {
+            if let Some(key) = node.state {
+                owners.entry(key).or_default().push(owner);
+            }
+        }
+        let mut seen = HashSet::new();
+        let mut edges = Vec::new();
+        for (reader, node) in cards.iter().enumerate() {
+            for key in &node.reads {
+                for &owner in owners.get(key).into_iter().flatten() {
+                    if owner != reader && seen.insert((owner, reader)) {
+                        edges.push((owner, reader));
+                    }
+                }
+            }
+        }
+        Self { cards, edges }
+    }
+
+    /// Renders the graph in Graphviz DOT syntax, with edges pointing from
+    /// the card owning a state to the cards reading it.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph notebook {\n    node [shape=box];\n");
+        for (index, node) in self.cards.iter().enumerate() {
+            let label = node.label.replace('\\', "\\\\").replace('"', "\\\"");
+            dot.push_str(&format!("    c{index} [label=\"{label}\"];\n"));
+        }
+        for (owner, reader) in &self.edges {
+            dot.push_str(&format!("    c{owner} -> c{reader};\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StateId;
+
+    fn key(name: &str) -> DependencyKey {
+        StateId::<u32>::new(egui::Id::new(name)).key()
+    }
+
+    fn node(label: &str, state: Option<&str>, reads: &[&str]) -> DependencyNode {
+        DependencyNode {
+            label: label.to_owned(),
+            state: state.map(key),
+            reads: reads.iter().map(|name| key(name)).collect(),
+        }
+    }
+
+    #[test]
+    fn edges_point_from_owner_to_reader_once() {
+        let graph = DependencyGraph::new(vec![
+            node("a", Some("a"), &["a"]),
+            node("b", Some("b"), &["b", "a", "a"]),
+            node("c", None, &["a", "b", "missing"]),
+        ]);
+        assert_eq!(graph.edges, vec![(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let graph = DependencyGraph::new(vec![
+            node("src/\"x\".rs:1:1", Some("a"), &[]),
+            node("card 1", None, &["a"]),
+        ]);
+        assert_eq!(
+            graph.to_dot(),
+            "digraph notebook {\n    node [shape=box];\n    c0 [label=\"src/\\\"x\\\".rs:1:1\"];\n    c1 [label=\"card 1\"];\n    c0 -> c1;\n}\n"
+        );
+    }
 }
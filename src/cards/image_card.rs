@@ -0,0 +1,63 @@
+use eframe::egui;
+
+use crate::cards::DEFAULT_CARD_PADDING;
+use crate::NotebookCtx;
+
+/// Tallest an [`image_card`] image is drawn, in points.
+pub const IMAGE_CARD_MAX_HEIGHT: f32 = 640.0;
+
+/// Adds a card showing `texture`, e.g. a rendered bitmap or a captured
+/// screenshot, with an optional caption below it.
+///
+/// The image keeps its aspect ratio and is only ever scaled down: to the
+/// card's width when it is wider, and to [`IMAGE_CARD_MAX_HEIGHT`] when it
+/// is taller. Smaller images are drawn at their own size, centered.
+///
+/// ```ignore
+/// let texture = ctx.load_texture("render", image, egui::TextureOptions::LINEAR);
+/// cards::image_card(nb, texture, Some("Frame 42"));
+/// ```
+#[track_caller]
+pub fn image_card(nb: &mut NotebookCtx, texture: egui::TextureHandle, caption: Option<&str>) {
+    let caption = caption.map(str::to_owned);
+    nb.view(move |ctx| {
+        ctx.with_padding(DEFAULT_CARD_PADDING, |ctx| {
+            let size = fit_image(texture.size_vec2(), ctx.available_width());
+            ctx.vertical_centered(|ui| {
+                ui.add(egui::Image::new((texture.id(), size)));
+                if let Some(caption) = caption.as_deref() {
+                    ui.label(egui::RichText::new(caption).small().weak());
+                }
+            });
+        });
+    });
+}
+
+/// Size an image of `size` is drawn at in `max_width`: scaled down to fit
+/// the width and [`IMAGE_CARD_MAX_HEIGHT`], never up.
+fn fit_image(size: egui::Vec2, max_width: f32) -> egui::Vec2 {
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return egui::Vec2::ZERO;
+    }
+    let scale = (max_width / size.x)
+        .min(IMAGE_CARD_MAX_HEIGHT / size.y)
+        .min(1.0);
+    size * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images_only_shrink_and_keep_their_aspect_ratio() {
+        let small = egui::vec2(200.0, 100.0);
+        assert_eq!(fit_image(small, 600.0), small);
+        assert_eq!(
+            fit_image(egui::vec2(1200.0, 300.0), 600.0),
+            egui::vec2(600.0, 150.0)
+        );
+        let tall = fit_image(egui::vec2(400.0, 1280.0), 600.0);
+        assert_eq!(tall, egui::vec2(200.0, IMAGE_CARD_MAX_HEIGHT));
+    }
+}
@@ -0,0 +1,33 @@
+use eframe::egui;
+
+use crate::card_ctx::{span_width, GRID_COLUMNS, GRID_EDGE_PAD, GRID_ROW_MODULE};
+use crate::cards::Card;
+use crate::CardCtx;
+
+/// A prose card: `text` rendered as markdown across the full grid width.
+///
+/// Added by [`NotebookCtx::md`](crate::NotebookCtx::md).
+pub struct MarkdownCard {
+    text: String,
+}
+
+impl MarkdownCard {
+    /// Creates a card rendering `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+impl Card for MarkdownCard {
+    fn draw(&mut self, ctx: &mut CardCtx<'_>) {
+        ctx.grid(|g| g.full(|ctx| ctx.markdown(&self.text)));
+    }
+
+    fn measure(&self, ui: &egui::Ui) -> Option<f32> {
+        let width = span_width(GRID_COLUMNS);
+        let text = crate::widgets::markdown::estimate_height(ui, &self.text, width);
+        // The grid snaps the row to its module and pads above and below.
+        let row = (text / GRID_ROW_MODULE).ceil() * GRID_ROW_MODULE;
+        Some(row + 2.0 * GRID_EDGE_PAD)
+    }
+}
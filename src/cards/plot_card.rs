@@ -0,0 +1,351 @@
+use std::any::TypeId;
+
+use eframe::egui;
+use egui_plot::{Line, PlotPoint, PlotPoints, Points};
+
+use crate::cards::{Card, DEFAULT_CARD_PADDING};
+use crate::state::{DependencyKey, StateId};
+use crate::CardCtx;
+use crate::NotebookCtx;
+use crate::{CardIdentityKey, SourceLocation};
+
+/// Height of the plot in a [`plot_card`], in points.
+pub const PLOT_CARD_HEIGHT: f32 = 320.0;
+
+/// How a [`PlotSeries`] is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeriesKind {
+    /// Points joined by a line.
+    Line,
+    /// Unconnected markers.
+    Points,
+}
+
+/// One named series of a [`PlotData`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotSeries {
+    /// Legend label; series sharing a name share a legend entry.
+    pub name: String,
+    /// How the series is drawn.
+    pub kind: SeriesKind,
+    /// Points in plot coordinates.
+    pub points: Vec<PlotPoint>,
+}
+
+/// The series a [`plot_card`] draws, in drawing order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlotData {
+    /// Series, drawn first to last.
+    pub series: Vec<PlotSeries>,
+}
+
+impl PlotData {
+    /// Creates an empty plot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a line series through `[x, y]` pairs.
+    pub fn line(mut self, name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+        self.series.push(PlotSeries {
+            name: name.into(),
+            kind: SeriesKind::Line,
+            points: points.into_iter().map(PlotPoint::from).collect(),
+        });
+        self
+    }
+
+    /// Adds a scatter series of `[x, y]` pairs.
+    pub fn points(mut self, name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+        self.series.push(PlotSeries {
+            name: name.into(),
+            kind: SeriesKind::Points,
+            points: points.into_iter().map(PlotPoint::from).collect(),
+        });
+        self
+    }
+}
+
+/// State of a [`plot_card`]: its last computed series and the dependency
+/// snapshot they were computed from.
+pub struct PlotCardState<D> {
+    deps: Option<D>,
+    force: bool,
+    data: PlotData,
+}
+
+impl<D: PartialEq> PlotCardState<D> {
+    fn new() -> Self {
+        Self {
+            deps: None,
+            force: false,
+            data: PlotData::default(),
+        }
+    }
+
+    /// The series drawn on the card's last frame.
+    pub fn data(&self) -> &PlotData {
+        &self.data
+    }
+
+    /// Forces the next frame to recompute the series even if the
+    /// dependencies compare equal, and asks for that frame. `compute`
+    /// still gets the last snapshot.
+    ///
+    /// A last resort for inputs `deps` can't see, such as a file changed
+    /// on disk; prefer returning whatever changed from `deps` so the card
+    /// notices by itself.
+    ///
+    /// ```ignore
+    /// plot.read_mut(ctx).invalidate(ctx.ctx());
+    /// ```
+    pub fn invalidate(&mut self, ctx: &egui::Context) {
+        self.force = true;
+        ctx.request_repaint();
+    }
+
+    /// Recomputes the series if `deps` differs from the last snapshot;
+    /// returns whether it did. `compute` gets that snapshot alongside
+    /// `deps`.
+    fn refresh(&mut self, deps: D, compute: impl FnOnce(Option<&D>, &D) -> PlotData) -> bool {
+        if !self.force && self.deps.as_ref() == Some(&deps) {
+            return false;
+        }
+        self.data = compute(self.deps.as_ref(), &deps);
+        self.deps = Some(deps);
+        self.force = false;
+        true
+    }
+}
+
+/// Adds a card plotting series that are only recomputed when their inputs
+/// change.
+///
+/// `deps` runs every frame and should read the states the plot depends on
+/// (those reads become edges in the
+/// [`DependencyGraph`](crate::cards::DependencyGraph)); `compute` turns
+/// its result into series and only runs when that result differs from
+/// the previous frame's. Since `deps` runs and is compared every frame,
+/// return something cheap from it: cloning a large config each frame
+/// costs as much as the clone, so prefer a generation counter, a hash or
+/// an `Arc` for big inputs. The plot is drawn with
+/// [`show_styled_plot`](crate::widgets::show_styled_plot),
+/// [`PLOT_CARD_HEIGHT`] high. Pan and zoom are kept per card and survive recomputation;
+/// double-click resets them.
+///
+/// ```ignore
+/// let config = nb.state("config", Config::default(), |ctx, config| { /* … */ });
+/// cards::plot_card(
+///     nb,
+///     "response",
+///     move |ctx| config.read(ctx).clone(),
+///     |config| PlotData::new().line("gain", config.sweep()),
+/// );
+/// ```
+#[track_caller]
+pub fn plot_card<D, F, C>(
+    nb: &mut NotebookCtx,
+    title: &str,
+    deps: F,
+    mut compute: C,
+) -> StateId<PlotCardState<D>>
+where
+    D: PartialEq + Send + Sync + 'static,
+    F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> D + 'static,
+    C: FnMut(&D) -> PlotData + 'static,
+{
+    plot_card_diff(nb, title, deps, move |_, current| compute(current))
+}
+
+/// Like [`plot_card`], but `compute` receives the previous dependency
+/// snapshot alongside the current one, for series that depend on *what*
+/// changed (e.g. drawing the old curve faded behind the new one).
+///
+/// The previous snapshot is `None` on the first run only; after
+/// [`PlotCardState::invalidate`] it is the snapshot the series were last
+/// computed from. Keeping it costs nothing beyond what
+/// [`plot_card`] already retains for change detection: one copy of the
+/// last `deps` result per card. For large inputs, return something cheap
+/// from `deps` (a generation counter, a hash, an `Arc`) rather than the
+/// data itself.
+///
+/// ```ignore
+/// cards::plot_card_diff(
+///     nb,
+///     "response",
+///     move |ctx| config.read(ctx).clone(),
+///     |previous, config| {
+///         let data = PlotData::new().line("gain", config.sweep());
+///         match previous {
+///             Some(previous) => data.line("previous", previous.sweep()),
+///             None => data,
+///         }
+///     },
+/// );
+/// ```
+#[track_caller]
+pub fn plot_card_diff<D, F, C>(
+    nb: &mut NotebookCtx,
+    title: &str,
+    deps: F,
+    compute: C,
+) -> StateId<PlotCardState<D>>
+where
+    D: PartialEq + Send + Sync + 'static,
+    F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> D + 'static,
+    C: FnMut(Option<&D>, &D) -> PlotData + 'static,
+{
+    let location = std::panic::Location::caller();
+    let source = SourceLocation::from_location(location);
+    // The call site keeps plot cards that share a title apart.
+    let key = (
+        "gorbie_plot_card",
+        title,
+        location.file(),
+        location.line(),
+        location.column(),
+    );
+    let state_id = nb.state_id_for(&key);
+    let identity = nb.card_identity(CardIdentityKey::Stateful {
+        source: Some(source.clone()),
+        state: state_id,
+        function: TypeId::of::<F>(),
+    });
+    let state = StateId::new(state_id);
+    nb.state_store.get_or_insert(state, PlotCardState::new());
+    let card = PlotCard {
+        state,
+        plot_id: state_id.with("plot"),
+        title: title.to_owned(),
+        deps,
+        compute,
+    };
+    nb.push_with_source(Box::new(card), Some(source), identity);
+    state
+}
+
+/// The card added by [`plot_card_diff`].
+struct PlotCard<D, F, C> {
+    state: StateId<PlotCardState<D>>,
+    plot_id: egui::Id,
+    title: String,
+    deps: F,
+    compute: C,
+}
+
+impl<D, F, C> Card for PlotCard<D, F, C>
+where
+    D: PartialEq + Send + Sync + 'static,
+    F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> D + 'static,
+    C: FnMut(Option<&D>, &D) -> PlotData + 'static,
+{
+    fn draw(&mut self, ctx: &mut CardCtx<'_>) {
+        let current = (self.deps)(ctx);
+        let mut state = self.state.read_mut(ctx);
+        state.refresh(current, &mut self.compute);
+        ctx.with_padding(DEFAULT_CARD_PADDING, |ctx| {
+            let plot = crate::widgets::styled_plot(&self.title)
+                .id(self.plot_id)
+                .height(PLOT_CARD_HEIGHT);
+            crate::widgets::show_styled_plot(ctx, plot, |plot_ui| {
+                for series in &state.data.series {
+                    let points = PlotPoints::Borrowed(&series.points);
+                    match series.kind {
+                        SeriesKind::Line => plot_ui.line(Line::new(&series.name, points)),
+                        SeriesKind::Points => {
+                            plot_ui.points(Points::new(&series.name, points).radius(2.0))
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    fn measure(&self, _ui: &egui::Ui) -> Option<f32> {
+        self.min_height()
+    }
+
+    fn min_height(&self) -> Option<f32> {
+        Some(PLOT_CARD_HEIGHT + DEFAULT_CARD_PADDING.sum().y)
+    }
+
+    fn state_key(&self) -> Option<DependencyKey> {
+        Some(self.state.key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recomputes_only_when_dependencies_change() {
+        let mut state = PlotCardState::new();
+        let mut runs = 0;
+        let mut compute = |_: Option<&u32>, n: &u32| {
+            runs += 1;
+            PlotData::new().line("n", vec![[0.0, *n as f64]])
+        };
+        assert!(state.refresh(1, &mut compute));
+        assert!(!state.refresh(1, &mut compute));
+        assert!(state.refresh(2, &mut compute));
+        assert_eq!(runs, 2);
+        assert_eq!(state.data().series[0].points, vec![PlotPoint::new(0.0, 2.0)]);
+    }
+
+    #[test]
+    fn invalidate_forces_exactly_one_recomputation() {
+        let mut state = PlotCardState::new();
+        let mut runs = 0;
+        let mut compute = |_: Option<&u32>, _: &u32| {
+            runs += 1;
+            PlotData::new()
+        };
+        state.refresh(1, &mut compute);
+        state.invalidate(&egui::Context::default());
+        assert!(state.refresh(1, &mut compute));
+        assert!(!state.refresh(1, &mut compute));
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn reserves_the_plot_height_before_drawing() {
+        let card = PlotCard {
+            state: StateId::<PlotCardState<u32>>::new(egui::Id::new("plot")),
+            plot_id: egui::Id::new("plot").with("plot"),
+            title: "plot".to_owned(),
+            deps: |_: &mut CardCtx<'_>| 0u32,
+            compute: |_: Option<&u32>, _: &u32| PlotData::new(),
+        };
+        let height = PLOT_CARD_HEIGHT + DEFAULT_CARD_PADDING.sum().y;
+        assert_eq!(card.min_height(), Some(height));
+    }
+
+    #[test]
+    fn invalidate_keeps_the_previous_snapshot() {
+        let mut state = PlotCardState::new();
+        let mut seen = Vec::new();
+        let mut compute = |previous: Option<&u32>, current: &u32| {
+            seen.push((previous.copied(), *current));
+            PlotData::new()
+        };
+        state.refresh(1, &mut compute);
+        state.invalidate(&egui::Context::default());
+        state.refresh(1, &mut compute);
+        assert_eq!(seen, vec![(None, 1), (Some(1), 1)]);
+    }
+
+    #[test]
+    fn diff_sees_the_previous_snapshot() {
+        let mut state = PlotCardState::new();
+        let mut seen = Vec::new();
+        let mut compute = |previous: Option<&u32>, current: &u32| {
+            seen.push((previous.copied(), *current));
+            PlotData::new()
+        };
+        state.refresh(1, &mut compute);
+        state.refresh(1, &mut compute);
+        state.refresh(3, &mut compute);
+        assert_eq!(seen, vec![(None, 1), (Some(1), 3)]);
+    }
+}
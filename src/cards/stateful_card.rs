@@ -1,16 +1,28 @@
 use std::hash::Hash;
 
 use crate::cards::Card;
-use crate::state::StateId;
+use crate::state::{DependencyKey, StateId};
 use crate::CardCtx;
 use crate::NotebookCtx;
 
 type StatefulCardFn<T> = dyn for<'a, 'b> FnMut(&'a mut CardCtx<'b>, &mut T);
 
+/// Height reserved by a [`LazyStatefulCard`] until it is initialized.
+const LAZY_PLACEHOLDER_HEIGHT: f32 = 48.0;
+
+/// Converts a card's state to and from JSON for saved sessions.
+#[cfg(feature = "serde")]
+struct StateCodec<T> {
+    save: fn(&T) -> Option<serde_json::Value>,
+    load: fn(&serde_json::Value) -> Option<T>,
+}
+
 /// A card that owns persistent state of type `T` across frames.
 pub struct StatefulCard<T> {
     state: StateId<T>,
     function: Box<StatefulCardFn<T>>,
+    #[cfg(feature = "serde")]
+    codec: Option<StateCodec<T>>,
 }
 
 impl<T> StatefulCard<T> {
@@ -21,15 +33,65 @@ impl<T> StatefulCard<T> {
         Self {
             state,
             function: Box::new(function),
+            #[cfg(feature = "serde")]
+            codec: None,
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T> StatefulCard<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Includes the card's state in saved sessions.
+    pub(crate) fn with_session_codec(mut self) -> Self {
+        self.codec = Some(StateCodec {
+            save: |value| serde_json::to_value(value).ok(),
+            load: |value| serde_json::from_value(value.clone()).ok(),
+        });
+        self
+    }
+}
+
 impl<T: Send + Sync + 'static> Card for StatefulCard<T> {
     fn draw(&mut self, ctx: &mut CardCtx<'_>) {
         let mut current = self.state.read_mut(ctx);
         (self.function)(ctx, &mut current);
     }
+
+    fn state_key(&self) -> Option<DependencyKey> {
+        Some(self.state.key())
+    }
+
+    #[cfg(feature = "serde")]
+    fn has_savable_state(&self) -> bool {
+        self.codec.is_some()
+    }
+
+    #[cfg(feature = "serde")]
+    fn save_state(&self, store: &crate::state::StateStore) -> Option<serde_json::Value> {
+        let codec = self.codec.as_ref()?;
+        let current = store.try_read(self.state)?;
+        (codec.save)(&current)
+    }
+
+    #[cfg(feature = "serde")]
+    fn load_state(&mut self, store: &crate::state::StateStore, value: &serde_json::Value) {
+        let Some(codec) = self.codec.as_ref() else {
+            return;
+        };
+        let Some(mut current) = store.try_read_mut(self.state) else {
+            return;
+        };
+        match (codec.load)(value) {
+            Some(loaded) => *current = loaded,
+            None => log::warn!(
+                "saved session state does not match {}",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
 }
 
 /// Creates a card with persistent state keyed by `key`, initialized with `init`.
@@ -48,3 +110,72 @@ where
 {
     nb.state(key, init, function)
 }
+
+/// A stateful card whose state is only built once the card scrolls into view.
+///
+/// Until then the card reserves a blank placeholder and its state reads as
+/// `None`.
+pub struct LazyStatefulCard<T> {
+    state: StateId<Option<T>>,
+    init: Option<Box<dyn FnOnce() -> T>>,
+    function: Box<StatefulCardFn<T>>,
+}
+
+impl<T> LazyStatefulCard<T> {
+    pub(crate) fn new(
+        state: StateId<Option<T>>,
+        init: impl FnOnce() -> T + 'static,
+        function: impl for<'a, 'b> FnMut(&'a mut CardCtx<'b>, &mut T) + 'static,
+    ) -> Self {
+        Self {
+            state,
+            init: Some(Box::new(init)),
+            function: Box::new(function),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Card for LazyStatefulCard<T> {
+    fn draw(&mut self, ctx: &mut CardCtx<'_>) {
+        let mut current = self.state.read_mut(ctx);
+        if current.is_none() {
+            let top =
+                egui::Rect::from_min_size(ctx.cursor().min, egui::vec2(ctx.available_width(), 1.0));
+            let init = match self.init.take() {
+                Some(init) if ctx.is_rect_visible(top) => init,
+                init => {
+                    self.init = init;
+                    let width = ctx.available_width();
+                    ctx.allocate_space(egui::vec2(width, LAZY_PLACEHOLDER_HEIGHT));
+                    return;
+                }
+            };
+            *current = Some(init());
+        }
+        if let Some(value) = current.as_mut() {
+            (self.function)(ctx, value);
+        }
+    }
+
+    fn state_key(&self) -> Option<DependencyKey> {
+        Some(self.state.key())
+    }
+}
+
+/// Like [`stateful_card`], but `init` only runs when the card first scrolls
+/// into view, so expensive setup is skipped for cards nobody looks at.
+///
+/// The returned handle reads `None` until then.
+#[track_caller]
+pub fn lazy_stateful_card<K, T>(
+    nb: &mut NotebookCtx,
+    key: &K,
+    init: impl FnOnce() -> T + 'static,
+    function: impl for<'a, 'b> FnMut(&'a mut CardCtx<'b>, &mut T) + 'static,
+) -> StateId<Option<T>>
+where
+    K: Hash + ?Sized,
+    T: Send + Sync + 'static,
+{
+    nb.lazy_state(key, init, function)
+}
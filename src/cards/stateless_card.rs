@@ -1,23 +1,42 @@
 use crate::cards::Card;
+use crate::state::{DependencyKey, StateId};
 use crate::CardCtx;
 use crate::NotebookCtx;
 
+type StatelessCardFn<R> = dyn for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> R;
+
 /// A card with no persistent state; redrawn from scratch each frame.
-pub struct StatelessCard {
-    function: Box<dyn for<'a, 'b> FnMut(&'a mut CardCtx<'b>)>,
+///
+/// Cards added with [`NotebookCtx::view_with_output`] keep whatever the
+/// function returns in the state store under `output`, so the notebook body
+/// can react to the card's last frame.
+pub struct StatelessCard<R = ()> {
+    output: Option<StateId<Option<R>>>,
+    function: Box<StatelessCardFn<R>>,
 }
 
-impl StatelessCard {
-    pub(crate) fn new(function: impl for<'a, 'b> FnMut(&'a mut CardCtx<'b>) + 'static) -> Self {
+impl<R> StatelessCard<R> {
+    pub(crate) fn new(
+        output: Option<StateId<Option<R>>>,
+        function: impl for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> R + 'static,
+    ) -> Self {
         Self {
+            output,
             function: Box::new(function),
         }
     }
 }
 
-impl Card for StatelessCard {
+impl<R: Send + Sync + 'static> Card for StatelessCard<R> {
     fn draw(&mut self, ctx: &mut CardCtx<'_>) {
-        (self.function)(ctx);
+        let value = (self.function)(ctx);
+        if let Some(output) = self.output {
+            *output.read_mut(ctx) = Some(value);
+        }
+    }
+
+    fn state_key(&self) -> Option<DependencyKey> {
+        self.output.map(StateId::key)
     }
 }
 
@@ -112,3 +112,845 @@ impl<T: std::fmt::Debug> std::fmt::Debug for ComputedState<T> {
             .finish()
     }
 }
+
+/// Undo/redo history for an editable value, e.g. the settings held in a
+/// card's state.
+///
+/// Edit the value through [`value_mut`](Self::value_mut) and call
+/// [`commit`](Self::commit) once per frame afterwards; a commit records an
+/// undo step whenever the value changed. Edits that follow each other within
+/// the coalescing window (default 0.5 s, see [`coalesce`](Self::coalesce)) —
+/// a slider drag, typing into a field — collapse into a single step. The
+/// number of retained undo steps is capped by
+/// [`max_depth`](Self::max_depth).
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    value: T,
+    committed: T,
+    undo: std::collections::VecDeque<T>,
+    redo: Vec<T>,
+    max_depth: usize,
+    coalesce: f64,
+    last_edit: Option<f64>,
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    /// Creates a history with `value` as its initial, non-undoable state.
+    pub fn new(value: T) -> Self {
+        Self {
+            committed: value.clone(),
+            value,
+            undo: std::collections::VecDeque::new(),
+            redo: Vec::new(),
+            max_depth: 100,
+            coalesce: 0.5,
+            last_edit: None,
+        }
+    }
+
+    /// Caps the number of retained undo steps (default 100).
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth.max(1);
+        self
+    }
+
+    /// Sets the window in seconds within which consecutive edits are merged
+    /// into one undo step (default 0.5 s). `0.0` records every change.
+    pub fn coalesce(mut self, seconds: f64) -> Self {
+        self.coalesce = seconds.max(0.0);
+        self
+    }
+
+    /// Returns a shared reference to the current value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the current value. Changes become
+    /// undoable on the next [`commit`](Self::commit).
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Records pending edits as an undo step, using the egui clock for
+    /// coalescing.
+    pub fn commit(&mut self, ctx: &eframe::egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.commit_at(now);
+    }
+
+    /// Like [`commit`](Self::commit), with an explicit timestamp in seconds.
+    pub fn commit_at(&mut self, now: f64) {
+        if self.value == self.committed {
+            return;
+        }
+        let coalesced = self
+            .last_edit
+            .is_some_and(|last| now - last < self.coalesce);
+        let previous = std::mem::replace(&mut self.committed, self.value.clone());
+        if !coalesced {
+            self.undo.push_back(previous);
+            while self.undo.len() > self.max_depth {
+                self.undo.pop_front();
+            }
+        }
+        self.redo.clear();
+        self.last_edit = Some(now);
+    }
+
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is an undone step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Restores the previous state. Returns `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.undo.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, previous.clone());
+        self.redo.push(current);
+        self.committed = previous;
+        self.last_edit = None;
+        true
+    }
+
+    /// Re-applies the most recently undone state. Returns `false` if there
+    /// is none.
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, next.clone());
+        self.undo.push_back(current);
+        self.committed = next;
+        self.last_edit = None;
+        true
+    }
+}
+
+impl<T: Clone + PartialEq + Default> Default for History<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Fixed-capacity history of the most recent values, e.g. per-run metrics
+/// feeding a live plot.
+///
+/// [`push`](Self::push) appends a value and evicts the oldest once the
+/// buffer holds [`capacity`](Self::capacity) entries, so long-running loops
+/// keep a bounded window without manual trimming. Iteration runs from
+/// oldest to newest.
+#[derive(Clone, Debug)]
+pub struct RingHistory<T> {
+    values: std::collections::VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingHistory<T> {
+    /// Creates an empty history retaining at most `capacity` values
+    /// (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            values: std::collections::VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of retained values.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, evicting the oldest values if the history is
+    /// now over it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.values.len() > self.capacity {
+            self.values.pop_front();
+        }
+    }
+
+    /// Appends `value`, returning the oldest value if it had to be evicted
+    /// to make room.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.values.len() == self.capacity {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        evicted
+    }
+
+    /// Returns the number of retained values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no values are retained.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` once the history holds `capacity` values and the next
+    /// push will evict.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// Removes all values; the capacity is kept.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Returns the oldest retained value.
+    pub fn first(&self) -> Option<&T> {
+        self.values.front()
+    }
+
+    /// Returns the most recently pushed value.
+    pub fn last(&self) -> Option<&T> {
+        self.values.back()
+    }
+
+    /// Returns the value at `index`, counting from the oldest.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Iterates from the oldest to the newest value.
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Returns the values as two contiguous slices, oldest first; the second
+    /// is empty unless the buffer has wrapped.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.values.as_slices()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingHistory<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+impl<T> std::ops::Index<usize> for RingHistory<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+}
+
+impl<T> Extend<T> for RingHistory<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Coalesces repaint requests from background work to at most one per
+/// frame.
+///
+/// Workers that call `request_repaint` on every update can flood the event
+/// loop when several run at once. Instead, they call
+/// [`notify`](Self::notify) on a shared throttle: the first notification
+/// in a frame schedules a repaint one [`interval`](Self::interval) out, and
+/// later ones in the same frame are dropped. Nothing is queued or timed on
+/// the throttle itself, so dropping it mid-flight is harmless.
+///
+/// The notebook installs one per context with the interval from
+/// [`NotebookConfig::with_repaint_interval`](crate::NotebookConfig::with_repaint_interval);
+/// get it with [`of`](Self::of).
+#[derive(Clone)]
+pub struct RepaintThrottle {
+    inner: std::sync::Arc<ThrottleInner>,
+}
+
+struct ThrottleInner {
+    ctx: eframe::egui::Context,
+    interval: std::time::Duration,
+    requested_frame: std::sync::atomic::AtomicU64,
+}
+
+impl RepaintThrottle {
+    /// Default interval: one frame at 60 Hz.
+    pub const DEFAULT_INTERVAL: std::time::Duration = std::time::Duration::from_micros(16_667);
+
+    /// A throttle for `ctx` that wakes the UI at most once per `interval`.
+    pub fn new(ctx: &eframe::egui::Context, interval: std::time::Duration) -> Self {
+        Self {
+            inner: std::sync::Arc::new(ThrottleInner {
+                ctx: ctx.clone(),
+                interval,
+                requested_frame: std::sync::atomic::AtomicU64::new(u64::MAX),
+            }),
+        }
+    }
+
+    /// The throttle installed on `ctx`, or a new one with
+    /// [`DEFAULT_INTERVAL`](Self::DEFAULT_INTERVAL) if there is none yet.
+    pub fn of(ctx: &eframe::egui::Context) -> Self {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_insert_with(Self::id(), || {
+                Self::new(ctx, Self::DEFAULT_INTERVAL)
+            })
+            .clone()
+        })
+    }
+
+    /// Makes this the throttle returned by [`of`](Self::of) for its context.
+    pub fn install(&self) {
+        let throttle = self.clone();
+        self.inner
+            .ctx
+            .data_mut(|data| data.insert_temp(Self::id(), throttle));
+    }
+
+    fn id() -> eframe::egui::Id {
+        eframe::egui::Id::new("gorbie_repaint_throttle")
+    }
+
+    /// The minimum time between repaints this throttle schedules.
+    pub fn interval(&self) -> std::time::Duration {
+        self.inner.interval
+    }
+
+    /// Signals that new data is ready. Callable from any thread.
+    pub fn notify(&self) {
+        let frame = self.inner.ctx.cumulative_frame_nr();
+        let previous = self
+            .inner
+            .requested_frame
+            .swap(frame, std::sync::atomic::Ordering::AcqRel);
+        if previous != frame {
+            self.inner.ctx.request_repaint_after(self.inner.interval);
+        }
+    }
+}
+
+impl std::fmt::Debug for RepaintThrottle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepaintThrottle")
+            .field("interval", &self.inner.interval)
+            .finish()
+    }
+}
+
+/// A change notification from outside the notebook — a file watcher, a
+/// socket, a timer.
+///
+/// A signal is a shared generation counter. Whoever observes the external
+/// event (usually a background thread holding a clone) calls
+/// [`bump`](Self::bump); cards compare the generation against the one they
+/// last saw with [`changed`](Self::changed) and recompute when it moved.
+/// Once [`attach`](Self::attach)ed to an egui context, every bump also
+/// wakes the UI through the context's [`RepaintThrottle`], so the notebook
+/// reacts without polling.
+///
+/// ```ignore
+/// // In the card, with `signal` handed to a watcher thread earlier:
+/// signal.attach(ctx.ctx());
+/// if signal.changed(&mut state.seen) {
+///     state.contents.spawn(move || std::fs::read_to_string(&path));
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Signal {
+    inner: std::sync::Arc<SignalInner>,
+}
+
+#[derive(Default)]
+struct SignalInner {
+    generation: std::sync::atomic::AtomicU64,
+    repaint: std::sync::Mutex<Option<eframe::egui::Context>>,
+}
+
+impl Signal {
+    /// Creates a signal at generation 0, not attached to any context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the signal as changed and notifies the attached context's
+    /// [`RepaintThrottle`], if any. Callable from any thread; inside
+    /// [`batch`] the bump is deferred until the batch ends.
+    pub fn bump(&self) {
+        let deferred = PENDING_BUMPS.with(|pending| match pending.borrow_mut().as_mut() {
+            Some(pending) => {
+                if !pending
+                    .iter()
+                    .any(|signal| std::sync::Arc::ptr_eq(&signal.inner, &self.inner))
+                {
+                    pending.push(self.clone());
+                }
+                true
+            }
+            None => false,
+        });
+        if !deferred {
+            self.bump_now();
+        }
+    }
+
+    fn bump_now(&self) {
+        self.inner
+            .generation
+            .fetch_add(1, std::sync::atomic::Ordering::Release);
+        let repaint = self.inner.repaint.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ctx) = repaint.as_ref() {
+            RepaintThrottle::of(ctx).notify();
+        }
+    }
+
+    /// The number of bumps so far.
+    pub fn generation(&self) -> u64 {
+        self.inner
+            .generation
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Requests a repaint of `ctx` on every future bump. Cheap to call every
+    /// frame.
+    pub fn attach(&self, ctx: &eframe::egui::Context) {
+        let mut repaint = self.inner.repaint.lock().unwrap_or_else(|e| e.into_inner());
+        if repaint.is_none() {
+            *repaint = Some(ctx.clone());
+        }
+    }
+
+    /// Returns `true` if the signal was bumped since the generation stored
+    /// in `seen`, or if `seen` is `None` (nothing observed yet), and records
+    /// the current generation in `seen`.
+    pub fn changed(&self, seen: &mut Option<u64>) -> bool {
+        let generation = self.generation();
+        let changed = *seen != Some(generation);
+        *seen = Some(generation);
+        changed
+    }
+}
+
+impl std::fmt::Debug for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signal")
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+
+thread_local! {
+    /// Signals bumped inside [`batch`] on this thread, bumped for real when
+    /// the outermost batch ends.
+    static PENDING_BUMPS: std::cell::RefCell<Option<Vec<Signal>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `f`, deferring every [`Signal::bump`] it makes on this thread until
+/// it returns.
+///
+/// Each signal bumped inside is bumped exactly once at the end, however
+/// often `f` bumped it, so consumers that depend on several signals see
+/// one coherent change and recompute once instead of after every write:
+///
+/// ```ignore
+/// dataflow::batch(|| {
+///     write_config(&config_signal);
+///     write_dataset(&dataset_signal);
+/// });
+/// ```
+///
+/// Nested batches flush with the outermost one, also when `f` panics.
+/// Bumps from other threads are not deferred.
+pub fn batch<R>(f: impl FnOnce() -> R) -> R {
+    struct Flush {
+        outermost: bool,
+    }
+
+    impl Drop for Flush {
+        fn drop(&mut self) {
+            if !self.outermost {
+                return;
+            }
+            let pending = PENDING_BUMPS.with(|pending| pending.borrow_mut().take());
+            for signal in pending.unwrap_or_default() {
+                signal.bump_now();
+            }
+        }
+    }
+
+    let outermost = PENDING_BUMPS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let outermost = pending.is_none();
+        if outermost {
+            *pending = Some(Vec::new());
+        }
+        outermost
+    });
+    let _flush = Flush { outermost };
+    f()
+}
+
+/// Error returned by [`Worker`] when its background thread has exited.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkerDisconnected;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Display for WorkerDisconnected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("worker disconnected")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::error::Error for WorkerDisconnected {}
+
+/// A long-lived background thread that answers one request at a time.
+///
+/// Where [`ComputedState`] spawns a fresh thread per computation, a `Worker`
+/// keeps its thread (and whatever expensive state it builds, e.g. a GPU
+/// client) alive across requests. The worker loop receives requests on an
+/// [`mpsc::Receiver`](std::sync::mpsc::Receiver) and answers on an
+/// [`mpsc::Sender`](std::sync::mpsc::Sender); the card owns the `Worker` and
+/// calls [`poll`](Self::poll) every frame.
+///
+/// At most one request is in flight: [`spawn`](Self::spawn) drops the request
+/// (and returns `Ok(false)`) while the previous request is still running, or
+/// until [`min_interval`](Self::min_interval) has passed since the last
+/// request was sent. Pacing an auto-running loop with the interval keeps it from
+/// re-spawning work every frame.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Worker<Req, Res> {
+    sender: std::sync::mpsc::Sender<Req>,
+    receiver: std::sync::Mutex<std::sync::mpsc::Receiver<Res>>,
+    in_flight: bool,
+    min_interval: std::time::Duration,
+    last_spawn: Option<std::time::Instant>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<Req, Res> Worker<Req, Res>
+where
+    Req: Send + 'static,
+    Res: Send + 'static,
+{
+    /// Starts `worker` on a background thread.
+    ///
+    /// The loop should answer every request it receives and return once the
+    /// request channel closes (i.e. when the `Worker` is dropped).
+    pub fn new(
+        worker: impl FnOnce(std::sync::mpsc::Receiver<Req>, std::sync::mpsc::Sender<Res>)
+            + Send
+            + 'static,
+    ) -> Self {
+        let (request_tx, request_rx) = std::sync::mpsc::channel();
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || worker(request_rx, result_tx));
+        Self {
+            sender: request_tx,
+            receiver: std::sync::Mutex::new(result_rx),
+            in_flight: false,
+            min_interval: std::time::Duration::ZERO,
+            last_spawn: None,
+        }
+    }
+
+    /// Sets the minimum time between sent requests (default zero, i.e.
+    /// unpaced).
+    pub fn min_interval(mut self, interval: std::time::Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Changes the minimum time between sent requests, e.g. from a rate
+    /// slider.
+    pub fn set_min_interval(&mut self, interval: std::time::Duration) {
+        self.min_interval = interval;
+    }
+
+    /// Returns how long [`spawn`](Self::spawn) will still be held back by
+    /// [`min_interval`](Self::min_interval), or `None` if it may send now.
+    /// Pass the wait to `request_repaint_after` to wake up in time.
+    pub fn cooldown(&self) -> Option<std::time::Duration> {
+        let elapsed = self.last_spawn?.elapsed();
+        (elapsed < self.min_interval).then(|| self.min_interval - elapsed)
+    }
+
+    /// Sends `request` to the worker unless a request is already in flight
+    /// or the worker is still in its [`cooldown`](Self::cooldown). Returns
+    /// whether it was sent; a request that wasn't is dropped.
+    pub fn spawn(&mut self, request: Req) -> Result<bool, WorkerDisconnected> {
+        if self.in_flight || self.cooldown().is_some() {
+            return Ok(false);
+        }
+        self.sender.send(request).map_err(|_| WorkerDisconnected)?;
+        self.in_flight = true;
+        self.last_spawn = Some(std::time::Instant::now());
+        Ok(true)
+    }
+
+    /// Returns the answer to the in-flight request once it is available.
+    ///
+    /// Yields `Some(Err(WorkerDisconnected))` if the worker thread exited
+    /// (e.g. panicked) instead of answering.
+    pub fn poll(&mut self) -> Option<Result<Res, WorkerDisconnected>> {
+        let receiver = self
+            .receiver
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.in_flight = false;
+                Some(Ok(result))
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => None,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                if !self.in_flight {
+                    return None;
+                }
+                self.in_flight = false;
+                Some(Err(WorkerDisconnected))
+            }
+        }
+    }
+
+    /// Returns `true` if a request has been sent and not yet answered.
+    pub fn is_running(&self) -> bool {
+        self.in_flight
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<Req, Res> std::fmt::Debug for Worker<Req, Res> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Worker")
+            .field("running", &self.in_flight)
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait_for<Req, Res>(worker: &mut Worker<Req, Res>) -> Result<Res, WorkerDisconnected>
+    where
+        Req: Send + 'static,
+        Res: Send + 'static,
+    {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(result) = worker.poll() {
+                return result;
+            }
+            assert!(Instant::now() < deadline, "worker did not answer in time");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn ring_history_evicts_oldest_on_wraparound() {
+        let mut history = RingHistory::new(3);
+        assert_eq!(history.push(1), None);
+        assert_eq!(history.push(2), None);
+        assert_eq!(history.push(3), None);
+        assert!(history.is_full());
+        assert_eq!(history.push(4), Some(1));
+        assert_eq!(history.push(5), Some(2));
+
+        assert_eq!(history.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+        assert_eq!(history.first(), Some(&3));
+        assert_eq!(history.last(), Some(&5));
+        let (head, tail) = history.as_slices();
+        assert_eq!([head, tail].concat(), [3, 4, 5]);
+    }
+
+    #[test]
+    fn ring_history_shrinking_capacity_keeps_newest() {
+        let mut history = RingHistory::new(4);
+        history.extend(0..10);
+        assert_eq!(history.iter().copied().collect::<Vec<_>>(), [6, 7, 8, 9]);
+
+        history.set_capacity(2);
+        assert_eq!(history.iter().copied().collect::<Vec<_>>(), [8, 9]);
+        assert_eq!(history.push(10), Some(8));
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.capacity(), 2);
+        assert_eq!(RingHistory::<u8>::new(0).capacity(), 1);
+    }
+
+    #[test]
+    fn signal_reports_changes_once() {
+        let signal = Signal::new();
+        let mut seen = None;
+        assert!(signal.changed(&mut seen), "first observation counts as a change");
+        assert!(!signal.changed(&mut seen));
+
+        let remote = signal.clone();
+        std::thread::spawn(move || remote.bump())
+            .join()
+            .expect("bump thread");
+        assert!(signal.changed(&mut seen));
+        assert!(!signal.changed(&mut seen));
+        assert_eq!(signal.generation(), 1);
+    }
+
+    #[test]
+    fn batch_coalesces_bumps_into_one_recompute() {
+        let config = Signal::new();
+        let dataset = Signal::new();
+        let mut seen = (None, None);
+        let runs = std::cell::Cell::new(0);
+        let derive = |seen: &mut (Option<u64>, Option<u64>)| {
+            let config_changed = config.changed(&mut seen.0);
+            let dataset_changed = dataset.changed(&mut seen.1);
+            if config_changed || dataset_changed {
+                runs.set(runs.get() + 1);
+            }
+        };
+        derive(&mut seen);
+        assert_eq!(runs.get(), 1, "initial computation");
+
+        batch(|| {
+            config.bump();
+            batch(|| dataset.bump());
+            derive(&mut seen);
+            config.bump();
+        });
+        assert_eq!(runs.get(), 1, "no recompute while the batch is open");
+        assert_eq!(config.generation(), 1);
+        assert_eq!(dataset.generation(), 1);
+
+        derive(&mut seen);
+        derive(&mut seen);
+        assert_eq!(runs.get(), 2, "one recompute for the whole batch");
+    }
+
+    #[test]
+    fn history_undo_redo_and_coalescing() {
+        let mut history = History::new(0).coalesce(0.5);
+        assert!(!history.can_undo());
+
+        // A drag: three edits in quick succession become one step.
+        for (t, v) in [(1.0, 1), (1.1, 2), (1.2, 3)] {
+            *history.value_mut() = v;
+            history.commit_at(t);
+        }
+        // A separate edit later on.
+        *history.value_mut() = 10;
+        history.commit_at(5.0);
+
+        assert!(history.undo());
+        assert_eq!(*history.value(), 3);
+        assert!(history.undo());
+        assert_eq!(*history.value(), 0);
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert_eq!(*history.value(), 3);
+
+        // A fresh edit discards the redo branch.
+        *history.value_mut() = 7;
+        history.commit_at(9.0);
+        assert!(!history.can_redo());
+        assert!(history.undo());
+        assert_eq!(*history.value(), 3);
+    }
+
+    #[test]
+    fn history_caps_depth() {
+        let mut history = History::new(0).coalesce(0.0).max_depth(2);
+        for v in 1..=5 {
+            *history.value_mut() = v;
+            history.commit_at(v as f64);
+        }
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert_eq!(*history.value(), 3);
+    }
+
+    #[test]
+    fn worker_round_trip() {
+        let mut worker = Worker::new(|requests, results| {
+            for n in requests {
+                let _ = results.send(n * 2u32);
+            }
+        });
+        assert!(!worker.is_running());
+
+        assert_eq!(worker.spawn(21), Ok(true));
+        assert!(worker.is_running());
+        assert_eq!(wait_for(&mut worker), Ok(42));
+        assert!(!worker.is_running());
+
+        assert_eq!(worker.spawn(5), Ok(true));
+        assert_eq!(wait_for(&mut worker), Ok(10));
+    }
+
+    #[test]
+    fn worker_rejects_spawn_while_in_flight() {
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let mut worker = Worker::new(move |requests, results| {
+            for n in requests {
+                let _ = release_rx.recv();
+                let _ = results.send(n * 2u32);
+            }
+        });
+        assert_eq!(worker.spawn(21), Ok(true));
+        assert_eq!(worker.spawn(100), Ok(false), "second request in flight");
+        release_tx.send(()).expect("release the worker");
+        assert_eq!(wait_for(&mut worker), Ok(42));
+        assert!(worker.poll().is_none(), "rejected request was not sent");
+    }
+
+    #[test]
+    fn worker_min_interval_holds_back_spawns() {
+        let mut worker = Worker::new(|requests, results| {
+            for n in requests {
+                let _ = results.send(n + 1u32);
+            }
+        })
+        .min_interval(Duration::from_secs(60));
+        assert_eq!(worker.cooldown(), None, "first request is never held back");
+
+        assert_eq!(worker.spawn(1), Ok(true));
+        assert_eq!(wait_for(&mut worker), Ok(2));
+        assert!(worker.cooldown().is_some());
+        assert_eq!(worker.spawn(2), Ok(false), "request during cooldown");
+        assert!(!worker.is_running(), "request during cooldown was dropped");
+
+        worker.set_min_interval(Duration::ZERO);
+        assert_eq!(worker.cooldown(), None);
+        assert_eq!(worker.spawn(3), Ok(true));
+        assert_eq!(wait_for(&mut worker), Ok(4));
+    }
+
+    #[test]
+    fn worker_reports_disconnect() {
+        let mut worker: Worker<u32, u32> = Worker::new(|requests, _results| {
+            let _ = requests.recv();
+        });
+        assert_eq!(worker.spawn(1), Ok(true));
+        assert_eq!(wait_for(&mut worker), Err(WorkerDisconnected));
+        assert!(!worker.is_running());
+        assert!(worker.poll().is_none());
+    }
+}
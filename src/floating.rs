@@ -7,7 +7,6 @@ use eframe::egui;
 
 use crate::card_ctx::{CardCtx, GRID_ROW_MODULE};
 use crate::state;
-use crate::themes;
 
 // ── scroll info (written by the notebook, read by floats) ────────────
 
@@ -85,6 +84,35 @@ fn right_outside_ratio(rect: egui::Rect, viewport: egui::Rect) -> f32 {
     (outside_width / width).clamp(0.0, 1.0)
 }
 
+/// Width of the drag handle that always stays inside the viewport, so a
+/// float can't be dragged (or left by a window resize) out of reach.
+const MIN_HANDLE_VISIBLE: f32 = 48.0;
+
+/// Clamps a float's screen position so its drag handle stays reachable.
+/// Viewport-fixed floats stay within `viewport` vertically as well;
+/// content-anchored ones may scroll away but not above the content's top.
+fn clamp_handle_pos(
+    pos: egui::Pos2,
+    card_width: f32,
+    anchor: Anchor,
+    viewport: egui::Rect,
+    content_top: f32,
+) -> egui::Pos2 {
+    let visible = MIN_HANDLE_VISIBLE.min(card_width);
+    let x = pos.x.clamp(
+        viewport.left() - card_width + visible,
+        (viewport.right() - visible).max(viewport.left()),
+    );
+    let y = match anchor {
+        Anchor::Viewport => pos.y.clamp(
+            viewport.top(),
+            (viewport.bottom() - GRID_ROW_MODULE).max(viewport.top()),
+        ),
+        Anchor::Content => pos.y.max(content_top),
+    };
+    egui::pos2(x, y)
+}
+
 fn content_to_screen(pos: egui::Pos2, info: &NotebookScrollInfo) -> egui::Pos2 {
     egui::pos2(pos.x, pos.y - info.scroll_y + info.viewport_top)
 }
@@ -145,6 +173,21 @@ pub fn show_floating_card(
         (Anchor::Content, Some(info)) => content_to_screen(fstate.pos, info),
         _ => fstate.pos,
     };
+    let (viewport, content_top) = match &scroll_info {
+        Some(info) => (info.clip_rect, info.viewport_top - info.scroll_y),
+        None => (egui_ctx.content_rect(), f32::NEG_INFINITY),
+    };
+    let clamped = clamp_handle_pos(screen_pos, card_width, fstate.anchor, viewport, content_top);
+    let screen_pos = if clamped == screen_pos {
+        screen_pos
+    } else {
+        fstate.pos = match (fstate.anchor, &scroll_info) {
+            (Anchor::Content, Some(info)) => screen_to_content(clamped, info),
+            _ => clamped,
+        };
+        egui_ctx.memory_mut(|mem| mem.data.insert_temp(float_state_id, fstate));
+        clamped
+    };
 
     let area_order = match fstate.anchor {
         Anchor::Content => egui::Order::Foreground,
@@ -159,6 +202,7 @@ pub fn show_floating_card(
         .constrain_to(egui::Rect::EVERYTHING);
 
     let mut handle_clicked = false;
+    let mut reset_requested = false;
 
     area.show(egui_ctx, |ui| {
         let resp = draw_card_chrome(
@@ -208,6 +252,7 @@ pub fn show_floating_card(
         }
 
         handle_clicked = resp.handle_clicked;
+        reset_requested = resp.reset_requested;
 
         // Track the content-space extent of this float for scroll area sizing.
         if fstate.anchor == Anchor::Content {
@@ -216,8 +261,9 @@ pub fn show_floating_card(
         }
     });
 
-    if handle_clicked {
-        // Clean up persisted state so next show starts fresh.
+    if handle_clicked || reset_requested {
+        // Clean up persisted state so next show starts fresh (at
+        // `initial_pos`).
         egui_ctx.memory_mut(|mem| {
             mem.data.remove_temp::<FloatState>(float_state_id);
         });
@@ -231,6 +277,7 @@ pub fn show_floating_card(
 struct CardChromeResponse {
     card_rect: egui::Rect,
     handle_clicked: bool,
+    reset_requested: bool,
     drag_delta: egui::Vec2,
     dragged: bool,
     layer_id: egui::LayerId,
@@ -245,7 +292,7 @@ fn draw_card_chrome(
     draw_body: &mut dyn FnMut(&mut CardCtx<'_>),
 ) -> CardChromeResponse {
     let outline = ui.visuals().widgets.noninteractive.bg_stroke.color;
-    let shadow_color = themes::ral(9004);
+    let shadow_color = ui.visuals().popup_shadow.color;
     let shadow = egui::epaint::Shadow {
         offset: [6, 6],
         blur: 0,
@@ -257,7 +304,7 @@ fn draw_card_chrome(
         .fill(ui.visuals().window_fill)
         .stroke(egui::Stroke::new(1.0, outline))
         .shadow(shadow)
-        .corner_radius(0.0)
+        .corner_radius(crate::themes::corner_radius(ui.ctx()))
         .inner_margin(egui::Margin::ZERO);
 
     let background_idx = ui.painter().add(egui::Shape::Noop);
@@ -339,7 +386,7 @@ fn draw_card_chrome(
 
     let show_stripes = handle_resp.hovered() || is_dragging;
     if show_stripes {
-        let stripe_color = themes::ral(9004);
+        let stripe_color = ui.visuals().popup_shadow.color;
         let stripe_stroke = egui::Stroke::new(1.0, stripe_color);
         let stripe_x = handle_rect.x_range();
         let stripe_spacing = 3.0;
@@ -355,9 +402,18 @@ fn draw_card_chrome(
         crate::show_postit_tooltip(ui, &handle_resp, tooltip);
     }
 
+    let mut reset_requested = false;
+    handle_resp.context_menu(|ui| {
+        if ui.button("Reset position").clicked() {
+            reset_requested = true;
+            ui.close();
+        }
+    });
+
     CardChromeResponse {
         card_rect,
         handle_clicked: handle_resp.clicked(),
+        reset_requested,
         drag_delta,
         dragged: is_dragging,
         layer_id: handle_resp.layer_id,
@@ -1,5 +1,5 @@
-use crate::themes::{industrial_dark, industrial_fonts, industrial_light};
-use crate::{HeadlessCaptureConfig, NotebookCore, NOTEBOOK_MIN_HEIGHT};
+use crate::themes::industrial_fonts;
+use crate::{CaptureStyle, HeadlessCaptureConfig, NotebookCore, NOTEBOOK_MIN_HEIGHT};
 use dark_light::Mode;
 use eframe::egui;
 use egui_wgpu::wgpu;
@@ -33,6 +33,32 @@ pub(super) fn run_headless(
     config: HeadlessCaptureConfig,
 ) -> HeadlessResult<()> {
     let mut runner = HeadlessWgpuRunner::new(config)?;
+    let (light, dark) = core.config.styles();
+    let capture_light = match &core.config.capture_style {
+        CaptureStyle::Print => Some(crate::themes::industrial_print()),
+        CaptureStyle::Custom(style) => Some(style.as_ref().clone()),
+        CaptureStyle::Notebook => None,
+    };
+    crate::set_reduced_motion(&runner.ctx, core.config.reduced_motion);
+    #[cfg(feature = "markdown")]
+    crate::widgets::code_block::set_code_line_numbers(&runner.ctx, core.config.code_line_numbers);
+    match capture_light {
+        Some(mut style) => {
+            if core.config.reduced_motion {
+                crate::reduce_motion_style(&mut style);
+            }
+            runner.ctx.set_style_of(egui::Theme::Light, style);
+            runner.ctx.set_theme(egui::Theme::Light);
+        }
+        None => {
+            runner.ctx.set_style_of(egui::Theme::Light, light);
+            runner.ctx.set_style_of(egui::Theme::Dark, dark);
+            if let Some(theme) = core.config.forced_theme {
+                runner.ctx.set_theme(theme);
+            }
+        }
+    }
+    crate::themes::set_corner_radius(&runner.ctx, core.config.corner_radius);
     runner.capture_cards(&mut core)
 }
 
@@ -60,8 +86,6 @@ impl HeadlessWgpuRunner {
         // always show their contents.
         crate::mark_headless(&ctx);
         ctx.set_fonts(industrial_fonts());
-        ctx.set_style_of(egui::Theme::Light, industrial_light());
-        ctx.set_style_of(egui::Theme::Dark, industrial_dark());
         let theme = match dark_light::detect() {
             Ok(Mode::Light) => egui::ThemePreference::Light,
             Ok(Mode::Dark) => egui::ThemePreference::Dark,
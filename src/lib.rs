@@ -25,8 +25,13 @@ pub mod dataflow;
 pub(crate) mod floating;
 #[cfg(not(target_arch = "wasm32"))]
 mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+mod perf_hud;
+mod palette;
 /// Convenient glob import of common types and constants.
 pub mod prelude;
+#[cfg(feature = "serde")]
+mod share;
 /// Notebook-wide search bar — opt-in via [`CardCtx::search`].
 pub mod search;
 /// Thread-safe state management via [`StateId`](state::StateId) handles.
@@ -36,6 +41,8 @@ pub mod state;
 pub mod telemetry;
 /// Visual themes, RAL colors, and widget style structs.
 pub mod themes;
+/// Formatting helpers for durations and counts.
+pub mod util;
 /// Built-in widgets: buttons, fields, sliders, progress bars, and more.
 pub mod widgets;
 
@@ -47,6 +54,7 @@ use crate::themes::industrial_fonts;
 use crate::themes::industrial_light;
 use eframe::egui::{self};
 use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
@@ -87,7 +95,6 @@ impl SourceLocation {
             .replace("{column}", &column.to_string())
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
     fn file_line_column(&self) -> String {
         let file = &self.file;
         let line = self.line;
@@ -154,16 +161,70 @@ impl EditorCommand {
 
 struct CardEntry {
     card: Box<dyn cards::Card + 'static>,
-    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
     source: Option<SourceLocation>,
     identity: egui::Id,
 }
 
+/// State reads recorded per card identity on the cards' last draws, kept
+/// across frames so cards skipped by virtualization keep their edges.
+#[derive(Default)]
+struct DependencyLog {
+    reads: std::collections::HashMap<egui::Id, Vec<state::DependencyKey>>,
+}
+
+impl DependencyLog {
+    fn record(
+        &mut self,
+        reads: Vec<(egui::Id, Vec<state::DependencyKey>)>,
+        cards: &[CardEntry],
+    ) {
+        self.reads.extend(reads);
+        // Forget removed cards once they make up half of the log.
+        if self.reads.len() > 2 * cards.len() {
+            let live: std::collections::HashSet<egui::Id> =
+                cards.iter().map(|entry| entry.identity).collect();
+            self.reads.retain(|identity, _| live.contains(identity));
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 struct NotebookState {
     card_detached: Vec<bool>,
     card_placeholder_sizes: Vec<egui::Vec2>,
     card_identities: Vec<Option<egui::Id>>,
+    scroll_to_card: Option<usize>,
+    /// First card reaching into the viewport last frame, for scroll
+    /// anchoring.
+    scroll_anchor: Option<ScrollAnchor>,
+    /// Scroll offset that keeps the anchor in place, applied on the next
+    /// pass.
+    scroll_correction: Option<f32>,
+    /// Frames in a row that were redone for scroll anchoring.
+    anchor_discards: u8,
+}
+
+/// Most frames in a row redone to keep the anchor in place. A card whose
+/// height animates moves the anchor every frame; past this its
+/// corrections land a frame late instead of discarding every frame.
+const MAX_ANCHOR_DISCARDS: u8 = 3;
+
+/// A card, by identity, and its top edge in content coordinates.
+#[derive(Clone, Copy, Debug)]
+struct ScrollAnchor {
+    identity: egui::Id,
+    top: f32,
+}
+
+impl ScrollAnchor {
+    /// How far the anchor moved, if the card with `identity` now at `top`
+    /// is the anchor and has moved at all. Identities are unique per
+    /// frame, so the anchor is found even after cards above it were
+    /// inserted or removed.
+    fn shift(&self, identity: egui::Id, top: f32) -> Option<f32> {
+        let shift = top - self.top;
+        (self.identity == identity && shift.abs() > 0.5).then_some(shift)
+    }
 }
 
 impl NotebookState {
@@ -195,12 +256,45 @@ impl NotebookState {
 /// Configuration for a notebook application.
 pub struct NotebookConfig {
     title: String,
+    forced_theme: Option<egui::Theme>,
+    styles: Option<Box<(egui::Style, egui::Style)>>,
+    sidebar: Option<Box<dyn FnMut(&mut egui::Ui)>>,
+    header_actions: Option<Box<dyn FnMut(&mut egui::Ui)>>,
+    footer: Option<Box<dyn FnMut(&mut egui::Ui)>>,
+    virtualize_cards: bool,
+    responsive: bool,
+    compact_width: f32,
+    scroll_anchoring: bool,
+    reduced_motion: bool,
+    corner_radius: f32,
+    #[cfg(feature = "markdown")]
+    code_line_numbers: bool,
+    repaint_interval: Option<std::time::Duration>,
     #[cfg(not(target_arch = "wasm32"))]
     editor: Option<EditorCommand>,
     #[cfg(not(target_arch = "wasm32"))]
     headless_capture: Option<HeadlessCaptureConfig>,
     #[cfg(not(target_arch = "wasm32"))]
     headless_settle_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    capture_style: CaptureStyle,
+    #[cfg(not(target_arch = "wasm32"))]
+    perf_hud: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    window_icon: Option<egui::IconData>,
+    #[cfg(not(target_arch = "wasm32"))]
+    app_id: Option<String>,
+}
+
+/// The style headless captures render with.
+#[cfg(not(target_arch = "wasm32"))]
+enum CaptureStyle {
+    /// [`themes::industrial_print`].
+    Print,
+    /// A custom light style.
+    Custom(Box<egui::Style>),
+    /// The notebook's own styles and theme, as shown on screen.
+    Notebook,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -224,6 +318,9 @@ struct NotebookCore {
     body: Box<dyn FnMut(&mut NotebookCtx)>,
     state_store: Arc<state::StateStore>,
     settled: Arc<AtomicBool>,
+    dependencies: Arc<parking_lot::Mutex<DependencyLog>>,
+    /// Output slots added by the previous frame's body.
+    outputs: HashSet<state::DependencyKey>,
 }
 
 struct Notebook {
@@ -232,6 +329,8 @@ struct Notebook {
     icons: Option<AppIcons>,
     #[cfg(not(target_arch = "wasm32"))]
     icon_is_dark: Option<bool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    perf_hud: Option<perf_hud::PerfHud>,
     #[cfg(feature = "telemetry")]
     #[allow(dead_code)]
     telemetry: Option<telemetry::Telemetry>,
@@ -243,6 +342,15 @@ pub struct NotebookCtx {
     cards: Vec<CardEntry>,
     state_store: Arc<state::StateStore>,
     settled: Arc<AtomicBool>,
+    dependencies: Arc<parking_lot::Mutex<DependencyLog>>,
+    reduced_motion: bool,
+    /// How many cards this frame already used each identity key, so
+    /// repeated keys (cards added in a loop) get distinct identities.
+    identity_counts: HashMap<egui::Id, usize>,
+    /// Output slots of the `view_with_output` cards added this frame.
+    outputs: HashSet<state::DependencyKey>,
+    /// States owned by cards dropped with [`clear`](Self::clear).
+    cleared: HashSet<state::DependencyKey>,
 }
 
 pub use card_ctx::CardCtx;
@@ -274,6 +382,30 @@ pub(crate) fn mark_headless(ctx: &egui::Context) {
     ctx.data_mut(|d| d.insert_temp(headless_marker_id(), true));
 }
 
+/// Turns off `style`'s animations for reduced motion.
+pub(crate) fn reduce_motion_style(style: &mut egui::Style) {
+    style.animation_time = 0.0;
+    style.scroll_animation = egui::style::ScrollAnimation::none();
+}
+
+/// Context-data key for the reduced-motion marker.
+fn reduced_motion_id() -> egui::Id {
+    egui::Id::new("gorbie_reduced_motion")
+}
+
+/// True when the notebook was configured with
+/// [`NotebookConfig::with_reduced_motion`]. Widgets with their own
+/// animations should hold still under it.
+pub fn reduced_motion(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp(reduced_motion_id()))
+        .unwrap_or(false)
+}
+
+/// Records the reduced-motion setting on `ctx`. Called during setup.
+pub(crate) fn set_reduced_motion(ctx: &egui::Context, reduced_motion: bool) {
+    ctx.data_mut(|d| d.insert_temp(reduced_motion_id(), reduced_motion));
+}
+
 /// Context-data key for the wgpu render-target colour format.
 fn wgpu_target_format_id() -> egui::Id {
     egui::Id::new("gorbie_wgpu_target_format")
@@ -294,6 +426,7 @@ pub fn wgpu_target_format(ctx: &egui::Context) -> Option<egui_wgpu::wgpu::Textur
 }
 
 pub(crate) const NOTEBOOK_COLUMN_WIDTH: f32 = 768.0;
+const SIDEBAR_DEFAULT_WIDTH: f32 = 240.0;
 #[cfg(not(target_arch = "wasm32"))]
 const NOTEBOOK_MIN_HEIGHT: f32 = 360.0;
 #[cfg(not(target_arch = "wasm32"))]
@@ -316,15 +449,229 @@ impl NotebookConfig {
         let title = name.into();
         Self {
             title,
+            forced_theme: None,
+            styles: None,
+            sidebar: None,
+            header_actions: None,
+            footer: None,
+            virtualize_cards: false,
+            responsive: false,
+            compact_width: NOTEBOOK_COLUMN_WIDTH,
+            scroll_anchoring: true,
+            reduced_motion: false,
+            corner_radius: 0.0,
+            #[cfg(feature = "markdown")]
+            code_line_numbers: false,
+            repaint_interval: None,
             #[cfg(not(target_arch = "wasm32"))]
             editor: editor_from_env(),
             #[cfg(not(target_arch = "wasm32"))]
             headless_capture: None,
             #[cfg(not(target_arch = "wasm32"))]
             headless_settle_timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            capture_style: CaptureStyle::Print,
+            #[cfg(not(target_arch = "wasm32"))]
+            perf_hud: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            window_icon: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            app_id: None,
         }
     }
 
+    /// Forces the notebook into `theme`, ignoring the system preference and
+    /// hiding the System/Dark/Light toggle in the page header.
+    ///
+    /// Useful for kiosk deployments and presentations. `None` restores the
+    /// default behavior.
+    pub fn with_forced_theme(mut self, theme: Option<egui::Theme>) -> Self {
+        self.forced_theme = theme;
+        self
+    }
+
+    /// Replaces the industrial light and dark styles with custom ones.
+    ///
+    /// The notebook chrome (dot grid, hairlines, floating-card shadows)
+    /// takes its colors from the active visuals, so it follows along. Start
+    /// from [`themes::industrial_light`]/[`themes::industrial_dark`] to keep
+    /// the GORBIE spacing and text styles and only restyle colors.
+    pub fn with_styles(mut self, light: egui::Style, dark: egui::Style) -> Self {
+        self.styles = Some(Box::new((light, dark)));
+        self
+    }
+
+    /// Turns off animations: busy indicators hold still instead of
+    /// pulsing, sections snap open and scrolling to a card jumps instead of
+    /// gliding. For accessibility and for deterministic renders.
+    ///
+    /// Cards can check [`NotebookCtx::reduced_motion`],
+    /// [`CardCtx::reduced_motion`] or [`reduced_motion`] so their own
+    /// animations follow along.
+    pub fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.reduced_motion = reduced_motion;
+        self
+    }
+
+    /// Rounds the corners of the notebook's own frames (the card column,
+    /// notes, post-it tooltips, floating cards and popups) by `radius`
+    /// points, for a softer look. Defaults to 0.0, the sharp industrial
+    /// style. Widgets drawing their own frames can follow it through
+    /// [`themes::corner_radius`].
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius.max(0.0);
+        self
+    }
+
+    /// Numbers the lines of every code block, markdown fences included, in
+    /// a dimmed gutter that copying leaves out. Off by default; single
+    /// blocks can opt in with [`widgets::code_block_with_line_numbers`].
+    #[cfg(feature = "markdown")]
+    pub fn with_code_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.code_line_numbers = line_numbers;
+        self
+    }
+
+    /// The light and dark styles to install: custom ones from
+    /// [`with_styles`](Self::with_styles) or the industrial defaults.
+    fn styles(&self) -> (egui::Style, egui::Style) {
+        let (mut light, mut dark) = match self.styles.as_deref() {
+            Some((light, dark)) => (light.clone(), dark.clone()),
+            None => (industrial_light(), industrial_dark()),
+        };
+        if self.reduced_motion {
+            reduce_motion_style(&mut light);
+            reduce_motion_style(&mut dark);
+        }
+        (light, dark)
+    }
+
+    /// Adds a fixed left sidebar for navigation or controls.
+    ///
+    /// The sidebar is drawn every frame beside the card column and does not
+    /// scroll with the notebook. It is not part of headless captures.
+    pub fn with_sidebar(mut self, sidebar: impl FnMut(&mut egui::Ui) + 'static) -> Self {
+        self.sidebar = Some(Box::new(sidebar));
+        self
+    }
+
+    /// Adds a fixed bottom bar for status text, progress or attribution.
+    ///
+    /// Like the sidebar, the footer is drawn every frame, does not scroll
+    /// with the notebook and is not part of headless captures.
+    pub fn with_footer(mut self, footer: impl FnMut(&mut egui::Ui) + 'static) -> Self {
+        self.footer = Some(Box::new(footer));
+        self
+    }
+
+    /// Adds app-wide controls to the page header, left of the theme toggle.
+    ///
+    /// `actions` draws into the header row's right-to-left layout, so the
+    /// first widget added sits rightmost.
+    pub fn with_header_actions(mut self, actions: impl FnMut(&mut egui::Ui) + 'static) -> Self {
+        self.header_actions = Some(Box::new(actions));
+        self
+    }
+
+    /// Skips drawing cards that are scrolled out of view, reserving their
+    /// height instead.
+    ///
+    /// The reserved height is the card's last drawn height. A card that
+    /// hasn't been drawn yet reserves its [`Card::measure`](cards::Card::measure)
+    /// estimate, or else its [`Card::min_height`](cards::Card::min_height),
+    /// so `measure` only matters until the first draw. The reservation is
+    /// never less than `min_height`. An undrawn card with neither is drawn
+    /// once to find out; one with a `min_height` is not drawn until it
+    /// scrolls into view. Offscreen cards do not run their closures, so
+    /// leave this off if cards poll background work or publish state every
+    /// frame.
+    pub fn with_card_virtualization(mut self, enabled: bool) -> Self {
+        self.virtualize_cards = enabled;
+        self
+    }
+
+    /// Switches to a compact layout while the window is narrower than
+    /// [`with_compact_width`](Self::with_compact_width): cards span the full
+    /// width with no dot-grid margin, the page header is tightened, the
+    /// detach and open-in-editor tabs move inside the card, and detached
+    /// cards open over the column instead of in the (missing) margin. Off
+    /// by default, which keeps the fixed-width column and clips it in narrow
+    /// windows.
+    pub fn with_responsive(mut self, enabled: bool) -> Self {
+        self.responsive = enabled;
+        self
+    }
+
+    /// Sets the window width below which a responsive notebook switches to
+    /// its compact layout. Defaults to the 768 px notebook column.
+    pub fn with_compact_width(mut self, width: f32) -> Self {
+        self.compact_width = width;
+        self
+    }
+
+    /// Keeps the visible content in place when cards above the viewport
+    /// change height (e.g. when their data finishes loading) by moving the
+    /// scroll offset along with them. On by default; turn it off to debug
+    /// layout jumps.
+    pub fn with_scroll_anchoring(mut self, enabled: bool) -> Self {
+        self.scroll_anchoring = enabled;
+        self
+    }
+
+    /// Sets the minimum time between repaints triggered by background work
+    /// through [`RepaintThrottle`](dataflow::RepaintThrottle) (including
+    /// [`Signal`](dataflow::Signal) bumps). Defaults to one frame at 60 Hz;
+    /// raise it to save CPU when many workers report progress at once.
+    pub fn with_repaint_interval(mut self, interval: std::time::Duration) -> Self {
+        self.repaint_interval = Some(interval);
+        self
+    }
+
+    /// Shows a frame-time overlay in the bottom-right corner: the last and
+    /// mean time spent drawing the notebook, a sparkline of recent frames
+    /// against a 60 Hz budget line, and the slowest card of the last frame.
+    ///
+    /// For spotting expensive cards without reaching for a profiler; the
+    /// telemetry viewer (`telemetry` feature) gives the full picture.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_perf_hud(mut self, enabled: bool) -> Self {
+        self.perf_hud = enabled;
+        self
+    }
+
+    /// Replaces the GORBIE window icon with `rgba`: `width * height`
+    /// pixels, row by row from the top left, 4 bytes each (red, green,
+    /// blue, alpha; not premultiplied), as eframe expects.
+    ///
+    /// # Panics
+    ///
+    /// If `rgba` is not `width * height * 4` bytes long.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_window_icon(mut self, rgba: impl Into<Vec<u8>>, width: u32, height: u32) -> Self {
+        let rgba = rgba.into();
+        assert_eq!(
+            rgba.len(),
+            width as usize * height as usize * 4,
+            "window icon must be width * height RGBA pixels"
+        );
+        self.window_icon = Some(egui::IconData {
+            rgba,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Sets the application id the window reports to the desktop (the
+    /// Wayland app id / X11 `WM_CLASS`), which taskbars and docks use to
+    /// group windows and match them to a `.desktop` entry. Defaults to
+    /// eframe's, derived from the window title.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.app_id = Some(app_id.into());
+        self
+    }
+
     /// Overrides the editor command used for "open in editor" buttons.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn with_editor(mut self, editor: EditorCommand) -> Self {
@@ -347,6 +694,19 @@ impl NotebookConfig {
         self
     }
 
+    /// Sets the style headless captures render with. Defaults to
+    /// [`themes::industrial_print`], black on white for printed docs;
+    /// `Some(style)` renders with a custom light style, and `None` with the
+    /// notebook's own styles and theme, as shown on screen.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_capture_style(mut self, style: Option<egui::Style>) -> Self {
+        self.capture_style = match style {
+            Some(style) => CaptureStyle::Custom(Box::new(style)),
+            None => CaptureStyle::Notebook,
+        };
+        self
+    }
+
     /// Like [`with_headless_capture`](Self::with_headless_capture), but with a
     /// custom `pixels_per_point` scaling factor for the rendered output.
     #[cfg(not(target_arch = "wasm32"))]
@@ -392,6 +752,11 @@ impl NotebookConfig {
     /// On native, the `body` closure is called once per frame in an interactive
     /// window (or headless capture mode). On wasm, the notebook renders into a
     /// `<canvas id="gorbie_canvas">` element.
+    ///
+    /// If no window or GPU context can be created (CI, SSH sessions) the
+    /// error explains the options; with `GORBIE_HEADLESS` set the notebook
+    /// is skipped instead and `run` returns `Ok`, so notebooks can be
+    /// invoked from test harnesses.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn run(self, body: impl FnMut(&mut NotebookCtx) + 'static) -> eframe::Result {
         let config = self;
@@ -406,7 +771,6 @@ impl NotebookConfig {
             config.title.clone()
         };
 
-        let icons = load_app_icons();
         let mut native_options = eframe::NativeOptions::default();
         // Render live on the wgpu backend (Metal/Vulkan/D3D12), not glow.
         // Unifies the live renderer with the headless capture path and
@@ -415,47 +779,125 @@ impl NotebookConfig {
         // `Frame::wgpu_render_state()` is `None` under the glow backend.
         native_options.renderer = eframe::Renderer::Wgpu;
         native_options.persist_window = true;
+        let sidebar_width = if config.sidebar.is_some() {
+            SIDEBAR_DEFAULT_WIDTH
+        } else {
+            0.0
+        };
         native_options.viewport = native_options
             .viewport
-            .with_inner_size(egui::vec2(1200.0, 800.0))
-            .with_min_inner_size(egui::vec2(NOTEBOOK_COLUMN_WIDTH, NOTEBOOK_MIN_HEIGHT));
+            .with_inner_size(egui::vec2(1200.0 + sidebar_width, 800.0))
+            .with_min_inner_size(egui::vec2(
+                NOTEBOOK_COLUMN_WIDTH + sidebar_width,
+                NOTEBOOK_MIN_HEIGHT,
+            ));
 
-        if let Some(icons) = icons.as_ref() {
+        if let Some(icon) = config.window_icon.clone() {
+            native_options.viewport = native_options.viewport.with_icon(icon);
+        } else if let Some(icons) = load_app_icons() {
             let icon = match dark_light::detect() {
-                Ok(Mode::Light) => icons.light.clone(),
-                Ok(Mode::Dark) => icons.dark.clone(),
-                Ok(Mode::Unspecified) | Err(_) => icons.dark.clone(),
+                Ok(Mode::Light) => icons.light,
+                Ok(Mode::Dark) => icons.dark,
+                Ok(Mode::Unspecified) | Err(_) => icons.dark,
             };
             native_options.viewport = native_options.viewport.with_icon(icon);
         }
+        if let Some(app_id) = config.app_id.clone() {
+            native_options.viewport = native_options.viewport.with_app_id(app_id);
+        }
 
-        let body = Box::new(body);
-        eframe::run_native(
+        // The app is only created once a window and GPU context exist, so
+        // an error before that means the notebook could not be displayed.
+        let created = std::cell::Cell::new(false);
+        let result = eframe::run_native(
             &window_title,
             native_options,
             Box::new(|cc| {
-                let ctx = cc.egui_ctx.clone();
-                ctrlc::set_handler(move || ctx.send_viewport_cmd(egui::ViewportCommand::Close))
-                    .expect("failed to set exit signal handler");
-
-                cc.egui_ctx.set_fonts(industrial_fonts());
-
-                cc.egui_ctx
-                    .set_style_of(egui::Theme::Light, industrial_light());
-                cc.egui_ctx
-                    .set_style_of(egui::Theme::Dark, industrial_dark());
-
-                #[cfg(feature = "telemetry")]
-                let telemetry_title = config.title.clone();
-                Ok(Box::new(Notebook {
-                    core: NotebookCore::new(config, body),
-                    icons,
-                    icon_is_dark: None,
-                    #[cfg(feature = "telemetry")]
-                    telemetry: telemetry::Telemetry::install_global_from_env(&telemetry_title),
-                }))
+                created.set(true);
+                Ok(config.install(cc, body))
             }),
-        )
+        );
+        match result {
+            Err(err) if !created.get() => display_unavailable(err),
+            result => result,
+        }
+    }
+
+    /// Sets up fonts, themes and the exit signal handler on `cc` and returns
+    /// the notebook as an [`eframe::App`].
+    ///
+    /// This is the setup half of [`run`](Self::run) without the event loop,
+    /// for embedding a notebook in an app that calls `eframe::run_native`
+    /// (or the web runner) itself:
+    ///
+    /// ```no_run
+    /// # fn body(_nb: &mut GORBIE::NotebookCtx) {}
+    /// eframe::run_native(
+    ///     "host app",
+    ///     eframe::NativeOptions::default(),
+    ///     Box::new(|cc| Ok(GORBIE::NotebookConfig::new("embedded").install(cc, body))),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// Headless capture is handled by `run` only and is ignored here.
+    pub fn install(
+        self,
+        cc: &eframe::CreationContext<'_>,
+        body: impl FnMut(&mut NotebookCtx) + 'static,
+    ) -> Box<dyn eframe::App> {
+        let config = self;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let ctx = cc.egui_ctx.clone();
+            // A host app may already own the handler; that is not fatal.
+            if let Err(err) =
+                ctrlc::set_handler(move || ctx.send_viewport_cmd(egui::ViewportCommand::Close))
+            {
+                log::warn!("failed to set exit signal handler: {err}");
+            }
+        }
+
+        cc.egui_ctx.set_fonts(industrial_fonts());
+        let (light, dark) = config.styles();
+        cc.egui_ctx.set_style_of(egui::Theme::Light, light);
+        cc.egui_ctx.set_style_of(egui::Theme::Dark, dark);
+        set_reduced_motion(&cc.egui_ctx, config.reduced_motion);
+        themes::set_corner_radius(&cc.egui_ctx, config.corner_radius);
+        #[cfg(feature = "markdown")]
+        widgets::code_block::set_code_line_numbers(&cc.egui_ctx, config.code_line_numbers);
+        if let Some(theme) = config.forced_theme {
+            cc.egui_ctx.set_theme(theme);
+        }
+        if let Some(interval) = config.repaint_interval {
+            dataflow::RepaintThrottle::new(&cc.egui_ctx, interval).install();
+        }
+
+        #[cfg(all(feature = "telemetry", not(target_arch = "wasm32")))]
+        let telemetry = telemetry::Telemetry::install_global_from_env(&config.title);
+        #[cfg(not(target_arch = "wasm32"))]
+        let perf_hud = config.perf_hud.then(perf_hud::PerfHud::default);
+        // A custom icon stays put instead of following the theme.
+        #[cfg(not(target_arch = "wasm32"))]
+        let icons = config
+            .window_icon
+            .is_none()
+            .then(load_app_icons)
+            .flatten();
+        Box::new(Notebook {
+            core: NotebookCore::new(config, Box::new(body)),
+            #[cfg(not(target_arch = "wasm32"))]
+            icons,
+            #[cfg(not(target_arch = "wasm32"))]
+            icon_is_dark: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            perf_hud,
+            #[cfg(all(feature = "telemetry", not(target_arch = "wasm32")))]
+            telemetry,
+            #[cfg(all(feature = "telemetry", target_arch = "wasm32"))]
+            telemetry: None,
+        })
     }
 
     /// Launches the notebook in the browser.
@@ -477,7 +919,6 @@ impl NotebookConfig {
             .expect("element is not a canvas");
 
         let runner = eframe::WebRunner::new();
-        let body = Box::new(body);
         let config = self;
 
         wasm_bindgen_futures::spawn_local(async move {
@@ -485,19 +926,7 @@ impl NotebookConfig {
                 .start(
                     canvas,
                     eframe::WebOptions::default(),
-                    Box::new(|cc| {
-                        cc.egui_ctx.set_fonts(industrial_fonts());
-                        cc.egui_ctx
-                            .set_style_of(egui::Theme::Light, industrial_light());
-                        cc.egui_ctx
-                            .set_style_of(egui::Theme::Dark, industrial_dark());
-
-                        Ok(Box::new(Notebook {
-                            core: NotebookCore::new(config, body),
-                            #[cfg(feature = "telemetry")]
-                            telemetry: None,
-                        }))
-                    }),
+                    Box::new(|cc| Ok(config.install(cc, body))),
                 )
                 .await
                 .expect("eframe start failed");
@@ -555,15 +984,32 @@ impl state::StateAccess for NotebookCtx {
 }
 
 impl NotebookCtx {
-    fn new(config: &NotebookConfig, state_store: Arc<state::StateStore>, settled: Arc<AtomicBool>) -> Self {
+    fn new(
+        config: &NotebookConfig,
+        state_store: Arc<state::StateStore>,
+        settled: Arc<AtomicBool>,
+        dependencies: Arc<parking_lot::Mutex<DependencyLog>>,
+    ) -> Self {
         Self {
             state_id: config.state_id(),
             cards: Vec::new(),
             state_store,
             settled,
+            dependencies,
+            reduced_motion: config.reduced_motion,
+            identity_counts: HashMap::new(),
+            outputs: HashSet::new(),
+            cleared: HashSet::new(),
         }
     }
 
+    /// True when the notebook was configured with
+    /// [`NotebookConfig::with_reduced_motion`]; custom animations should
+    /// hold still.
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
     /// Signal that notebook content is fully loaded and ready for capture.
     /// In headless mode this triggers immediate capture instead of waiting
     /// for the settle timeout. In interactive mode this is a no-op.
@@ -571,6 +1017,43 @@ impl NotebookCtx {
         self.settled.store(true, Ordering::Relaxed);
     }
 
+    /// Returns which of the cards added so far this frame read state
+    /// owned by which others, as recorded when they last drew. Call it at
+    /// the end of the body to cover the whole notebook. Reads through
+    /// [`StateId`](state::StateId) handles are picked up automatically;
+    /// custom cards can add more with [`Card::dependencies`](cards::Card::dependencies).
+    ///
+    /// The graph is built on each call, so notebooks that never ask for it
+    /// pay only for recording the reads.
+    /// [`DependencyGraph::to_dot`](cards::DependencyGraph::to_dot) exports it
+    /// for Graphviz.
+    pub fn dependency_graph(&self) -> cards::DependencyGraph {
+        let log = self.dependencies.lock();
+        let nodes = self
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let mut reads = log.reads.get(&entry.identity).cloned().unwrap_or_default();
+                for key in entry.card.dependencies() {
+                    if !reads.contains(key) {
+                        reads.push(*key);
+                    }
+                }
+                cards::DependencyNode {
+                    label: entry
+                        .source
+                        .as_ref()
+                        .map(|source| source.file_line_column())
+                        .unwrap_or_else(|| format!("card {index}")),
+                    state: entry.card.state_key(),
+                    reads,
+                }
+            })
+            .collect();
+        cards::DependencyGraph::new(nodes)
+    }
+
     /// Adds a stateless card whose content is drawn by `function` each frame.
     #[track_caller]
     pub fn view<F>(&mut self, function: F)
@@ -582,10 +1065,45 @@ impl NotebookCtx {
             source: Some(source.clone()),
             function: TypeId::of::<F>(),
         });
-        let card = cards::StatelessCard::new(function);
+        let card = cards::StatelessCard::new(None, function);
         self.push_with_source(Box::new(card), Some(source), identity);
     }
 
+    /// Like [`view`](Self::view), but keeps whatever `function` returned on
+    /// its last draw and returns a handle to it, `None` until the card has
+    /// been drawn once. Returning a widget's state lets the body react to
+    /// interaction inside the card:
+    ///
+    /// ```no_run
+    /// # fn body(nb: &mut GORBIE::NotebookCtx) {
+    /// let clicked = nb.view_with_output(|ctx| ctx.button("Run").clicked());
+    /// nb.view(move |ctx| {
+    ///     let ran = clicked.read(ctx).unwrap_or(false);
+    ///     ctx.label(if ran { "Running…" } else { "Idle" });
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// The output is dropped once the card is no longer added.
+    #[track_caller]
+    pub fn view_with_output<F, R>(&mut self, function: F) -> state::StateId<Option<R>>
+    where
+        F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>) -> R + 'static,
+        R: Send + Sync + 'static,
+    {
+        let source = SourceLocation::from_location(std::panic::Location::caller());
+        let identity = self.card_identity(CardIdentityKey::Stateless {
+            source: Some(source.clone()),
+            function: TypeId::of::<F>(),
+        });
+        let output = state::StateId::new(identity.with("output"));
+        self.state_store.get_or_insert(output, None);
+        self.outputs.insert(output.key());
+        let card = cards::StatelessCard::new(Some(output), function);
+        self.push_with_source(Box::new(card), Some(source), identity);
+        output
+    }
+
     /// Adds a stateful card backed by a value of type `T` in the shared state store.
     ///
     /// The state is initialized with `init` on first use and persists across frames.
@@ -613,18 +1131,223 @@ impl NotebookCtx {
         handle
     }
 
+    /// Like [`state`](Self::state), but `init` is only called when the card
+    /// first scrolls into view, for state that is expensive to build (a
+    /// pile, a GPU runner). Until then the card shows a blank placeholder
+    /// and the returned handle reads `None`.
+    #[track_caller]
+    pub fn lazy_state<K, T, I, F>(
+        &mut self,
+        key: &K,
+        init: I,
+        function: F,
+    ) -> state::StateId<Option<T>>
+    where
+        K: std::hash::Hash + ?Sized,
+        T: Send + Sync + 'static,
+        I: FnOnce() -> T + 'static,
+        F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>, &mut T) + 'static,
+    {
+        let source = SourceLocation::from_location(std::panic::Location::caller());
+        let state_id = self.state_id_for(key);
+        let identity = self.card_identity(CardIdentityKey::Stateful {
+            source: Some(source.clone()),
+            state: state_id,
+            function: TypeId::of::<F>(),
+        });
+        let state = state::StateId::new(state_id);
+        self.state_store.get_or_insert(state, None);
+        let card = cards::LazyStatefulCard::new(state, init, function);
+        self.push_with_source(Box::new(card), Some(source), identity);
+        state
+    }
+
+    /// Adds a prose card rendering `text` as markdown.
+    ///
+    /// Shorthand for a [`MarkdownCard`](cards::MarkdownCard), for notebooks
+    /// that are mostly prose:
+    ///
+    /// ```no_run
+    /// # fn body(nb: &mut GORBIE::NotebookCtx) {
+    /// nb.md("# Results\nThe solver converges after a few hundred sweeps.");
+    /// # }
+    /// ```
+    #[cfg(feature = "markdown")]
+    #[track_caller]
+    pub fn md(&mut self, text: impl Into<String>) {
+        let source = SourceLocation::from_location(std::panic::Location::caller());
+        let identity = self.card_identity(CardIdentityKey::Stateless {
+            source: Some(source.clone()),
+            function: TypeId::of::<cards::MarkdownCard>(),
+        });
+        let card = cards::MarkdownCard::new(text);
+        self.push_with_source(Box::new(card), Some(source), identity);
+    }
+
+    /// Like [`state`](Self::state), but the card's state is included in
+    /// sessions written by [`save_session`](Self::save_session) and
+    /// restored by [`load_session`](Self::load_session).
+    #[cfg(feature = "serde")]
+    #[track_caller]
+    pub fn session_state<K, T, F>(&mut self, key: &K, init: T, function: F) -> state::StateId<T>
+    where
+        K: std::hash::Hash + ?Sized,
+        T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+        F: for<'a, 'b> FnMut(&'a mut CardCtx<'b>, &mut T) + 'static,
+    {
+        let source = SourceLocation::from_location(std::panic::Location::caller());
+        let state_id = self.state_id_for(key);
+        let identity = self.card_identity(CardIdentityKey::Stateful {
+            source: Some(source.clone()),
+            state: state_id,
+            function: TypeId::of::<F>(),
+        });
+        let state = state::StateId::new(state_id);
+        self.state_store.get_or_insert(state, init);
+        let card = cards::StatefulCard::new(state, function).with_session_codec();
+        self.push_with_source(Box::new(card), Some(source), identity);
+        state
+    }
+
+    /// Writes the state of every card added so far to `path` as JSON,
+    /// one entry per card in order (`null` for cards without savable state).
+    ///
+    /// Call it at the end of the notebook body so all cards are included,
+    /// e.g. when a "save" flag set by a button card is seen:
+    ///
+    /// ```ignore
+    /// if std::mem::take(&mut *save_requested.read_mut(nb)) {
+    ///     nb.save_session("session.json")?;
+    /// }
+    /// ```
+    ///
+    /// Only cards created with [`session_state`](Self::session_state) (or
+    /// custom [`Card`](cards::Card)s implementing `save_state`) are saved.
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    pub fn save_session(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json =
+            serde_json::to_vec_pretty(&self.session_states()).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Restores card state saved by [`save_session`](Self::save_session),
+    /// matching cards by index. Entries for cards that no longer exist, and
+    /// `null` entries, are skipped.
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    pub fn load_session(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = std::fs::read(path)?;
+        let states: Vec<serde_json::Value> =
+            serde_json::from_slice(&json).map_err(std::io::Error::other)?;
+        self.apply_session_states(&states);
+        Ok(())
+    }
+
+    /// Encodes the same card state as [`save_session`](Self::save_session)
+    /// into a compact share string (URL-safe base64 of JSON), for pasting
+    /// an exact reproduction into a bug report. The command palette's
+    /// "Copy share string" action copies it to the clipboard.
+    #[cfg(feature = "serde")]
+    pub fn encode_state(&self) -> String {
+        let mut states = self.session_states();
+        while states.last().is_some_and(serde_json::Value::is_null) {
+            states.pop();
+        }
+        let json = serde_json::to_vec(&states).expect("JSON values serialize");
+        share::encode(&json)
+    }
+
+    /// Restores card state from a string made by
+    /// [`encode_state`](Self::encode_state), matching cards by index like
+    /// [`load_session`](Self::load_session). A malformed string is rejected
+    /// without touching any state.
+    ///
+    /// Like `load_session`, call it once, not on every run of the body:
+    ///
+    /// ```ignore
+    /// if let Some(share) = std::mem::take(&mut *pending_share.read_mut(nb)) {
+    ///     if let Err(err) = nb.apply_state(&share) {
+    ///         log::warn!("{err}");
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn apply_state(&mut self, share: &str) -> Result<(), String> {
+        let json = share::decode(share)?;
+        let states: Vec<serde_json::Value> =
+            serde_json::from_slice(&json).map_err(|err| format!("malformed share string: {err}"))?;
+        self.apply_session_states(&states);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    fn session_states(&self) -> Vec<serde_json::Value> {
+        self.cards
+            .iter()
+            .map(|entry| {
+                entry
+                    .card
+                    .save_state(&self.state_store)
+                    .unwrap_or(serde_json::Value::Null)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "serde")]
+    fn apply_session_states(&mut self, states: &[serde_json::Value]) {
+        for (entry, value) in self.cards.iter_mut().zip(states) {
+            if !value.is_null() {
+                entry.card.load_state(&self.state_store, value);
+            }
+        }
+    }
+
     /// Adds a pre-built [`Card`](cards::Card) trait object to the notebook.
     pub fn push(&mut self, card: Box<dyn cards::Card>) {
         let identity = self.card_identity(CardIdentityKey::Custom);
         self.push_with_source(card, None, identity);
     }
 
+    /// Number of cards added so far this frame.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Whether no card has been added yet this frame.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Drops every card added so far this frame, e.g. to replace a
+    /// generated section wholesale; cards added afterwards are numbered as
+    /// if nothing had been added before.
+    ///
+    /// The cards are dropped right away, so anything they own (such as a
+    /// [`Worker`](dataflow::Worker) moved into a card closure) shuts down
+    /// with them. Their state in the [`StateStore`](state::StateStore) is
+    /// kept for cards added again under the same keys this frame, and
+    /// dropped at the end of the frame otherwise, stopping any worker
+    /// stored in it.
+    pub fn clear(&mut self) {
+        self.cleared
+            .extend(self.cards.iter().filter_map(|entry| entry.card.state_key()));
+        self.cards.clear();
+        self.identity_counts.clear();
+        self.outputs.clear();
+    }
+
     pub(crate) fn state_id_for<K: std::hash::Hash + ?Sized>(&self, key: &K) -> egui::Id {
         self.state_id.with(("state", key))
     }
 
-    fn card_identity(&self, key: CardIdentityKey) -> egui::Id {
-        self.state_id.with(("card", key))
+    /// Identity of the next card added under `key`: the key plus how many
+    /// cards already used it this frame, so e.g. views created in a loop
+    /// don't share an output slot.
+    fn card_identity(&mut self, key: CardIdentityKey) -> egui::Id {
+        let base = self.state_id.with(("card", key));
+        let occurrence = self.identity_counts.entry(base).or_default();
+        let identity = base.with(*occurrence);
+        *occurrence += 1;
+        identity
     }
 
     fn push_with_source(
@@ -648,6 +1371,8 @@ impl NotebookCore {
             body,
             state_store: Arc::new(state::StateStore::default()),
             settled: Arc::new(AtomicBool::new(false)),
+            dependencies: Arc::default(),
+            outputs: HashSet::new(),
         }
     }
 
@@ -657,8 +1382,27 @@ impl NotebookCore {
     }
 
     fn build_notebook(&mut self) -> NotebookCtx {
-        let mut notebook = NotebookCtx::new(&self.config, self.state_store.clone(), self.settled.clone());
+        let mut notebook = NotebookCtx::new(
+            &self.config,
+            self.state_store.clone(),
+            self.settled.clone(),
+            self.dependencies.clone(),
+        );
         (self.body)(&mut notebook);
+        // Cards that are gone don't need their last output anymore.
+        for stale in self.outputs.difference(&notebook.outputs) {
+            self.state_store.remove(*stale);
+        }
+        self.outputs = std::mem::take(&mut notebook.outputs);
+        // Cleared cards that weren't added back take their state with them.
+        let live: HashSet<_> = notebook
+            .cards
+            .iter()
+            .filter_map(|entry| entry.card.state_key())
+            .collect();
+        for stale in notebook.cleared.difference(&live) {
+            self.state_store.remove(*stale);
+        }
         notebook
     }
 
@@ -684,6 +1428,7 @@ impl NotebookCore {
                 if let Some(entry) = notebook.cards.get_mut(index) {
                     let card: &mut dyn cards::Card = entry.card.as_mut();
                     let rect = draw_card_body(ui, card_width, card, store.as_ref(), None);
+                    cards::set_note_color(ui.ctx(), None);
                     measured_height = Some(rect.height());
                 }
             });
@@ -737,7 +1482,28 @@ impl eframe::App for Notebook {
             let _build_span = tracing::info_span!("build_notebook").entered();
             self.core.build_notebook()
         };
+
+        // The sidebar claims the left edge first; the notebook column below
+        // lays out in whatever remains.
+        if let Some(sidebar) = self.core.config.sidebar.as_mut() {
+            egui::SidePanel::left(egui::Id::new("gorbie_sidebar"))
+                .default_width(SIDEBAR_DEFAULT_WIDTH)
+                .frame(egui::Frame::side_top_panel(&ctx.global_style()))
+                .show_inside(ui, |ui| sidebar(ui));
+        }
+        if let Some(footer) = self.core.config.footer.as_mut() {
+            egui::TopBottomPanel::bottom(egui::Id::new("gorbie_footer"))
+                .frame(egui::Frame::side_top_panel(&ctx.global_style()))
+                .show_inside(ui, |ui| footer(ui));
+        }
+        // Moved out for the frame so it can be called while `config` is
+        // borrowed; put back once the frame is drawn.
+        let mut header_actions = self.core.config.header_actions.take();
         let config = &self.core.config;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut perf_hud = self.perf_hud.as_mut();
+        #[cfg(not(target_arch = "wasm32"))]
+        let frame_start = std::time::Instant::now();
 
         let state_id = config.state_id();
         let mut runtime = ctx.data_mut(|data| {
@@ -749,8 +1515,9 @@ impl eframe::App for Notebook {
         // or background color (see the trait doc). Wrap in a central-panel
         // Frame so the notebook draws on the theme's panel fill instead
         // of transparent/black.
+        let scroll_correction = runtime.scroll_correction.take();
         egui::Frame::central_panel(&ctx.global_style()).show(ui, |ui| {
-        egui::ScrollArea::vertical()
+        let mut scroll_area = egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
             // Disable drag-to-scroll on the main notebook scroller. egui's
             // hit-test panics (`hit_test.rs:365`) when a big drag-sensing
@@ -762,7 +1529,11 @@ impl eframe::App for Notebook {
                 scroll_bar: true,
                 drag: false,
                 mouse_wheel: true,
-            })
+            });
+        if let Some(offset) = scroll_correction {
+            scroll_area = scroll_area.vertical_scroll_offset(offset);
+        }
+        scroll_area
             .show_viewport(ui, |ui, viewport| {
                     let rect = ui.max_rect();
                     let clip_rect = ui.clip_rect();
@@ -778,7 +1549,15 @@ impl eframe::App for Notebook {
                         },
                     );
 
-                    let column_width = NOTEBOOK_COLUMN_WIDTH;
+                    // Too narrow for the column: let it fill the window
+                    // instead, which leaves the margin empty.
+                    let compact = config.responsive && rect.width() < config.compact_width;
+                    let column_width = if compact {
+                        rect.width()
+                    } else {
+                        NOTEBOOK_COLUMN_WIDTH
+                    };
+                    let header_pad = if compact { 8.0 } else { 16.0 };
                     let left_margin_width = 0.0;
                     let card_width = column_width;
 
@@ -841,18 +1620,18 @@ impl eframe::App for Notebook {
                         let column_inner_margin = egui::Margin {
                             left: 0,
                             right: 0,
-                            top: 12,
+                            top: if compact { 4 } else { 12 },
                             bottom: card_gap_i8,
                         };
                         let column_frame = egui::Frame::new()
                             .fill(fill)
                             .stroke(egui::Stroke::NONE)
-                            .corner_radius(0.0)
+                            .corner_radius(themes::corner_radius(ui.ctx()))
                             .inner_margin(column_inner_margin)
                             .show(ui, |ui| {
                                 // Theme switch is part of the page header (above the first card).
                                 ui.horizontal(|ui| {
-                                    ui.add_space(16.0);
+                                    ui.add_space(header_pad);
                                     if !config.title.is_empty() {
                                         let header_title =
                                             egui::RichText::new(config.title.to_uppercase())
@@ -861,28 +1640,35 @@ impl eframe::App for Notebook {
                                         ui.add(egui::Label::new(header_title).truncate());
                                     }
 
-                                    ui.with_layout(
-                                        egui::Layout::right_to_left(egui::Align::Center),
-                                        |ui| {
-                                            ui.add_space(16.0);
-                                            let mut preference =
-                                                ui.ctx().options(|opt| opt.theme_preference);
-                                            if ui
-                                                .add(
-                                                    widgets::ChoiceToggle::new(&mut preference)
-                                                        .choice(egui::ThemePreference::System, "◐")
-                                                        .choice(egui::ThemePreference::Dark, "●")
-                                                        .choice(egui::ThemePreference::Light, "○"),
-                                                )
-                                                .changed()
-                                            {
-                                                ui.ctx().set_theme(preference);
-                                            }
-                                        },
-                                    );
+                                    if config.forced_theme.is_none() || header_actions.is_some() {
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                ui.add_space(header_pad);
+                                                if config.forced_theme.is_none() {
+                                                    let mut preference =
+                                                        ui.ctx().options(|opt| opt.theme_preference);
+                                                    if ui
+                                                        .add(
+                                                            widgets::ChoiceToggle::new(&mut preference)
+                                                                .choice(egui::ThemePreference::System, "◐")
+                                                                .choice(egui::ThemePreference::Dark, "●")
+                                                                .choice(egui::ThemePreference::Light, "○"),
+                                                        )
+                                                        .changed()
+                                                    {
+                                                        ui.ctx().set_theme(preference);
+                                                    }
+                                                }
+                                                if let Some(actions) = header_actions.as_mut() {
+                                                    actions(ui);
+                                                }
+                                            },
+                                        );
+                                    }
                                 });
 
-                                ui.add_space(12.0);
+                                ui.add_space(if compact { 4.0 } else { 12.0 });
 
                                 let default_item_spacing = ui.style().spacing.item_spacing;
                                 ui.style_mut().spacing.item_spacing.y = 0.0;
@@ -898,7 +1684,12 @@ impl eframe::App for Notebook {
                                 }
 
                                 runtime.sync_len(notebook.cards.len());
+                                let scroll_to_card = runtime.scroll_to_card.take();
+                                let scroll_anchor = runtime.scroll_anchor.take();
+                                let mut next_anchor = None;
+                                let mut anchor_shift = None;
                                 let store = notebook.state_store.clone();
+                                let mut frame_reads = Vec::new();
                                 let cards_len = notebook.cards.len();
                                 for (i, entry) in notebook.cards.iter_mut().enumerate() {
                                     let card_identity = entry.identity;
@@ -909,7 +1700,9 @@ impl eframe::App for Notebook {
                                     let card_placeholder_size = runtime.card_placeholder_sizes
                                         .get_mut(i)
                                         .expect("card_placeholder_sizes synced to cards");
-                                    ui.push_id((i, card_identity), |ui| {
+                                    // Tab tooltips and detached cards share the card's note color.
+                                    cards::set_note_color(ui.ctx(), entry.card.note_color());
+                                    let (_, reads) = state::record_reads(|| ui.push_id((i, card_identity), |ui| {
                                         let card_left = column_rect.min.x;
                                         let card: &mut dyn cards::Card = entry.card.as_mut();
                                         let card_rect = if *card_detached {
@@ -923,7 +1716,11 @@ impl eframe::App for Notebook {
                                             let fill = ui.visuals().window_fill;
                                             let outline =
                                                 ui.visuals().widgets.noninteractive.bg_stroke.color;
-                                            ui.painter().rect_filled(rect, 0.0, fill);
+                                            ui.painter().rect_filled(
+                                                rect,
+                                                themes::corner_radius(ui.ctx()),
+                                                fill,
+                                            );
                                             paint_hatching(
                                                 &ui.painter().with_clip_rect(rect),
                                                 rect,
@@ -935,10 +1732,14 @@ impl eframe::App for Notebook {
                                             }
 
                                             if *card_detached {
-                                                let initial_screen_pos = egui::pos2(
-                                                    right_margin.min.x + 12.0,
-                                                    rect.top(),
-                                                );
+                                                let initial_screen_pos = if compact {
+                                                    egui::pos2(column_rect.min.x, rect.top())
+                                                } else {
+                                                    egui::pos2(
+                                                        right_margin.min.x + 12.0,
+                                                        rect.top(),
+                                                    )
+                                                };
                                                 let detached_id = ui.id().with("detached_card");
                                                 let float_resp = floating::show_floating_card(
                                                     ui.ctx(),
@@ -951,7 +1752,8 @@ impl eframe::App for Notebook {
                                                     &mut |ctx| {
                                                         #[cfg(feature = "telemetry")]
                                                         let _detached_span = tracing::info_span!(
-                                                            "detached_draw"
+                                                            "detached_draw",
+                                                            index = i
                                                         )
                                                         .entered();
                                                         card.draw(ctx);
@@ -974,33 +1776,131 @@ impl eframe::App for Notebook {
                                                     clip_rect.max.y,
                                                 ),
                                             );
-                                            #[cfg(feature = "telemetry")]
-                                            let _card_span = {
-                                                let source = entry
-                                                    .source
-                                                    .as_ref()
-                                                    .map(|s| s.file_line_column())
-                                                    .unwrap_or_default();
-                                                tracing::info_span!(
-                                                    "card",
-                                                    source = source.as_str()
+                                            let estimate = if config.virtualize_cards {
+                                                reserved_height(
+                                                    card_placeholder_size.y,
+                                                    || card.measure(ui),
+                                                    card.min_height(),
                                                 )
-                                                .entered()
+                                            } else {
+                                                None
                                             };
-                                            let inner_rect = draw_card_body(
-                                                ui,
-                                                card_width,
-                                                card,
-                                                store.as_ref(),
-                                                Some(card_clip_rect),
-                                            );
-                                            *card_placeholder_size =
-                                                egui::vec2(card_width, inner_rect.height());
-                                            egui::Rect::from_min_size(
-                                                egui::pos2(card_left, inner_rect.min.y),
-                                                egui::vec2(card_width, inner_rect.height()),
-                                            )
+                                            let card_top = ui.cursor().top();
+                                            let offscreen = estimate.is_some_and(|height| {
+                                                let overscan = clip_rect.height() * 0.5;
+                                                card_top + height < clip_rect.min.y - overscan
+                                                    || card_top > clip_rect.max.y + overscan
+                                            });
+                                            if let (true, Some(height)) = (offscreen, estimate) {
+                                                let (rect, _) = ui.allocate_exact_size(
+                                                    egui::vec2(card_width, height),
+                                                    egui::Sense::hover(),
+                                                );
+                                                rect
+                                            } else {
+                                                #[cfg(feature = "telemetry")]
+                                                let _card_span = {
+                                                    let source = entry
+                                                        .source
+                                                        .as_ref()
+                                                        .map(|s| s.file_line_column())
+                                                        .unwrap_or_default();
+                                                    tracing::info_span!(
+                                                        "card",
+                                                        index = i,
+                                                        source = source.as_str()
+                                                    )
+                                                    .entered()
+                                                };
+                                                // The same span for a notebook's own recorder;
+                                                // its name is only built when one is installed.
+                                                #[cfg(all(
+                                                    feature = "telemetry",
+                                                    not(target_arch = "wasm32")
+                                                ))]
+                                                let _recorded_span = telemetry::recording().then(|| {
+                                                    let source = entry
+                                                        .source
+                                                        .as_ref()
+                                                        .map(|s| s.file_line_column());
+                                                    telemetry::record_span(
+                                                        "card",
+                                                        &format!("card {i}"),
+                                                        source.as_deref(),
+                                                    )
+                                                });
+                                                // Registered before the card's widgets so they
+                                                // keep their clicks; sized from the last frame.
+                                                let menu_response = ui.interact(
+                                                    egui::Rect::from_min_size(
+                                                        ui.cursor().min,
+                                                        egui::vec2(card_width, card_placeholder_size.y),
+                                                    ),
+                                                    ui.id().with("card_context_menu"),
+                                                    egui::Sense::click(),
+                                                );
+                                                #[cfg(not(target_arch = "wasm32"))]
+                                                let card_start = std::time::Instant::now();
+                                                let inner_rect = draw_card_body(
+                                                    ui,
+                                                    card_width,
+                                                    card,
+                                                    store.as_ref(),
+                                                    Some(card_clip_rect),
+                                                );
+                                                #[cfg(not(target_arch = "wasm32"))]
+                                                if let Some(hud) = perf_hud.as_mut() {
+                                                    hud.record_card(card_start.elapsed(), || {
+                                                        entry
+                                                            .source
+                                                            .as_ref()
+                                                            .map(|s| s.file_line_column())
+                                                            .unwrap_or_else(|| format!("card {i}"))
+                                                    });
+                                                }
+                                                menu_response.context_menu(|ui| {
+                                                    card_context_menu(
+                                                        ui,
+                                                        config,
+                                                        entry.source.as_ref(),
+                                                        card_detached,
+                                                    );
+                                                    card.context_menu(ui);
+                                                });
+                                                *card_placeholder_size =
+                                                    egui::vec2(card_width, inner_rect.height());
+                                                egui::Rect::from_min_size(
+                                                    egui::pos2(card_left, inner_rect.min.y),
+                                                    egui::vec2(card_width, inner_rect.height()),
+                                                )
+                                            }
                                         };
+                                        if scroll_to_card == Some(i) {
+                                            ui.scroll_to_rect(card_rect, Some(egui::Align::TOP));
+                                        }
+                                        // Scroll anchoring: if the card that reached into the
+                                        // viewport last frame moved, cards above it changed
+                                        // height, so move the scroll offset with it.
+                                        if config.scroll_anchoring
+                                            && scroll_to_card.is_none()
+                                            && !*card_detached
+                                            && scroll_y > 0.0
+                                        {
+                                            let top = card_rect.top() - rect.min.y;
+                                            if let Some(shift) = scroll_anchor.and_then(|anchor| {
+                                                anchor.shift(card_identity, top)
+                                            }) {
+                                                anchor_shift = Some(shift);
+                                            }
+                                            if next_anchor.is_none()
+                                                && card_rect.bottom() - rect.min.y > scroll_y
+                                            {
+                                                next_anchor = Some(ScrollAnchor {
+                                                    identity: card_identity,
+                                                    top,
+                                                });
+                                            }
+                                        }
                                         if i + 1 < cards_len {
                                             let separator_top = card_rect.bottom().ceil();
                                             let cursor_top = ui.cursor().top();
@@ -1065,7 +1965,13 @@ impl eframe::App for Notebook {
                                                 }
                                             }
 
-                                            let tab_x = card_rect.right().round();
+                                            // Compact columns fill the window, so the tabs
+                                            // would hang off its edge; tuck them inside.
+                                            let tab_x = if compact {
+                                                (card_rect.right() - tab_size.x - tab_pull).round()
+                                            } else {
+                                                card_rect.right().round()
+                                            };
                                             let top_y =
                                                 (card_rect.top() + top_offset).round();
                                             let detach_pos = egui::pos2(tab_x, top_y);
@@ -1196,9 +2102,30 @@ impl eframe::App for Notebook {
                                             });
                                         }
 
-                                    });
+                                    }));
+                                    // Cards read at least their own state when drawn, so
+                                    // no reads means the card was skipped this frame.
+                                    if !reads.is_empty() {
+                                        frame_reads.push((card_identity, reads));
+                                    }
+                                }
+                                notebook.dependencies.lock().record(frame_reads, &notebook.cards);
+                                runtime.scroll_anchor = next_anchor;
+                                if let Some(shift) = anchor_shift {
+                                    runtime.scroll_correction = Some((scroll_y + shift).max(0.0));
+                                    // Redo the frame at the corrected offset
+                                    // rather than show the jump for a frame.
+                                    if runtime.anchor_discards < MAX_ANCHOR_DISCARDS {
+                                        runtime.anchor_discards += 1;
+                                        ui.ctx().request_discard("scroll anchoring");
+                                    } else {
+                                        ui.ctx().request_repaint();
+                                    }
+                                } else {
+                                    runtime.anchor_discards = 0;
                                 }
 
+                                cards::set_note_color(ui.ctx(), None);
                                 ui.style_mut().spacing.item_spacing = default_item_spacing;
 
                             });
@@ -1209,8 +2136,12 @@ impl eframe::App for Notebook {
                             egui::pos2(column_rect.max.x, frame_rect.max.y),
                         );
                         let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
-                        ui.painter()
-                            .rect_stroke(frame_rect, 0.0, stroke, egui::StrokeKind::Inside);
+                        ui.painter().rect_stroke(
+                            frame_rect,
+                            themes::corner_radius(ui.ctx()),
+                            stroke,
+                            egui::StrokeKind::Inside,
+                        );
 
                         // Extend scroll content to include content-anchored
                         // floating cards. The comparison is in *heights*,
@@ -1238,10 +2169,146 @@ impl eframe::App for Notebook {
         // the last couple of frames.
         search::render_bar(&ctx);
 
+        let action = palette::show(&ctx, || palette_entries(config, &notebook, &runtime));
+        if let Some(action) = action {
+            match action {
+                palette::PaletteAction::JumpToCard(index) => {
+                    runtime.scroll_to_card = Some(index);
+                }
+                palette::PaletteAction::SetTheme(preference) => ctx.set_theme(preference),
+                palette::PaletteAction::DockAllCards => runtime.card_detached.fill(false),
+                #[cfg(feature = "serde")]
+                palette::PaletteAction::CopyShareString => ctx.copy_text(notebook.encode_state()),
+            }
+            ctx.request_repaint();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(hud) = perf_hud {
+            hud.finish_frame(frame_start.elapsed());
+            hud.show(&ctx);
+            // Keep the overlay live even when nothing else repaints.
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+
         ctx.data_mut(|data| {
             data.insert_temp(state_id, runtime);
         });
+        self.core.config.header_actions = header_actions;
+    }
+}
+
+/// Handles `run_native` failing before the notebook could be shown.
+#[cfg(not(target_arch = "wasm32"))]
+fn display_unavailable(err: eframe::Error) -> eframe::Result {
+    if std::env::var_os("GORBIE_HEADLESS").is_some() {
+        log::warn!("headless: notebook not displayed ({err})");
+        return Ok(());
     }
+    Err(eframe::Error::AppCreation(
+        format!(
+            "could not create a window or GPU context: {err}. \
+             Set GORBIE_HEADLESS=1 to skip displaying the notebook, \
+             or pass --headless to capture cards to PNG instead."
+        )
+        .into(),
+    ))
+}
+
+/// The notebook's entries in every card's right-click menu.
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+fn card_context_menu(
+    ui: &mut egui::Ui,
+    config: &NotebookConfig,
+    source: Option<&SourceLocation>,
+    card_detached: &mut bool,
+) {
+    if let Some(source) = source {
+        if ui.button("Copy source location").clicked() {
+            ui.ctx().copy_text(source.file_line_column());
+            ui.close();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(editor) = config.editor.as_ref() {
+            if ui.button("Open in editor").clicked() {
+                if let Err(err) = editor.open(source) {
+                    log::warn!("failed to open editor: {err}");
+                }
+                ui.close();
+            }
+        }
+    }
+    if ui.button("Detach card").clicked() {
+        *card_detached = true;
+        ui.close();
+    }
+    ui.separator();
+}
+
+/// Actions offered by the ctrl+K command palette.
+fn palette_entries(
+    config: &NotebookConfig,
+    notebook: &NotebookCtx,
+    runtime: &NotebookState,
+) -> Vec<palette::PaletteEntry> {
+    use palette::{PaletteAction, PaletteEntry};
+
+    let mut entries: Vec<PaletteEntry> = notebook
+        .cards
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let label = match entry.source.as_ref() {
+                Some(source) => format!("Go to card {} · {}", index + 1, source.file_line_column()),
+                None => format!("Go to card {}", index + 1),
+            };
+            PaletteEntry {
+                label,
+                action: PaletteAction::JumpToCard(index),
+            }
+        })
+        .collect();
+    if config.forced_theme.is_none() {
+        for (name, preference) in [
+            ("system", egui::ThemePreference::System),
+            ("dark", egui::ThemePreference::Dark),
+            ("light", egui::ThemePreference::Light),
+        ] {
+            entries.push(PaletteEntry {
+                label: format!("Theme: {name}"),
+                action: PaletteAction::SetTheme(preference),
+            });
+        }
+    }
+    if runtime.card_detached.contains(&true) {
+        entries.push(PaletteEntry {
+            label: "Dock all cards".to_owned(),
+            action: PaletteAction::DockAllCards,
+        });
+    }
+    #[cfg(feature = "serde")]
+    if notebook.cards.iter().any(|entry| entry.card.has_savable_state()) {
+        entries.push(PaletteEntry {
+            label: "Copy share string".to_owned(),
+            action: PaletteAction::CopyShareString,
+        });
+    }
+    entries
+}
+
+/// Height reserved for a virtualized card: its last drawn height, else its
+/// measurement, else its minimum height, and never less than that minimum.
+/// `None` when nothing is known, so the card gets drawn to find out.
+fn reserved_height(
+    drawn: f32,
+    measure: impl FnOnce() -> Option<f32>,
+    min_height: Option<f32>,
+) -> Option<f32> {
+    (drawn > 0.0)
+        .then_some(drawn)
+        .or_else(measure)
+        .or(min_height)
+        .map(|height| height.max(min_height.unwrap_or(0.0)))
 }
 
 fn draw_card_body(
@@ -1260,13 +2327,18 @@ fn draw_card_body(
         restore
     });
 
+    let min_height = card.min_height();
     let inner = egui::Frame::group(ui.style())
         .stroke(egui::Stroke::NONE)
-        .corner_radius(0.0)
+        .corner_radius(themes::corner_radius(ui.ctx()))
         .inner_margin(egui::Margin::ZERO)
         .show(ui, |ui| {
             ui.reset_style();
             ui.set_width(card_width);
+            if let Some(min_height) = min_height {
+                ui.set_min_height(min_height);
+            }
+            cards::set_note_color(ui.ctx(), card.note_color());
             let mut ctx = CardCtx::new(ui, store);
             card.draw(&mut ctx);
         });
@@ -1288,7 +2360,7 @@ fn paint_dot_grid(ui: &egui::Ui, rect: egui::Rect, scroll_y: f32) {
     let radius = 1.2;
     let background = ui.visuals().window_fill;
     let outline = ui.visuals().widgets.noninteractive.bg_stroke.color;
-    let color = crate::themes::blend(background, outline, 0.35);
+    let color = crate::themes::blend_oklab(background, outline, 0.35);
 
     let start_x = (rect.left() / spacing).floor() * spacing + spacing / 2.0;
     let start_y = rect.top() - scroll_y.rem_euclid(spacing) + spacing / 2.0;
@@ -1321,7 +2393,7 @@ fn paint_hatching(painter: &egui::Painter, rect: egui::Rect, color: egui::Color3
 
 pub(crate) fn show_postit_tooltip(ui: &egui::Ui, response: &egui::Response, text: &str) {
     let outline = ui.visuals().widgets.noninteractive.bg_stroke.color;
-    let shadow_color = crate::themes::ral(9004);
+    let shadow_color = ui.visuals().popup_shadow.color;
     let shadow = egui::epaint::Shadow {
         offset: [4, 4],
         blur: 0,
@@ -1330,10 +2402,10 @@ pub(crate) fn show_postit_tooltip(ui: &egui::Ui, response: &egui::Response, text
     };
 
     let frame = egui::Frame::new()
-        .fill(crate::themes::ral(1003))
+        .fill(cards::note_color(ui.ctx()))
         .stroke(egui::Stroke::new(1.0, outline))
         .shadow(shadow)
-        .corner_radius(0.0)
+        .corner_radius(themes::corner_radius(ui.ctx()))
         .inner_margin(egui::Margin::same(10));
 
     let mut tooltip = egui::containers::Tooltip::for_enabled(response);
@@ -1391,3 +2463,118 @@ fn paint_card_tab_button(
 }
 
 // notebook initialization is handled by the #[notebook] attribute macro.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_notebook() -> NotebookCtx {
+        NotebookCtx::new(
+            &NotebookConfig::new("test"),
+            Arc::new(state::StateStore::default()),
+            Arc::new(AtomicBool::new(false)),
+            Arc::default(),
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn share_strings_round_trip_card_state() {
+        let mut nb = test_notebook();
+        nb.state("plain", 0u32, |_, _| {});
+        let count = nb.session_state("count", 0u32, |_, _| {});
+        assert!(nb.cards.iter().any(|entry| entry.card.has_savable_state()));
+        *count.read_mut(&nb) = 7;
+        let share = nb.encode_state();
+
+        *count.read_mut(&nb) = 0;
+        nb.apply_state(&share).expect("share string decodes");
+        assert_eq!(*count.read(&nb), 7);
+        assert!(nb.apply_state("not a share string").is_err());
+        assert_eq!(*count.read(&nb), 7);
+    }
+
+    #[test]
+    fn views_added_in_a_loop_get_distinct_outputs() {
+        let mut nb = test_notebook();
+        let outputs: Vec<_> = (0..2).map(|_| nb.view_with_output(|_| ())).collect();
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(nb.cards[0].identity, nb.cards[1].identity);
+    }
+
+    #[test]
+    fn outputs_of_removed_views_are_dropped() {
+        let show = Arc::new(AtomicBool::new(true));
+        let handle = Arc::new(parking_lot::Mutex::new(None));
+        let (body_show, body_handle) = (show.clone(), handle.clone());
+        let mut core = NotebookCore::new(
+            NotebookConfig::new("test"),
+            Box::new(move |nb: &mut NotebookCtx| {
+                if body_show.load(Ordering::Relaxed) {
+                    *body_handle.lock() = Some(nb.view_with_output(|_| 1u32));
+                }
+            }),
+        );
+        core.build_notebook();
+        let output = handle.lock().expect("view was added");
+        assert!(core.state_store.try_get(output).is_some());
+
+        show.store(false, Ordering::Relaxed);
+        core.build_notebook();
+        assert!(core.state_store.try_get(output).is_none());
+    }
+
+    #[test]
+    fn clear_resets_identities_and_drops_unused_state() {
+        let re_add = Arc::new(AtomicBool::new(true));
+        let handles = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let (body_re_add, body_handles) = (re_add.clone(), handles.clone());
+        let mut core = NotebookCore::new(
+            NotebookConfig::new("test"),
+            Box::new(move |nb: &mut NotebookCtx| {
+                let mut handles = body_handles.lock();
+                handles.clear();
+                let mut add = |nb: &mut NotebookCtx| handles.push(nb.view_with_output(|_| 1u32));
+                add(nb);
+                nb.clear();
+                if body_re_add.load(Ordering::Relaxed) {
+                    add(nb);
+                }
+            }),
+        );
+        core.build_notebook();
+        let (before, after) = {
+            let handles = handles.lock();
+            (handles[0], handles[1])
+        };
+        // The re-added view takes the identity of the one that was cleared.
+        assert_eq!(before, after);
+        assert!(core.state_store.try_get(after).is_some());
+
+        re_add.store(false, Ordering::Relaxed);
+        core.build_notebook();
+        assert!(core.state_store.try_get(before).is_none());
+    }
+
+    #[test]
+    fn reserved_height_falls_back_to_min_height() {
+        assert_eq!(reserved_height(0.0, || None, None), None);
+        // First frame of an async card: nothing drawn or measured yet.
+        assert_eq!(reserved_height(0.0, || None, Some(200.0)), Some(200.0));
+        assert_eq!(reserved_height(0.0, || Some(80.0), Some(200.0)), Some(200.0));
+        assert_eq!(reserved_height(240.0, || Some(80.0), Some(200.0)), Some(240.0));
+    }
+
+    #[test]
+    fn scroll_anchor_follows_its_card_by_identity() {
+        let anchored = egui::Id::new("anchored");
+        let anchor = ScrollAnchor {
+            identity: anchored,
+            top: 100.0,
+        };
+        assert_eq!(anchor.shift(anchored, 100.0), None);
+        assert_eq!(anchor.shift(anchored, 130.0), Some(30.0));
+        // Other cards never move the anchor, wherever they are.
+        assert_eq!(anchor.shift(egui::Id::new("other"), 130.0), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Command palette: ctrl+K (cmd+K on macOS) opens a searchable list of
+//! notebook actions, filtered as you type.
+
+use egui::{Align2, Area, Frame, Id, Key, KeyboardShortcut, Margin, Modifiers};
+
+use crate::search::contains_case_insensitive_ascii;
+use crate::themes::{corner_radius, ral};
+use crate::widgets::TextField;
+
+const SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::K);
+const PALETTE_WIDTH: f32 = 420.0;
+const MAX_VISIBLE_ROWS: usize = 12;
+
+/// Something the palette can do; the notebook carries it out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PaletteAction {
+    JumpToCard(usize),
+    SetTheme(egui::ThemePreference),
+    DockAllCards,
+    #[cfg(feature = "serde")]
+    CopyShareString,
+}
+
+pub(crate) struct PaletteEntry {
+    pub(crate) label: String,
+    pub(crate) action: PaletteAction,
+}
+
+#[derive(Clone, Default)]
+struct PaletteState {
+    open: bool,
+    just_opened: bool,
+    query: String,
+    selected: usize,
+}
+
+fn state_id() -> Id {
+    Id::new("gorbie_command_palette")
+}
+
+/// Handles the shortcut and, while open, renders the palette. `entries` is
+/// only called when the palette is open. Returns the action the user
+/// picked this frame.
+pub(crate) fn show(
+    ctx: &egui::Context,
+    entries: impl FnOnce() -> Vec<PaletteEntry>,
+) -> Option<PaletteAction> {
+    let mut state: PaletteState = ctx.data(|d| d.get_temp(state_id())).unwrap_or_default();
+    if ctx.input_mut(|i| i.consume_shortcut(&SHORTCUT)) {
+        state = PaletteState {
+            open: !state.open,
+            just_opened: true,
+            ..PaletteState::default()
+        };
+    }
+    if state.open && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+        state.open = false;
+    }
+    if !state.open {
+        ctx.data_mut(|d| d.insert_temp(state_id(), state));
+        return None;
+    }
+
+    // Consume navigation keys before the text field sees them.
+    let (up, down, enter) = ctx.input_mut(|i| {
+        (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+            i.consume_key(Modifiers::NONE, Key::Enter),
+        )
+    });
+
+    let entries = entries();
+    let mut chosen = None;
+    let area = Area::new(state_id())
+        .order(egui::Order::Foreground)
+        .anchor(Align2::CENTER_TOP, egui::vec2(0.0, 96.0))
+        .show(ctx, |ui| {
+            ui.set_width(PALETTE_WIDTH);
+            let outline = ui.visuals().widgets.noninteractive.bg_stroke.color;
+            Frame::new()
+                .fill(ral(1003))
+                .stroke(egui::Stroke::new(1.0, outline))
+                .shadow(egui::epaint::Shadow {
+                    offset: [4, 4],
+                    blur: 0,
+                    spread: 0,
+                    color: ral(9004),
+                })
+                .corner_radius(corner_radius(ui.ctx()))
+                .inner_margin(Margin::same(10))
+                .show(ui, |ui| {
+                    let response = ui.add(TextField::singleline(&mut state.query));
+                    if std::mem::take(&mut state.just_opened) {
+                        response.request_focus();
+                    }
+                    if response.changed() {
+                        state.selected = 0;
+                    }
+
+                    let needle = state.query.to_ascii_lowercase();
+                    let matches: Vec<&PaletteEntry> = entries
+                        .iter()
+                        .filter(|entry| contains_case_insensitive_ascii(&entry.label, needle.as_bytes()))
+                        .collect();
+                    let last = matches.len().saturating_sub(1);
+                    if down {
+                        state.selected += 1;
+                    }
+                    if up {
+                        state.selected = state.selected.saturating_sub(1);
+                    }
+                    state.selected = state.selected.min(last);
+                    if enter {
+                        chosen = matches.get(state.selected).map(|entry| entry.action);
+                    }
+
+                    ui.add_space(6.0);
+                    if matches.is_empty() {
+                        ui.label(
+                            egui::RichText::new("NO MATCHING ACTIONS")
+                                .monospace()
+                                .color(ral(9011)),
+                        );
+                    }
+                    let first = state.selected.saturating_sub(MAX_VISIBLE_ROWS - 1);
+                    for (index, entry) in matches.iter().enumerate().skip(first).take(MAX_VISIBLE_ROWS) {
+                        if palette_row(ui, &entry.label, index == state.selected).clicked() {
+                            chosen = Some(entry.action);
+                        }
+                    }
+                });
+        });
+
+    let clicked_outside =
+        ctx.input(|i| i.pointer.any_pressed()) && !area.response.contains_pointer();
+    if chosen.is_some() || clicked_outside {
+        state.open = false;
+    }
+    ctx.data_mut(|d| d.insert_temp(state_id(), state));
+    chosen
+}
+
+fn palette_row(ui: &mut egui::Ui, label: &str, selected: bool) -> egui::Response {
+    let font = egui::TextStyle::Monospace.resolve(ui.style());
+    let height = ui.fonts_mut(|f| f.row_height(&font)) + 6.0;
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), height),
+        egui::Sense::click(),
+    );
+    let ink = ral(9011);
+    let text_color = if selected || response.hovered() {
+        ui.painter().rect_filled(rect, 0.0, ink);
+        ral(1003)
+    } else {
+        ink
+    };
+    ui.painter().text(
+        rect.left_center() + egui::vec2(6.0, 0.0),
+        Align2::LEFT_CENTER,
+        label,
+        font,
+        text_color,
+    );
+    response
+}
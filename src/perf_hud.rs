@@ -0,0 +1,103 @@
+//! Frame-time overlay enabled by
+//! [`NotebookConfig::with_perf_hud`](crate::NotebookConfig::with_perf_hud).
+
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::dataflow::RingHistory;
+
+/// Number of frames kept for the sparkline.
+const HISTORY_LEN: usize = 120;
+const SPARKLINE_SIZE: egui::Vec2 = egui::vec2(160.0, 32.0);
+/// Frame budget drawn as a reference line in the sparkline (60 Hz).
+const FRAME_BUDGET_MS: f32 = 1000.0 / 60.0;
+
+/// Rolling frame and card timings for the overlay.
+pub(crate) struct PerfHud {
+    frame_ms: RingHistory<f32>,
+    slowest_card: Option<(String, Duration)>,
+    frame_slowest_card: Option<(String, Duration)>,
+}
+
+impl Default for PerfHud {
+    fn default() -> Self {
+        Self {
+            frame_ms: RingHistory::new(HISTORY_LEN),
+            slowest_card: None,
+            frame_slowest_card: None,
+        }
+    }
+}
+
+impl PerfHud {
+    /// Records how long a card took to draw this frame; `label` names it
+    /// in the overlay and is only built for a new slowest card.
+    pub(crate) fn record_card(&mut self, elapsed: Duration, label: impl FnOnce() -> String) {
+        if self
+            .frame_slowest_card
+            .as_ref()
+            .is_none_or(|(_, slowest)| elapsed > *slowest)
+        {
+            self.frame_slowest_card = Some((label(), elapsed));
+        }
+    }
+
+    /// Closes the current frame after the card loop took `elapsed`.
+    pub(crate) fn finish_frame(&mut self, elapsed: Duration) {
+        self.frame_ms.push(elapsed.as_secs_f32() * 1000.0);
+        self.slowest_card = self.frame_slowest_card.take();
+    }
+
+    /// Paints the overlay in the bottom-right corner of the window.
+    pub(crate) fn show(&self, ctx: &egui::Context) {
+        let Some(&last) = self.frame_ms.last() else {
+            return;
+        };
+        let mean = self.frame_ms.iter().sum::<f32>() / self.frame_ms.len() as f32;
+        let peak = self.frame_ms.iter().copied().fold(0.0_f32, f32::max);
+
+        egui::Area::new(egui::Id::new("gorbie_perf_hud"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(ui.visuals().window_fill)
+                    .stroke(ui.visuals().widgets.noninteractive.bg_stroke)
+                    .inner_margin(egui::Margin::same(8))
+                    .show(ui, |ui| {
+                        let mono = |text: String| egui::RichText::new(text).monospace().small();
+                        ui.label(mono(format!("FRAME {last:5.2} MS")));
+                        ui.label(mono(format!("MEAN  {mean:5.2} MS  PEAK {peak:5.2} MS")));
+                        if let Some((label, elapsed)) = self.slowest_card.as_ref() {
+                            let ms = elapsed.as_secs_f32() * 1000.0;
+                            ui.label(mono(format!("SLOWEST {ms:5.2} MS  {label}")));
+                        }
+                        self.paint_sparkline(ui, peak);
+                    });
+            });
+    }
+
+    fn paint_sparkline(&self, ui: &mut egui::Ui, peak: f32) {
+        let (rect, _) = ui.allocate_exact_size(SPARKLINE_SIZE, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let scale = peak.max(FRAME_BUDGET_MS * 1.25);
+        let y_of = |ms: f32| rect.bottom() - (ms / scale).min(1.0) * rect.height();
+
+        let hairline = ui.visuals().widgets.noninteractive.bg_stroke;
+        let budget_y = y_of(FRAME_BUDGET_MS);
+        painter.hline(rect.x_range(), budget_y, hairline);
+
+        let step = rect.width() / (HISTORY_LEN - 1) as f32;
+        let offset = HISTORY_LEN - self.frame_ms.len();
+        let points: Vec<egui::Pos2> = self
+            .frame_ms
+            .iter()
+            .enumerate()
+            .map(|(i, &ms)| egui::pos2(rect.left() + (offset + i) as f32 * step, y_of(ms)))
+            .collect();
+        let accent = ui.visuals().selection.stroke.color;
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, accent)));
+    }
+}
@@ -11,6 +11,8 @@ pub use crate::card_ctx::GRID_GUTTER;
 pub use crate::card_ctx::FloatResponse;
 pub use crate::card_ctx::GRID_ROW_MODULE;
 pub use crate::dataflow::ComputedState;
+pub use crate::dataflow::History;
+pub use crate::dataflow::Signal;
 #[cfg(feature = "markdown")]
 pub use crate::md;
 #[cfg(feature = "markdown")]
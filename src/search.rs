@@ -220,6 +220,33 @@ fn paint_double_underline_stripe(
     painter.rect_filled(stripe, egui::CornerRadius::ZERO, color);
 }
 
+/// True when `haystack` contains `needle_lc`, comparing ASCII letters
+/// case-insensitively. `needle_lc` must already be lowercased; an empty
+/// needle matches everything. Allocation-free, for filtering long lists
+/// every frame.
+pub fn contains_case_insensitive_ascii(haystack: &str, needle_lc: &[u8]) -> bool {
+    if needle_lc.is_empty() {
+        return true;
+    }
+    let hay = haystack.as_bytes();
+    if needle_lc.len() > hay.len() {
+        return false;
+    }
+    for start in 0..=hay.len().saturating_sub(needle_lc.len()) {
+        let mut ok = true;
+        for (offset, &needle) in needle_lc.iter().enumerate() {
+            if hay[start + offset].to_ascii_lowercase() != needle {
+                ok = false;
+                break;
+            }
+        }
+        if ok {
+            return true;
+        }
+    }
+    false
+}
+
 /// Wrapper so we can `insert_temp` an `Option<Id>` (egui's
 /// `remove_temp`/`get_temp_mut_or_default` require `Default`, which
 /// `egui::Id` itself doesn't implement).
@@ -376,7 +403,7 @@ pub(crate) fn render_bar(ctx: &egui::Context) {
                     spread: 0,
                     color: egui::Color32::from_black_alpha(48),
                 })
-                .corner_radius(egui::CornerRadius::ZERO)
+                .corner_radius(crate::themes::corner_radius(ui.ctx()))
                 .inner_margin(Margin::symmetric(6, 4))
                 .show(ui, |ui| {
                     ui.horizontal(|ui| {
@@ -0,0 +1,60 @@
+//! Share strings: notebook state as unpadded URL-safe base64 of compact
+//! JSON, short enough to paste into a bug report or a URL query.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for i in 0..=chunk.len() {
+            let sextet = (bits >> (18 - 6 * i)) & 0x3f;
+            out.push(ALPHABET[sextet as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decodes [`encode`]'s output; surrounding whitespace is ignored.
+pub(crate) fn decode(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim().as_bytes();
+    if text.len() % 4 == 1 {
+        return Err("share string has a truncated final group".to_owned());
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3 + 2);
+    for chunk in text.chunks(4) {
+        let mut bits = 0u32;
+        for (i, &byte) in chunk.iter().enumerate() {
+            let sextet = ALPHABET
+                .iter()
+                .position(|&c| c == byte)
+                .ok_or_else(|| format!("invalid character {:?} in share string", byte as char))?;
+            bits |= (sextet as u32) << (18 - 6 * i);
+        }
+        out.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_tail_length() {
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 251) as u8).collect();
+            assert_eq!(decode(&encode(&bytes)).unwrap(), bytes);
+        }
+        assert_eq!(encode(b"[null]"), "W251bGxd");
+    }
+
+    #[test]
+    fn rejects_malformed_strings() {
+        assert!(decode("W251b=xd").is_err());
+        assert!(decode("W251b").is_err());
+        assert!(decode("  W251bGxd\n").is_ok());
+    }
+}
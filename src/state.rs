@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -22,8 +23,34 @@ pub struct StateStore {
     states: RwLock<HashMap<egui::Id, Arc<dyn Any + Send + Sync>>>,
 }
 
+thread_local! {
+    /// States read on this thread while [`record_reads`] is active.
+    static READ_LOG: RefCell<Option<Vec<DependencyKey>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns the states it read through any [`StateStore`] on
+/// this thread, deduplicated in first-read order.
+pub(crate) fn record_reads<R>(f: impl FnOnce() -> R) -> (R, Vec<DependencyKey>) {
+    let outer = READ_LOG.with(|log| log.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let reads = READ_LOG.with(|log| std::mem::replace(&mut *log.borrow_mut(), outer));
+    (result, reads.unwrap_or_default())
+}
+
+fn note_read(id: egui::Id) {
+    READ_LOG.with(|log| {
+        if let Some(reads) = log.borrow_mut().as_mut() {
+            let key = DependencyKey(id);
+            if !reads.contains(&key) {
+                reads.push(key);
+            }
+        }
+    });
+}
+
 impl StateStore {
     fn get_raw<T: Send + Sync + 'static>(&self, id: egui::Id) -> Option<Arc<RwLock<T>>> {
+        note_read(id);
         let entry = self.states.read().get(&id).cloned()?;
         entry.downcast::<RwLock<T>>().ok()
     }
@@ -95,6 +122,11 @@ impl StateStore {
     ) -> Option<ArcWriteGuard<T>> {
         self.try_get(id).and_then(|state| state.try_write_arc())
     }
+
+    /// Drops the state stored under `key`; handles to it read as missing.
+    pub(crate) fn remove(&self, key: DependencyKey) {
+        self.states.write().remove(&key.0);
+    }
 }
 
 /// Typed handle for a value stored in a [`StateStore`].
@@ -126,6 +158,23 @@ impl<T> StateId<T> {
     pub(crate) fn id(self) -> egui::Id {
         self.id
     }
+
+    /// Returns the type-erased key naming this state in a
+    /// [`DependencyGraph`](crate::cards::DependencyGraph).
+    pub fn key(self) -> DependencyKey {
+        DependencyKey(self.id)
+    }
+}
+
+/// Type-erased [`StateId`], used to describe which states a card owns and
+/// reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DependencyKey(egui::Id);
+
+impl<T> From<StateId<T>> for DependencyKey {
+    fn from(id: StateId<T>) -> Self {
+        id.key()
+    }
 }
 
 /// Trait for anything that provides access to the shared [`StateStore`].
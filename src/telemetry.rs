@@ -1,6 +1,519 @@
 //! Tracing telemetry support re-exported from `triblespace`.
 //!
 //! Enable GORBIE's `telemetry` feature and set `TELEMETRY_PILE`
-//! to capture spans into a dedicated telemetry pile.
+//! to capture spans into a dedicated telemetry pile. To instrument your own
+//! cards with spans named at runtime, open a [`SpanRecorder`] and wrap work
+//! in [`record_span`].
 
 pub use triblespace::telemetry::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use recorder::{flush_spans, record_span, recording, RecordedSpan, SpanRecorder};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod recorder {
+    use std::cell::RefCell;
+    use std::path::Path;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+    use std::sync::OnceLock;
+    use std::thread::JoinHandle;
+    use std::time::{Duration, Instant};
+
+    use ed25519_dalek::SigningKey;
+    use rand_core06::OsRng;
+    use triblespace::core::blob::encodings::longstring::LongString;
+    use triblespace::core::id::{fucid, ExclusiveId, Id};
+    use triblespace::core::inline::encodings::iu256::U256BE;
+    use triblespace::core::inline::Inline;
+    use triblespace::core::metadata;
+    use triblespace::core::repo::pile::Pile;
+    use triblespace::core::repo::Repository;
+    use triblespace::core::trible::TribleSet;
+    use triblespace::macros::entity;
+
+    use super::schema as t;
+
+    /// The writer thread pushes pending spans at most this often.
+    const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+    static GLOBAL: OnceLock<SpanRecorder> = OnceLock::new();
+
+    thread_local! {
+        /// Recorded spans open on this thread, innermost last.
+        static OPEN: RefCell<Vec<Id>> = const { RefCell::new(Vec::new()) };
+    }
+
+    enum SpanFact {
+        Begin {
+            id: Id,
+            parent: Option<Id>,
+            category: String,
+            name: String,
+            source: Option<String>,
+            begin_ns: u64,
+        },
+        End {
+            id: Id,
+            duration_ns: u64,
+        },
+    }
+
+    enum Message {
+        Fact(SpanFact),
+        /// Push everything pending and report the result.
+        Flush(mpsc::Sender<Result<(), String>>),
+    }
+
+    /// The pile side of a recorder, owned by its writer thread.
+    struct RecorderState {
+        repo: Repository<Pile>,
+        branch_id: Id,
+        session: Id,
+        session_name: Option<String>,
+        pending: Vec<SpanFact>,
+    }
+
+    /// Writes spans straight into a telemetry pile, in the same schema the
+    /// `tracing` sink uses, so the telemetry viewer shows them next to
+    /// GORBIE's own frame and card spans.
+    ///
+    /// Each recorder opens a `telemetry` branch with one session. Span
+    /// begin facts are written when a span starts and its duration when the
+    /// [`RecordedSpan`] guard drops; spans opened while another is open on
+    /// the same thread become its children. Recording only queues facts on
+    /// a channel: a background writer thread pushes them to the pile in
+    /// batches every 250 ms, on [`flush`](Self::flush), and once more when
+    /// the recorder is dropped, so pile I/O never runs on the thread that
+    /// records.
+    pub struct SpanRecorder {
+        origin: Instant,
+        sender: Option<mpsc::Sender<Message>>,
+        writer: Option<JoinHandle<()>>,
+    }
+
+    impl SpanRecorder {
+        /// Opens (or creates) the pile at `path` and starts a session named
+        /// `session_name` on a fresh `telemetry` branch.
+        pub fn open(path: impl AsRef<Path>, session_name: &str) -> Result<Self, String> {
+            let path = path.as_ref();
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| format!("create pile: {err}"))?;
+            let mut pile = Pile::open(path).map_err(|err| format!("open pile: {err:?}"))?;
+            pile.refresh().map_err(|err| format!("refresh pile: {err:?}"))?;
+            let mut repo = Repository::new(pile, SigningKey::generate(&mut OsRng), TribleSet::new())
+                .map_err(|err| format!("open repository: {err:?}"))?;
+            let branch_id = *repo
+                .create_branch("telemetry", None)
+                .map_err(|err| format!("create branch: {err:?}"))?;
+            let state = RecorderState {
+                repo,
+                branch_id,
+                session: *fucid(),
+                session_name: Some(session_name.to_owned()),
+                pending: Vec::new(),
+            };
+            let (sender, receiver) = mpsc::channel();
+            let writer = std::thread::Builder::new()
+                .name("gorbie-telemetry".to_owned())
+                .spawn(move || write_loop(state, receiver))
+                .map_err(|err| format!("spawn writer: {err}"))?;
+            Ok(Self {
+                origin: Instant::now(),
+                sender: Some(sender),
+                writer: Some(writer),
+            })
+        }
+
+        /// Makes this the recorder used by [`record_span`]. Fails (handing
+        /// the recorder back) if one is already installed.
+        pub fn install_global(self) -> Result<(), Self> {
+            GLOBAL.set(self)
+        }
+
+        /// Starts a span; it ends when the returned guard is dropped.
+        /// `category` is stored as a short string and cut to 32 bytes.
+        pub fn span(&self, category: &str, name: &str, source: Option<&str>) -> RecordedSpan<'_> {
+            let id = *fucid();
+            let parent = OPEN.with(|open| {
+                let mut open = open.borrow_mut();
+                let parent = open.last().copied();
+                open.push(id);
+                parent
+            });
+            let begin = Instant::now();
+            self.push_fact(SpanFact::Begin {
+                id,
+                parent,
+                category: short_string(category).to_owned(),
+                name: name.to_owned(),
+                source: source.map(str::to_owned),
+                begin_ns: self.elapsed_ns(begin),
+            });
+            RecordedSpan {
+                open: Some((self, id)),
+                begin,
+            }
+        }
+
+        /// Pushes all span facts recorded so far to the pile, blocking until
+        /// the writer thread has done so.
+        pub fn flush(&self) -> Result<(), String> {
+            let Some(sender) = &self.sender else {
+                return Ok(());
+            };
+            let (reply, done) = mpsc::channel();
+            sender
+                .send(Message::Flush(reply))
+                .map_err(|_| "writer thread stopped".to_owned())?;
+            done.recv()
+                .map_err(|_| "writer thread stopped".to_owned())?
+        }
+
+        fn elapsed_ns(&self, at: Instant) -> u64 {
+            u64::try_from(at.duration_since(self.origin).as_nanos()).unwrap_or(u64::MAX)
+        }
+
+        fn push_fact(&self, fact: SpanFact) {
+            if let Some(sender) = &self.sender {
+                // Only fails once the writer is gone; the fact is lost then.
+                let _ = sender.send(Message::Fact(fact));
+            }
+        }
+    }
+
+    /// Runs on the writer thread: collects facts and pushes them every
+    /// [`FLUSH_INTERVAL`] while any are pending, on request, and once more
+    /// when the recorder hangs up.
+    fn write_loop(mut state: RecorderState, receiver: mpsc::Receiver<Message>) {
+        let mut last_flush = Instant::now();
+        loop {
+            let message = if state.is_idle() {
+                receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                receiver.recv_timeout(FLUSH_INTERVAL.saturating_sub(last_flush.elapsed()))
+            };
+            match message {
+                Ok(Message::Fact(fact)) => state.pending.push(fact),
+                Ok(Message::Flush(reply)) => {
+                    last_flush = Instant::now();
+                    let _ = reply.send(state.flush());
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    if let Err(err) = state.flush() {
+                        log::warn!("telemetry recorder: {err}");
+                    }
+                    return;
+                }
+            }
+            if last_flush.elapsed() >= FLUSH_INTERVAL {
+                last_flush = Instant::now();
+                if let Err(err) = state.flush() {
+                    log::warn!("telemetry recorder: {err}");
+                }
+            }
+        }
+    }
+
+    impl RecorderState {
+        fn is_idle(&self) -> bool {
+            self.pending.is_empty() && self.session_name.is_none()
+        }
+
+        fn flush(&mut self) -> Result<(), String> {
+            if self.is_idle() {
+                return Ok(());
+            }
+            let session = self.session;
+            let mut ws = self
+                .repo
+                .pull(self.branch_id)
+                .map_err(|err| format!("pull: {err:?}"))?;
+            let mut facts = TribleSet::new();
+            let session_ref = ExclusiveId::force_ref(&session);
+            if let Some(name) = self.session_name.take() {
+                let name = ws.put::<LongString, _>(name);
+                facts += entity! { session_ref @
+                    metadata::tag: t::kind_session,
+                    t::name: name,
+                };
+            }
+            for fact in self.pending.drain(..) {
+                match fact {
+                    SpanFact::Begin {
+                        id,
+                        parent,
+                        category,
+                        name,
+                        source,
+                        begin_ns,
+                    } => {
+                        let span = ExclusiveId::force_ref(&id);
+                        let name = ws.put::<LongString, _>(name);
+                        facts += entity! { span @
+                            metadata::tag: t::kind_span,
+                            t::session: session,
+                            t::category: category.as_str(),
+                            t::name: name,
+                            t::begin_ns: u256be(begin_ns),
+                        };
+                        if let Some(parent) = parent {
+                            facts += entity! { span @ t::parent: parent };
+                        }
+                        if let Some(source) = source {
+                            let source = ws.put::<LongString, _>(source);
+                            facts += entity! { span @ t::source: source };
+                        }
+                    }
+                    SpanFact::End { id, duration_ns } => {
+                        let span = ExclusiveId::force_ref(&id);
+                        facts += entity! { span @ t::duration_ns: u256be(duration_ns) };
+                    }
+                }
+            }
+            ws.commit(facts, "telemetry spans");
+            self.repo
+                .push(&mut ws)
+                .map_err(|err| format!("push: {err:?}"))?;
+            Ok(())
+        }
+    }
+
+    impl Drop for SpanRecorder {
+        fn drop(&mut self) {
+            // Hanging up makes the writer push what is left and exit.
+            drop(self.sender.take());
+            if let Some(writer) = self.writer.take() {
+                if writer.join().is_err() {
+                    log::warn!("telemetry recorder: writer thread panicked");
+                }
+            }
+        }
+    }
+
+    /// Guard for a span started with [`record_span`] or
+    /// [`SpanRecorder::span`]; records the span's duration when dropped.
+    #[must_use = "the span ends as soon as this guard is dropped"]
+    pub struct RecordedSpan<'a> {
+        open: Option<(&'a SpanRecorder, Id)>,
+        begin: Instant,
+    }
+
+    impl Drop for RecordedSpan<'_> {
+        fn drop(&mut self) {
+            let Some((recorder, id)) = self.open else {
+                return;
+            };
+            OPEN.with(|open| {
+                let mut open = open.borrow_mut();
+                if let Some(pos) = open.iter().rposition(|span| *span == id) {
+                    open.remove(pos);
+                }
+            });
+            let duration_ns = u64::try_from(self.begin.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            recorder.push_fact(SpanFact::End { id, duration_ns });
+        }
+    }
+
+    /// Starts a span on the recorder installed with
+    /// [`SpanRecorder::install_global`]; it ends when the returned guard is
+    /// dropped. Without an installed recorder this is a no-op.
+    ///
+    /// ```no_run
+    /// # fn solve() {}
+    /// GORBIE::telemetry::SpanRecorder::open("notebook.pile", "my notebook")
+    ///     .expect("open telemetry pile")
+    ///     .install_global()
+    ///     .ok();
+    /// let _span = GORBIE::telemetry::record_span("solver", "solve", Some(file!()));
+    /// solve();
+    /// ```
+    pub fn record_span(category: &str, name: &str, source: Option<&str>) -> RecordedSpan<'static> {
+        match GLOBAL.get() {
+            Some(recorder) => recorder.span(category, name, source),
+            None => RecordedSpan {
+                open: None,
+                begin: Instant::now(),
+            },
+        }
+    }
+
+    /// Whether a recorder is installed, so callers can skip building span
+    /// names that [`record_span`] would throw away.
+    pub fn recording() -> bool {
+        GLOBAL.get().is_some()
+    }
+
+    /// Pushes pending spans of the global recorder and waits for the push,
+    /// e.g. before the process exits (the global recorder is never dropped).
+    pub fn flush_spans() -> Result<(), String> {
+        GLOBAL.get().map_or(Ok(()), SpanRecorder::flush)
+    }
+
+    /// Cuts `text` to the 32 bytes a short string holds, on a char boundary.
+    fn short_string(text: &str) -> &str {
+        let mut end = text.len().min(32);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        &text[..end]
+    }
+
+    fn u256be(value: u64) -> Inline<U256BE> {
+        let mut raw = [0u8; 32];
+        raw[24..].copy_from_slice(&value.to_be_bytes());
+        Inline::new(raw)
+    }
+}
+
+/// A node of a collapsed flamegraph: every call of `name` under the same
+/// parent path merged into one, with its own and its total time.
+#[derive(Clone, Debug)]
+pub struct CollapsedSpan {
+    pub category: String,
+    pub name: String,
+    pub source: Option<String>,
+    pub self_ns: u64,
+    pub total_ns: u64,
+    pub children: Vec<CollapsedSpan>,
+}
+
+/// Renders a collapsed flamegraph as CSV with one row per node, depth
+/// first. `path` joins the names from the root down with dots.
+pub fn export_collapsed_csv(nodes: &[CollapsedSpan]) -> String {
+    fn field(out: &mut String, value: &str) {
+        if value.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&value.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(value);
+        }
+    }
+
+    fn walk(out: &mut String, parent: &str, nodes: &[CollapsedSpan]) {
+        for node in nodes {
+            let path = if parent.is_empty() {
+                node.name.clone()
+            } else {
+                format!("{parent}.{}", node.name)
+            };
+            field(out, &path);
+            out.push(',');
+            field(out, &node.category);
+            out.push(',');
+            field(out, &node.name);
+            out.push(',');
+            field(out, node.source.as_deref().unwrap_or(""));
+            out.push_str(&format!(",{},{}\n", node.self_ns, node.total_ns));
+            walk(out, &path, &node.children);
+        }
+    }
+
+    let mut out = String::from("path,category,name,source,self_ns,total_ns\n");
+    walk(&mut out, "", nodes);
+    out
+}
+
+/// Opens a span `source` location (`file:line` or `file:line:column`) in the
+/// editor configured via `GORBIE_EDITOR` — the same opt-in command template
+/// used by the cards' "open in editor" buttons.
+///
+/// Returns `true` if an editor was launched. Malformed sources and an unset
+/// `GORBIE_EDITOR` are a no-op.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_source(source: &str) -> bool {
+    let Some(location) = parse_source(source) else {
+        return false;
+    };
+    let Some(editor) = crate::editor_from_env() else {
+        return false;
+    };
+    match editor.open(&location) {
+        Ok(()) => true,
+        Err(err) => {
+            log::warn!("failed to open {source} in editor: {err}");
+            false
+        }
+    }
+}
+
+/// Parses `file:line` or `file:line:column`, splitting from the right so
+/// paths containing `:` (e.g. Windows drive letters) survive.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_source(source: &str) -> Option<crate::SourceLocation> {
+    let source = source.trim();
+    let (rest, last) = source.rsplit_once(':')?;
+    let last: u32 = last.trim().parse().ok()?;
+    let (file, line, column) = match rest.rsplit_once(':') {
+        Some((file, line)) => match line.trim().parse::<u32>() {
+            Ok(line) => (file, line, last),
+            Err(_) => (rest, last, 1),
+        },
+        None => (rest, last, 1),
+    };
+    let file = file.trim();
+    if file.is_empty() || line == 0 {
+        return None;
+    }
+    Some(crate::SourceLocation {
+        file: file.to_owned(),
+        line,
+        column: column.max(1),
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{export_collapsed_csv, parse_source, CollapsedSpan};
+
+    fn parts(source: &str) -> Option<(String, u32, u32)> {
+        parse_source(source).map(|loc| (loc.file, loc.line, loc.column))
+    }
+
+    #[test]
+    fn parses_file_line_and_column() {
+        assert_eq!(parts("src/lib.rs:42"), Some(("src/lib.rs".into(), 42, 1)));
+        assert_eq!(parts(" src/lib.rs:42:7 "), Some(("src/lib.rs".into(), 42, 7)));
+        assert_eq!(
+            parts(r"C:\work\main.rs:3:9"),
+            Some((r"C:\work\main.rs".into(), 3, 9))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_sources() {
+        assert_eq!(parts(""), None);
+        assert_eq!(parts("src/lib.rs"), None);
+        assert_eq!(parts("src/lib.rs:abc"), None);
+        assert_eq!(parts(":12"), None);
+        assert_eq!(parts("src/lib.rs:0"), None);
+    }
+
+    #[test]
+    fn collapsed_csv_has_one_row_per_node_with_dotted_paths() {
+        let span = |name: &str, self_ns, total_ns, children| CollapsedSpan {
+            category: "test".to_owned(),
+            name: name.to_owned(),
+            source: None,
+            self_ns,
+            total_ns,
+            children,
+        };
+        let mut leaf = span("level2", 10, 10, Vec::new());
+        leaf.source = Some("src/a, b.rs:3".to_owned());
+        let csv = export_collapsed_csv(&[span("level1", 10, 20, vec![leaf])]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "path,category,name,source,self_ns,total_ns");
+        assert_eq!(lines[1], "level1,test,level1,,10,20");
+        assert_eq!(
+            lines[2],
+            "level1.level2,test,level2,\"src/a, b.rs:3\",10,10"
+        );
+    }
+}
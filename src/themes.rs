@@ -331,6 +331,108 @@ pub fn blend(a: Color32, b: Color32, t: f32) -> Color32 {
     Color32::from_rgb(r, g, bch)
 }
 
+/// Interpolate between two colors in the OKLab perceptual color space.
+///
+/// `t = 0.0` returns `a`, `t = 1.0` returns `b`. Unlike [`blend`], the
+/// midpoints keep their perceived lightness and chroma, so faded colors
+/// stay distinguishable on dark backgrounds instead of turning muddy.
+/// Alpha is mixed linearly.
+pub fn blend_oklab(a: Color32, b: Color32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+    let la = srgb_to_oklab(a);
+    let lb = srgb_to_oklab(b);
+    let mixed = [
+        la[0] + (lb[0] - la[0]) * t,
+        la[1] + (lb[1] - la[1]) * t,
+        la[2] + (lb[2] - la[2]) * t,
+    ];
+    let [r, g, bch] = oklab_to_srgb(mixed);
+    let alpha = (a.a() as f32 * (1.0 - t) + b.a() as f32 * t).round() as u8;
+    Color32::from_rgba_unmultiplied(r, g, bch, alpha)
+}
+
+fn srgb_to_oklab(color: Color32) -> [f32; 3] {
+    let [r, g, b, _] = color.to_srgba_unmultiplied();
+    let r = egui::ecolor::linear_f32_from_gamma_u8(r);
+    let g = egui::ecolor::linear_f32_from_gamma_u8(g);
+    let b = egui::ecolor::linear_f32_from_gamma_u8(b);
+
+    let l = (0.412_221_47 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn oklab_to_srgb([lightness, a, b]: [f32; 3]) -> [u8; 3] {
+    let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    let r = 4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s;
+    let g = -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s;
+    let b = -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s;
+
+    [
+        egui::ecolor::gamma_u8_from_linear_f32(r.clamp(0.0, 1.0)),
+        egui::ecolor::gamma_u8_from_linear_f32(g.clamp(0.0, 1.0)),
+        egui::ecolor::gamma_u8_from_linear_f32(b.clamp(0.0, 1.0)),
+    ]
+}
+
+/// WCAG 2 contrast ratio between two colors, from 1.0 (identical
+/// luminance) to 21.0 (black on white); the order doesn't matter.
+///
+/// WCAG asks for at least 4.5 for body text and 3.0 for large text.
+/// Alpha is ignored.
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let luminance = |color: Color32| {
+        let r = egui::ecolor::linear_f32_from_gamma_u8(color.r());
+        let g = egui::ecolor::linear_f32_from_gamma_u8(color.g());
+        let b = egui::ecolor::linear_f32_from_gamma_u8(color.b());
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    };
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Minimum WCAG contrast for body text, checked by the industrial themes
+/// in debug builds.
+#[cfg(debug_assertions)]
+const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Warns when `theme`'s ink falls below [`MIN_TEXT_CONTRAST`] on its
+/// background, to catch palette tweaks that hurt readability.
+#[cfg(debug_assertions)]
+fn check_text_contrast(theme: &str, foreground: Color32, background: Color32) {
+    let ratio = contrast_ratio(foreground, background);
+    if ratio < MIN_TEXT_CONTRAST {
+        log::warn!("{theme} theme: text contrast {ratio:.2}:1 is below {MIN_TEXT_CONTRAST}:1");
+    }
+}
+
+fn corner_radius_id() -> egui::Id {
+    egui::Id::new("gorbie_corner_radius")
+}
+
+/// Corner radius of GORBIE's own frames (cards, notes, tooltips, popups),
+/// set with [`NotebookConfig::with_corner_radius`](crate::NotebookConfig::with_corner_radius).
+/// 0.0, the sharp industrial look, unless configured otherwise.
+pub fn corner_radius(ctx: &egui::Context) -> f32 {
+    ctx.data(|d| d.get_temp(corner_radius_id())).unwrap_or(0.0)
+}
+
+/// Records the frame corner radius on `ctx` and rounds egui's menus and
+/// popups to match. Called during setup, after the styles are installed.
+pub(crate) fn set_corner_radius(ctx: &egui::Context, radius: f32) {
+    ctx.data_mut(|d| d.insert_temp(corner_radius_id(), radius));
+    ctx.all_styles_mut(|style| style.visuals.menu_corner_radius = radius.into());
+}
+
 /// Look up a RAL Classic color by its number, returning black if not found.
 pub fn ral(num: u16) -> Color32 {
     RAL_COLORS
@@ -436,20 +538,13 @@ pub fn industrial(
     base_visuals
 }
 
-/// Complete light-mode egui `Style` using the industrial RAL palette.
-pub fn industrial_light() -> Style {
+/// The industrial typography and spacing around `visuals`.
+fn industrial_style(visuals: Visuals) -> Style {
     let mut style = Style {
         text_styles: industrial_text_styles().into_iter().collect(),
         ..Default::default()
     };
 
-    let foreground = ral(9011);
-    let background = ral(7047);
-    let surface = ral(7047);
-    let accent = ral(2009);
-
-    let visuals = industrial(foreground, background, surface, accent, Visuals::light());
-
     style.spacing.item_spacing = egui::vec2(12.0, 12.0);
     style.spacing.button_padding = egui::vec2(12.0, 8.0);
     style.spacing.indent = 18.0;
@@ -462,30 +557,45 @@ pub fn industrial_light() -> Style {
     style
 }
 
+/// Complete light-mode egui `Style` using the industrial RAL palette.
+pub fn industrial_light() -> Style {
+    let foreground = ral(9011);
+    let background = ral(7047);
+    let surface = ral(7047);
+    let accent = ral(2009);
+
+    #[cfg(debug_assertions)]
+    check_text_contrast("industrial_light", foreground, background);
+    industrial_style(industrial(foreground, background, surface, accent, Visuals::light()))
+}
+
 /// Complete dark-mode egui `Style` using the industrial RAL palette.
 pub fn industrial_dark() -> Style {
-    let mut style = Style {
-        text_styles: industrial_text_styles().into_iter().collect(),
-        ..Default::default()
-    };
-
     let foreground = ral(9003);
     let background = ral(7046);
     let surface = ral(7047);
     let accent = ral(2009);
 
-    let visuals = industrial(foreground, background, surface, accent, Visuals::dark());
+    #[cfg(debug_assertions)]
+    check_text_contrast("industrial_dark", foreground, background);
+    industrial_style(industrial(foreground, background, surface, accent, Visuals::dark()))
+}
 
-    style.spacing.item_spacing = egui::vec2(12.0, 12.0);
-    style.spacing.button_padding = egui::vec2(12.0, 8.0);
-    style.spacing.indent = 18.0;
-    style.spacing.slider_width = 240.0;
-    // 34px matches our button + LCD field visual height (with the current padding/font sizes).
-    style.spacing.interact_size = egui::vec2(34.0, 34.0);
-    style.animation_time = 0.12;
+/// Light `Style` for printed output: black ink on pure white, half-width
+/// hairlines and no drop shadows, with the industrial typography.
+/// Headless captures use it by default
+/// (see `NotebookConfig::with_capture_style`).
+pub fn industrial_print() -> Style {
+    let foreground = Color32::BLACK;
+    let background = Color32::WHITE;
+    let accent = ral(2009);
 
-    style.visuals = visuals;
-    style
+    let mut visuals = industrial(foreground, background, background, accent, Visuals::light());
+    visuals.window_stroke.width = 0.5;
+    visuals.widgets.noninteractive.bg_stroke.width = 0.5;
+    visuals.widgets.inactive.bg_stroke.width = 0.5;
+    visuals.popup_shadow = egui::epaint::Shadow::NONE;
+    industrial_style(visuals)
 }
 
 /// Font definitions for the industrial theme (IosevkaGorbie + LCD).
@@ -576,3 +686,19 @@ pub fn industrial_text_styles() -> Vec<(TextStyle, FontId)> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_ratio_spans_wcag_range() {
+        let black_white = contrast_ratio(Color32::BLACK, Color32::WHITE);
+        assert!((black_white - 21.0).abs() < 0.01);
+        assert_eq!(contrast_ratio(ral(5005), ral(5005)), 1.0);
+        assert_eq!(
+            contrast_ratio(ral(9011), ral(7047)),
+            contrast_ratio(ral(7047), ral(9011))
+        );
+    }
+}
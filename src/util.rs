@@ -0,0 +1,61 @@
+//! Formatting helpers shared by the widgets and the telemetry viewer.
+
+/// Format a nanosecond duration with an adaptive unit (`ns`, `us`, `ms`, `s`).
+pub fn fmt_duration_ns(ns: u64) -> String {
+    const US: u64 = 1_000;
+    const MS: u64 = 1_000_000;
+    const S: u64 = 1_000_000_000;
+
+    if ns < US {
+        format!("{ns}ns")
+    } else if ns < MS {
+        format!("{:.1}us", ns as f64 / US as f64)
+    } else if ns < S {
+        format!("{:.3}ms", ns as f64 / MS as f64)
+    } else {
+        format!("{:.3}s", ns as f64 / S as f64)
+    }
+}
+
+/// Format a count compactly with one decimal and a `k`, `M` or `G` suffix
+/// from a thousand up (`999`, `1.0k`, `1.5M`), for counts that can run
+/// into the millions.
+pub fn fmt_count(n: u64) -> String {
+    if n < 1_000 {
+        return n.to_string();
+    }
+    let units = [(1e3, "k"), (1e6, "M"), (1e9, "G")];
+    for (scale, suffix) in &units[..units.len() - 1] {
+        let scaled = n as f64 / scale;
+        // Move up a unit rather than print "1000.0k".
+        if (scaled * 10.0).round() < 10_000.0 {
+            return format!("{scaled:.1}{suffix}");
+        }
+    }
+    format!("{:.1}G", n as f64 / 1e9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fmt_duration_ns_switches_units_at_boundaries() {
+        assert_eq!(fmt_duration_ns(999), "999ns");
+        assert_eq!(fmt_duration_ns(1_000), "1.0us");
+        assert_eq!(fmt_duration_ns(1_000_000), "1.000ms");
+        assert_eq!(fmt_duration_ns(1_500_000_000), "1.500s");
+    }
+
+    #[test]
+    fn fmt_count_switches_units_at_boundaries() {
+        assert_eq!(fmt_count(0), "0");
+        assert_eq!(fmt_count(999), "999");
+        assert_eq!(fmt_count(1_000), "1.0k");
+        assert_eq!(fmt_count(3_400), "3.4k");
+        assert_eq!(fmt_count(999_949), "999.9k");
+        assert_eq!(fmt_count(999_950), "1.0M");
+        assert_eq!(fmt_count(1_500_000), "1.5M");
+        assert_eq!(fmt_count(2_000_000_000), "2.0G");
+    }
+}
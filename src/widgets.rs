@@ -3,10 +3,19 @@
 //! The actual widget implementations live in `src/widgets/*.rs` so each widget can
 //! be edited independently.
 
+/// Size cap for widgets that load blobs.
+pub mod blob_limits;
 /// Scalar-vs-budget gauge composed from the LCD readout and progress meter.
 pub mod budget_gauge;
 /// Toggle buttons, radio buttons, and choice toggles.
 pub mod button;
+/// Highlighted, horizontally scrolling code blocks.
+#[cfg(feature = "markdown")]
+pub mod code_block;
+/// Square collapsible section for nested detail.
+pub mod collapsing_section;
+/// Monospace text that copies itself to the clipboard when clicked.
+pub mod copyable_label;
 /// Polars dataframe display widgets.
 #[cfg(feature = "polars")]
 pub mod dataframe;
@@ -16,6 +25,10 @@ pub mod event_feed;
 pub mod field;
 /// Bar chart / histogram widget.
 pub mod histogram;
+/// Horizontal bar chart of telemetry hotspots.
+pub mod hotspot_bars;
+/// Two-column key/value table for metadata panels.
+pub mod kv_table;
 /// Row-height labels for grid alignment.
 pub mod label;
 /// Background-loading toggle buttons and auto-loaders.
@@ -26,8 +39,15 @@ pub mod markdown;
 /// Rolling time-series strip with readout and percentile band.
 #[cfg(feature = "plots")]
 pub mod metric_strip;
+/// Theme-matched `egui_plot` preset and stacked area plots.
+#[cfg(feature = "plots")]
+pub mod plot;
 /// Determinate progress bars.
 pub mod progress;
+/// Vertical option list with per-option descriptions.
+pub mod radio_list;
+/// Uppercase sub-section label with a hairline rule.
+pub mod section_header;
 /// Horizontal and vertical sliders.
 pub mod slider;
 /// Scrolling stream of tagged text runs.
@@ -37,6 +57,8 @@ pub mod stream_lane;
 pub mod table;
 mod table_layout;
 mod table_sizing;
+/// Tab row switching between several views of a card.
+pub mod tabs;
 /// TribleSpace browser widgets (pile repo, inspectors).
 #[cfg(feature = "triblespace")]
 pub mod triblespace;
@@ -46,31 +68,50 @@ pub mod typst_render;
 /// Typst compilation and selection widget.
 #[cfg(feature = "typst")]
 pub mod typst_widget;
+/// Undo/redo controls for [`History`](crate::dataflow::History).
+pub mod undo;
 
+pub use blob_limits::BlobLoadLimits;
 pub use budget_gauge::BudgetGauge;
 pub use button::Button;
 pub use button::ChoiceToggle;
 pub use button::RadioButton;
+#[cfg(feature = "markdown")]
+pub use code_block::{code_block, code_block_with_line_numbers, code_line_numbers};
+pub use collapsing_section::{collapsing_section, collapsing_section_with_id};
+pub use copyable_label::{copyable_label, copyable_label_as};
 #[cfg(feature = "polars")]
 pub use dataframe::{data_export_tiny, data_summary_tiny, dataframe, dataframe_summary};
 pub use event_feed::EventFeed;
+pub use field::drag_value;
 pub use field::lcd_readout;
 pub use field::NumberField;
+pub use field::seed_control;
 pub use field::TextField;
 pub use histogram::Histogram;
 pub use histogram::HistogramBucket;
 pub use histogram::HistogramYAxis;
+pub use hotspot_bars::hotspot_bars;
+pub use hotspot_bars::Hotspot;
+pub use kv_table::kv_table;
 pub use label::row_label;
 pub use load::load_auto;
 pub use load::load_button;
 #[cfg(feature = "markdown")]
-pub use markdown::markdown;
+pub use markdown::{markdown, markdown_with_search, markdown_with_width};
 #[cfg(feature = "plots")]
 pub use metric_strip::MetricStrip;
+#[cfg(feature = "plots")]
+pub use plot::{show_styled_plot, stacked_area, styled_plot};
+pub use progress::progress_bar;
 pub use progress::ProgressBar;
+pub use radio_list::radio_list;
+pub use section_header::section_header;
 pub use slider::Slider;
 pub use slider::SliderClamping;
 pub use stream_lane::RunStyle;
 pub use stream_lane::StreamLane;
 pub use table::Column;
 pub use table::TableBuilder;
+pub use tabs::tabs;
+pub use undo::undo_redo_buttons;
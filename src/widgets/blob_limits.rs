@@ -0,0 +1,45 @@
+use eframe::egui::{Response, RichText, Ui};
+
+/// Size cap for widgets that resolve blobs from a store.
+///
+/// Blob-loading widgets check a blob's length against the cap before
+/// fetching it; oversized blobs are shown as a "value too large"
+/// placeholder instead, so exploring an untrusted or very large pile can't
+/// freeze the UI or exhaust memory on a single value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobLoadLimits {
+    /// Largest blob, in bytes, that will be loaded.
+    pub max_bytes: u64,
+}
+
+impl Default for BlobLoadLimits {
+    /// 16 MiB: far above any name or formatter, well below trouble.
+    fn default() -> Self {
+        Self {
+            max_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl BlobLoadLimits {
+    /// Limits with the given byte cap.
+    pub fn new(max_bytes: u64) -> Self {
+        Self { max_bytes }
+    }
+
+    /// True when a blob of `len` bytes may be loaded.
+    pub fn allows(&self, len: u64) -> bool {
+        len <= self.max_bytes
+    }
+
+    /// Placeholder text for a blob of `len` bytes that exceeded the cap.
+    pub fn too_large_text(len: u64) -> String {
+        format!("value too large ({:.1} MB)", len as f64 / 1_000_000.0)
+    }
+
+    /// Draw the placeholder for a blob of `len` bytes in the weak text
+    /// color.
+    pub fn too_large_label(ui: &mut Ui, len: u64) -> Response {
+        ui.label(RichText::new(Self::too_large_text(len)).monospace().weak())
+    }
+}
@@ -7,12 +7,17 @@ use crate::themes::{
     GorbieButtonStyle, GorbieChoiceToggleStyle, GorbieRadioStyle,
 };
 
+/// How long a [`Button::require_confirm`] button stays armed.
+const CONFIRM_TIMEOUT_S: f64 = 3.0;
+
 /// A momentary or toggle button with optional LED indicator.
 ///
 /// - Momentary (default): click returns `response.clicked()`
 /// - Toggle: pass `on(&mut bool)` to toggle state on click
 /// - Disabled = flush (no shadow), not grayed out
 /// - Always fills available container width
+/// - Destructive: `danger()` draws it in signal red; with `require_confirm()`
+///   `response.clicked()` only fires on the second click
 #[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
 pub struct Button<'a> {
     text: WidgetText,
@@ -21,6 +26,8 @@ pub struct Button<'a> {
     fill: Option<Color32>,
     light: Option<Color32>,
     latched: bool,
+    danger: bool,
+    require_confirm: bool,
     width_px: Option<f32>,
     gorbie_style: Option<GorbieButtonStyle>,
 }
@@ -35,6 +42,8 @@ impl<'a> Button<'a> {
             fill: None,
             light: None,
             latched: false,
+            danger: false,
+            require_confirm: false,
             width_px: None,
             gorbie_style: None,
         }
@@ -70,6 +79,22 @@ impl<'a> Button<'a> {
         self
     }
 
+    /// Mark the button as destructive: outline and label in signal red
+    /// (RAL 3001).
+    pub fn danger(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+
+    /// Ask for a second click before acting. The first click arms the
+    /// button, relabelling it "Click again to confirm" for a few seconds,
+    /// and is not reported: `clicked()` only fires on the confirming click
+    /// (which also flips a toggle button's `on`).
+    pub fn require_confirm(mut self) -> Self {
+        self.require_confirm = true;
+        self
+    }
+
     /// Set the width in grid modules (1 module = 12px). Height is fixed at
     /// 3 modules. Without an explicit width the button sizes to fit its label.
     pub fn modules(mut self, n: u32) -> Self {
@@ -109,6 +134,8 @@ impl Widget for Button<'_> {
             fill,
             light,
             latched,
+            danger,
+            require_confirm,
             width_px,
             gorbie_style,
         } = self;
@@ -120,6 +147,18 @@ impl Widget for Button<'_> {
         let padding = ui.spacing().button_padding;
         let module = crate::card_ctx::GRID_ROW_MODULE;
 
+        let confirm_id = ui.next_auto_id().with("confirm_armed");
+        let now = ui.input(|input| input.time);
+        let armed = require_confirm
+            && ui
+                .data(|data| data.get_temp::<f64>(confirm_id))
+                .is_some_and(|armed_at| now - armed_at < CONFIRM_TIMEOUT_S);
+        let text = if armed {
+            WidgetText::from("Click again to confirm")
+        } else {
+            text
+        };
+
         let label_text = text.text().to_string();
         // If width is explicit, constrain the galley to that box. Otherwise
         // let it size to natural content.
@@ -144,10 +183,31 @@ impl Widget for Button<'_> {
 
         let (outer_rect, mut response) = ui.allocate_exact_size(desired_size, Sense::click());
 
+        let clicked = response.clicked() && enabled;
+        let activated = if require_confirm {
+            let activated = clicked && armed;
+            if clicked && !armed {
+                ui.data_mut(|data| data.insert_temp(confirm_id, now));
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs_f64(CONFIRM_TIMEOUT_S));
+            } else if activated || (armed && response.clicked_elsewhere()) {
+                ui.data_mut(|data| data.remove_temp::<f64>(confirm_id));
+            }
+            if !activated {
+                // The arming click is not a click for the caller.
+                let click =
+                    egui::response::Flags::CLICKED | egui::response::Flags::FAKE_PRIMARY_CLICKED;
+                response.flags.remove(click);
+            }
+            activated
+        } else {
+            clicked
+        };
+
         // Toggle behavior.
         let mut toggled_on = false;
         if let Some(on) = on {
-            if response.clicked() && enabled {
+            if activated {
                 *on = !*on;
                 response.mark_changed();
             }
@@ -174,7 +234,14 @@ impl Widget for Button<'_> {
         let prepress =
             enabled && !is_down && (response.hovered() || response.has_focus());
 
-        let stroke_color = if enabled && selected { accent } else { outline };
+        let danger_color = crate::themes::ral(3001);
+        let stroke_color = if enabled && selected {
+            accent
+        } else if danger {
+            danger_color
+        } else {
+            outline
+        };
 
         let body_rect_up =
             Rect::from_min_max(outer_rect.min, outer_rect.max - shadow_inset).intersect(outer_rect);
@@ -210,14 +277,19 @@ impl Widget for Button<'_> {
             egui::StrokeKind::Inside,
         );
 
-        let text_color = crate::themes::ral(9011);
+        let text_color = if danger {
+            danger_color
+        } else {
+            crate::themes::ral(9011)
+        };
         let text_pos = pos2(
             body_rect.center().x - galley.size().x / 2.0,
             body_rect.center().y - galley.size().y / 2.0,
         );
         painter.galley(text_pos, galley, text_color);
 
-        // LED indicator.
+        // LED indicator; an armed confirm lights it red.
+        let light = if armed { Some(danger_color) } else { light };
         if light.is_some() || toggled_on {
             let led_height = 4.0;
             let led_inset_x = 2.0;
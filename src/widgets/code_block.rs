@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use eframe::egui;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{self, FontStyle, Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Render `code` as a syntax-highlighted block with the theme's code
+/// background.
+///
+/// `language` is the fence info tag (`"rust"`, `"toml"`, …) and selects
+/// the highlighter; an empty tag renders plain monospace. Long lines are
+/// not wrapped — the block scrolls horizontally instead.
+///
+/// ```ignore
+/// widgets::code_block(ui, "fn main() {}", "rust");
+/// ```
+pub fn code_block(ui: &mut egui::Ui, code: &str, language: &str) {
+    if code_line_numbers(ui.ctx()) {
+        code_block_with_line_numbers(ui, code, language);
+        return;
+    }
+    // Fence with one backtick more than the longest run in the code, so
+    // code containing fences of its own stays intact.
+    let longest_run = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat((longest_run + 1).max(3));
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let fenced = format!("{fence}{language}\n{code}\n{fence}\n");
+
+    egui::ScrollArea::horizontal()
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+            super::markdown::render_commonmark(ui, &fenced);
+        });
+}
+
+/// [`code_block`] with right-aligned line numbers in a dimmed gutter to
+/// its left, for longer listings that get discussed line by line.
+///
+/// The code is highlighted here rather than by the markdown renderer, so
+/// the numbers are set in the same font and row height as the code and
+/// line up with it from the top. They are not selectable, so copying the
+/// code never picks them up. The gutter stays put while long lines scroll.
+///
+/// ```ignore
+/// widgets::code_block_with_line_numbers(ui, source, "rust");
+/// ```
+pub fn code_block_with_line_numbers(ui: &mut egui::Ui, code: &str, language: &str) {
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let font = egui::TextStyle::Monospace.resolve(ui.style());
+    let (job, background) = highlighted(ui, code, language, &font);
+    let lines = code.lines().count().max(1);
+    let digits = lines.to_string().len();
+    let numbers = (1..=lines)
+        .map(|n| format!("{n:>digits$}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let numbers =
+        egui::text::LayoutJob::simple_singleline(numbers, font, ui.visuals().weak_text_color());
+
+    egui::Frame::new()
+        .fill(background)
+        .corner_radius(crate::themes::corner_radius(ui.ctx()))
+        .inner_margin(egui::Margin::same(8))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal_top(|ui| {
+                ui.add(egui::Label::new(numbers).selectable(false).extend());
+                egui::ScrollArea::horizontal()
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        ui.add(egui::Label::new(job).extend());
+                    });
+            });
+        });
+}
+
+/// Whether markdown code fences get line numbers, as set with
+/// [`NotebookConfig::with_code_line_numbers`](crate::NotebookConfig::with_code_line_numbers).
+pub fn code_line_numbers(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp(code_line_numbers_id()))
+        .unwrap_or(false)
+}
+
+/// Records the code line number setting on `ctx`. Called during setup.
+pub(crate) fn set_code_line_numbers(ctx: &egui::Context, on: bool) {
+    ctx.data_mut(|d| d.insert_temp(code_line_numbers_id(), on));
+}
+
+fn code_line_numbers_id() -> egui::Id {
+    egui::Id::new("gorbie_code_line_numbers")
+}
+
+struct Highlighter {
+    syntaxes: SyntaxSet,
+    light: Option<Theme>,
+    dark: Option<Theme>,
+}
+
+fn load_theme(name: &str, bytes: &[u8]) -> Option<Theme> {
+    ThemeSet::load_from_reader(&mut std::io::Cursor::new(bytes))
+        .inspect_err(|err| log::warn!("failed to load {name} syntax theme: {err}"))
+        .ok()
+}
+
+thread_local! {
+    static HIGHLIGHTER: Highlighter = Highlighter {
+        syntaxes: SyntaxSet::load_defaults_newlines(),
+        light: load_theme("RAL light", super::markdown::RAL_THEME_LIGHT_BYTES),
+        dark: load_theme("RAL dark", super::markdown::RAL_THEME_DARK_BYTES),
+    };
+    static HIGHLIGHTED: RefCell<HighlightCache> = RefCell::new(HighlightCache::default());
+}
+
+#[derive(Default)]
+struct HighlightCache {
+    pass: u64,
+    blocks: HashMap<u64, (u64, egui::text::LayoutJob, egui::Color32)>,
+}
+
+fn color(color: highlighting::Color) -> egui::Color32 {
+    egui::Color32::from_rgba_unmultiplied(color.r, color.g, color.b, color.a)
+}
+
+/// `code` highlighted with the RAL theme for the current visuals, and the
+/// theme's background. Blocks not drawn during the previous pass are
+/// dropped from the cache.
+fn highlighted(
+    ui: &egui::Ui,
+    code: &str,
+    language: &str,
+    font: &egui::FontId,
+) -> (egui::text::LayoutJob, egui::Color32) {
+    let dark = ui.visuals().dark_mode;
+    let text_color = ui.visuals().text_color();
+    let fallback_background = ui.visuals().code_bg_color;
+    let pass = ui.ctx().cumulative_pass_nr();
+    let mut hasher = DefaultHasher::new();
+    (code, language, dark, font).hash(&mut hasher);
+    let key = hasher.finish();
+    HIGHLIGHTED.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.pass != pass {
+            let previous = cache.pass;
+            cache.blocks.retain(|_, (used, ..)| *used >= previous);
+            cache.pass = pass;
+        }
+        let (used, job, background) = cache.blocks.entry(key).or_insert_with(|| {
+            HIGHLIGHTER.with(|highlighter| {
+                let theme = if dark {
+                    &highlighter.dark
+                } else {
+                    &highlighter.light
+                };
+                let plain = egui::TextFormat::simple(font.clone(), text_color);
+                let mut job = egui::text::LayoutJob::default();
+                let Some(theme) = theme else {
+                    job.append(code, 0.0, plain);
+                    return (pass, job, fallback_background);
+                };
+                let syntaxes = &highlighter.syntaxes;
+                let syntax = syntaxes
+                    .find_syntax_by_token(language)
+                    .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+                let mut lines = HighlightLines::new(syntax, theme);
+                for line in LinesWithEndings::from(code) {
+                    match lines.highlight_line(line, syntaxes) {
+                        Ok(ranges) => {
+                            for (style, text) in ranges {
+                                let mut format =
+                                    egui::TextFormat::simple(font.clone(), color(style.foreground));
+                                format.italics = style.font_style.contains(FontStyle::ITALIC);
+                                job.append(text, 0.0, format);
+                            }
+                        }
+                        Err(_) => job.append(line, 0.0, plain.clone()),
+                    }
+                }
+                let background = theme.settings.background.map_or(fallback_background, color);
+                (pass, job, background)
+            })
+        });
+        *used = pass;
+        (job.clone(), *background)
+    })
+}
@@ -0,0 +1,95 @@
+use eframe::egui::{
+    self, pos2, vec2, CursorIcon, Margin, Response, Sense, TextStyle, TextWrapMode, Ui,
+    WidgetText,
+};
+
+use crate::cards::DEFAULT_CARD_PADDING;
+
+/// A collapsible section with a square monospace header.
+///
+/// The header row shows a `[+]`/`[-]` toggle and the title over a thin
+/// divider in the theme's `bg_stroke` color; clicking anywhere on the row
+/// toggles the section. The open state persists per title. The body is
+/// indented by the card padding and only built while open, and the return
+/// value tells the caller whether it is, so heavy bodies can be skipped
+/// entirely when collapsed.
+///
+/// Unlike [`CardCtx::section`](crate::CardCtx::section), which draws a
+/// large colored bar for top-level card structure, this is meant for
+/// nested detail inside a card or panel.
+///
+/// ```ignore
+/// widgets::collapsing_section(ui, "Advanced", false, |ui| {
+///     ui.label("Rarely needed settings.");
+/// });
+/// ```
+pub fn collapsing_section(
+    ui: &mut Ui,
+    title: &str,
+    default_open: bool,
+    body: impl FnOnce(&mut Ui),
+) -> bool {
+    collapsing_section_with_id(ui, title, title, default_open, body)
+}
+
+/// [`collapsing_section`] whose open state persists per `id_salt` instead
+/// of per title, for titles that change, e.g. to show a count.
+pub fn collapsing_section_with_id(
+    ui: &mut Ui,
+    id_salt: impl std::hash::Hash,
+    title: &str,
+    default_open: bool,
+    body: impl FnOnce(&mut Ui),
+) -> bool {
+    // Headless captures render everything open, like `CardCtx::section`.
+    let default_open = default_open || crate::is_headless(ui.ctx());
+    let id = ui.make_persistent_id(("gorbie_collapsing_section", id_salt));
+    let mut open = ui
+        .ctx()
+        .data_mut(|d| *d.get_persisted_mut_or(id, default_open));
+
+    let response = header(ui, title, open);
+    if response.clicked() {
+        open = !open;
+        ui.ctx().data_mut(|d| d.insert_persisted(id, open));
+    }
+
+    if open {
+        let indent = Margin {
+            left: DEFAULT_CARD_PADDING.left,
+            ..Margin::ZERO
+        };
+        egui::Frame::new().inner_margin(indent).show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            body(ui);
+        });
+    }
+    open
+}
+
+fn header(ui: &mut Ui, title: &str, open: bool) -> Response {
+    let width = ui.available_width();
+    let pad = vec2(0.0, 4.0);
+    let toggle = if open { "[-] " } else { "[+] " };
+    let galley = WidgetText::from(format!("{toggle}{}", title.to_uppercase())).into_galley(
+        ui,
+        Some(TextWrapMode::Truncate),
+        width,
+        TextStyle::Monospace,
+    );
+    let height = galley.size().y + pad.y * 2.0 + 1.0;
+    let (rect, response) = ui.allocate_exact_size(vec2(width, height), Sense::click());
+    let response = response.on_hover_cursor(CursorIcon::PointingHand);
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+        let painter = ui.painter();
+        if response.hovered() {
+            painter.rect_filled(rect, 0.0, ui.visuals().widgets.hovered.weak_bg_fill);
+        }
+        painter.galley(pos2(rect.left(), rect.top() + pad.y), galley, visuals.text_color());
+        let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+        painter.hline(rect.x_range(), rect.bottom() - 0.5, stroke);
+    }
+    response
+}
@@ -0,0 +1,78 @@
+use eframe::egui::{self, vec2, Rect, Response, Sense, Stroke, TextStyle, TextWrapMode, Ui};
+
+/// How long the "Copied" tooltip stays up after a click, in seconds.
+const COPIED_FLASH: f64 = 1.0;
+
+/// Monospace text that copies itself to the clipboard when clicked, for
+/// ids, hashes and paths.
+///
+/// Hovering shows a small copy glyph after the text; a click copies
+/// `text` and flashes a post-it "Copied" tooltip.
+pub fn copyable_label(ui: &mut Ui, text: &str) -> Response {
+    copyable_label_as(ui, text, text)
+}
+
+/// Like [`copyable_label`], but shows `text` while a click copies
+/// `copy_text`, e.g. a shortened id that copies the full one.
+pub fn copyable_label_as(ui: &mut Ui, text: &str, copy_text: &str) -> Response {
+    let galley = egui::WidgetText::from(text).into_galley(
+        ui,
+        Some(TextWrapMode::Extend),
+        f32::INFINITY,
+        TextStyle::Monospace,
+    );
+    let glyph_side = (galley.size().y * 0.6).round();
+    let gap = ui.spacing().item_spacing.x * 0.5;
+    let size = vec2(galley.size().x + gap + glyph_side, galley.size().y);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    let response = response.on_hover_cursor(egui::CursorIcon::Copy);
+    response
+        .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, ui.is_enabled(), text));
+
+    let copied_at_id = response.id.with("copied_at");
+    let now = ui.input(|input| input.time);
+    if response.clicked() {
+        ui.ctx().copy_text(copy_text.to_owned());
+        ui.data_mut(|memory| memory.insert_temp(copied_at_id, now));
+    }
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.visuals();
+        let text_color = if response.hovered() {
+            visuals.strong_text_color()
+        } else {
+            visuals.text_color()
+        };
+        let painter = ui.painter();
+        painter.galley(rect.min, galley, text_color);
+        if response.hovered() {
+            // Two offset squares, the front one filled so it hides the
+            // back one's corner.
+            let stroke = Stroke::new(1.0, visuals.weak_text_color());
+            let glyph = Rect::from_min_size(
+                rect.right_center() - vec2(glyph_side, glyph_side * 0.5),
+                vec2(glyph_side, glyph_side),
+            );
+            let offset = (glyph_side * 0.3).round();
+            let back = Rect::from_min_max(glyph.min, glyph.max - vec2(offset, offset));
+            let front = Rect::from_min_max(glyph.min + vec2(offset, offset), glyph.max);
+            painter.rect_stroke(back, 0.0, stroke, egui::StrokeKind::Inside);
+            painter.rect_filled(front, 0.0, visuals.panel_fill);
+            painter.rect_stroke(front, 0.0, stroke, egui::StrokeKind::Inside);
+        }
+    }
+
+    let copied_at = ui.data(|memory| memory.get_temp::<f64>(copied_at_id));
+    if let Some(copied_at) = copied_at {
+        let remaining = copied_at + COPIED_FLASH - now;
+        if remaining > 0.0 {
+            crate::show_postit_tooltip(ui, &response, "Copied");
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+        } else {
+            ui.data_mut(|memory| memory.remove::<f64>(copied_at_id));
+        }
+    }
+
+    response
+}
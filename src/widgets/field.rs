@@ -711,6 +711,60 @@ pub fn lcd_readout(ui: &mut Ui, text: &str, ink: Option<Color32>) -> Response {
     response
 }
 
+/// A [`NumberField`] clamped to `range` with a unit `suffix` (e.g. `" ms"`).
+///
+/// Shorthand for the common bounded case; use [`NumberField`] directly for
+/// drag speed, decimals or custom constraints.
+///
+/// ```ignore
+/// if widgets::drag_value(ui, &mut config.timeout_ms, 10..=5000, " ms").changed() {
+///     restart();
+/// }
+/// ```
+pub fn drag_value<Num: egui::emath::Numeric>(
+    ui: &mut Ui,
+    value: &mut Num,
+    range: RangeInclusive<Num>,
+    suffix: impl Into<String>,
+) -> Response {
+    ui.add(NumberField::new(value).range(range).suffix(suffix))
+}
+
+/// Seed input for stochastic content: the seed in a [`NumberField`], a
+/// "Random" button drawing a fresh time-based seed and a "+1" button
+/// stepping to the next one. Returns whether the seed changed.
+///
+/// Random seeds stay below 2^32 so they read (and retype) exactly.
+///
+/// ```ignore
+/// if widgets::seed_control(ui, &mut config.seed) {
+///     regenerate(config.seed);
+/// }
+/// ```
+pub fn seed_control(ui: &mut Ui, seed: &mut u64) -> bool {
+    let before = *seed;
+    ui.horizontal(|ui| {
+        ui.add(NumberField::new(seed));
+        if ui.add(crate::widgets::Button::new("Random")).clicked() {
+            let time = ui.input(|input| input.time);
+            let mixed = splitmix64(time.to_bits() ^ ui.ctx().cumulative_frame_nr());
+            *seed = mixed & u64::from(u32::MAX);
+        }
+        if ui.add(crate::widgets::Button::new("+1")).clicked() {
+            *seed = seed.wrapping_add(1);
+        }
+    });
+    *seed != before
+}
+
+/// SplitMix64 finalizer: spreads nearby inputs over the whole range.
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
 /// A draggable numeric input with LCD-style text rendering.
 ///
 /// Click to enter edit mode; drag horizontally to adjust the value.
@@ -721,6 +775,7 @@ pub struct NumberField<'a, Num: egui::emath::Numeric> {
     value: &'a mut Num,
     speed: f64,
     constrain_value: Option<&'a dyn Fn(Num, Num) -> Num>,
+    range: Option<RangeInclusive<Num>>,
     prefix: String,
     suffix: String,
     min_decimals: usize,
@@ -738,6 +793,7 @@ impl<'a, Num: egui::emath::Numeric> NumberField<'a, Num> {
             value,
             speed: 1.0,
             constrain_value: None,
+            range: None,
             prefix: String::new(),
             suffix: String::new(),
             min_decimals: 0,
@@ -758,6 +814,14 @@ impl<'a, Num: egui::emath::Numeric> NumberField<'a, Num> {
         self
     }
 
+    /// Clamp user-produced changes to `range`, after any
+    /// [`constrain_value`](Self::constrain_value). A value already outside
+    /// the range is left alone until the user changes it.
+    pub fn range(mut self, range: RangeInclusive<Num>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
     /// Set the drag speed (value change per pixel of horizontal drag). Default is 1.0.
     pub fn speed(mut self, speed: f64) -> Self {
         self.speed = speed;
@@ -820,6 +884,7 @@ impl<Num: egui::emath::Numeric> Widget for NumberField<'_, Num> {
             value,
             speed,
             constrain_value,
+            range,
             prefix,
             suffix,
             min_decimals,
@@ -830,6 +895,17 @@ impl<Num: egui::emath::Numeric> Widget for NumberField<'_, Num> {
             gorbie_style,
         } = self;
 
+        let constrain = |old: Num, new: Num| {
+            let new = constrain_value.map_or(new, |constrain| constrain(old, new));
+            // Compared as `Num`, not through f64, so integer bounds past
+            // 2^53 stay exact.
+            match range.as_ref() {
+                Some(range) if new < *range.start() => *range.start(),
+                Some(range) if new > *range.end() => *range.end(),
+                _ => new,
+            }
+        };
+
         let enabled = ui.is_enabled();
         let gstyle =
             gorbie_style.unwrap_or_else(|| GorbieNumberFieldStyle::from(ui.style().as_ref()));
@@ -897,9 +973,7 @@ impl<Num: egui::emath::Numeric> Widget for NumberField<'_, Num> {
 
             let proposed_value = Num::from_f64(proposed_f64);
             let mut new_value = proposed_value;
-            if let Some(constrain_value) = constrain_value {
-                new_value = constrain_value(*value, new_value);
-            }
+            new_value = constrain(*value, new_value);
 
             if new_value != *value {
                 *value = new_value;
@@ -965,9 +1039,7 @@ impl<Num: egui::emath::Numeric> Widget for NumberField<'_, Num> {
                         parsed_value = parsed_value.round();
                     }
                     let mut new_value = Num::from_f64(parsed_value);
-                    if let Some(constrain_value) = constrain_value {
-                        new_value = constrain_value(*value, new_value);
-                    }
+                    new_value = constrain(*value, new_value);
                     if new_value != *value {
                         *value = new_value;
                         response.mark_changed();
@@ -1039,9 +1111,7 @@ impl<Num: egui::emath::Numeric> Widget for NumberField<'_, Num> {
 
                         let proposed_value = Num::from_f64(proposed_f64);
                         let mut new_value = proposed_value;
-                        if let Some(constrain_value) = constrain_value {
-                            new_value = constrain_value(*value, new_value);
-                        }
+                        new_value = constrain(*value, new_value);
 
                         if new_value != *value {
                             *value = new_value;
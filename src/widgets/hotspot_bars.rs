@@ -0,0 +1,135 @@
+use eframe::egui::{
+    pos2, vec2, Align2, Rect, Response, Sense, StrokeKind, TextStyle, TextWrapMode, Ui,
+    WidgetText,
+};
+
+use crate::themes::colorhash;
+use crate::util::{fmt_count, fmt_duration_ns};
+
+/// Aggregated timing for one span label, as collected by the telemetry
+/// viewer.
+#[derive(Clone, Debug)]
+pub struct Hotspot {
+    /// Display name (span name, usually with its source location).
+    pub label: String,
+    /// Optional `file:line` source location of the span.
+    pub source: Option<String>,
+    /// Number of completed spans aggregated into this hotspot.
+    pub count: u64,
+    /// Summed duration of all spans, in nanoseconds.
+    pub total_ns: u64,
+    /// Longest single span, in nanoseconds.
+    pub max_ns: u64,
+}
+
+/// Horizontal bar chart of hotspots, sorted by total time.
+///
+/// Each row shows the hotspot label and its total duration over a bar
+/// scaled to the largest total. Bars are colored from the RAL categorical
+/// palette, keyed by the label so a hotspot keeps its color across
+/// refreshes. Hovering a bar shows the call count, max and average
+/// duration in a post-it tooltip.
+///
+/// Returns one response per drawn row, in sorted order, so callers can
+/// attach click or context-menu behavior.
+pub fn hotspot_bars<'h>(ui: &mut Ui, hotspots: &'h [Hotspot]) -> Vec<(&'h Hotspot, Response)> {
+    let mut sorted: Vec<&Hotspot> = hotspots.iter().collect();
+    sorted.sort_by(|a, b| {
+        b.total_ns
+            .cmp(&a.total_ns)
+            .then_with(|| b.max_ns.cmp(&a.max_ns))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    let max_total = sorted.first().map_or(1, |hot| hot.total_ns.max(1));
+
+    let width = ui.available_width();
+    let row_h = ui.spacing().interact_size.y;
+    let pad = 4.0;
+    let track_color = ui.visuals().widgets.noninteractive.bg_fill;
+
+    let spacing = ui.spacing().item_spacing;
+    ui.spacing_mut().item_spacing.y = 2.0;
+    let mut rows = Vec::with_capacity(sorted.len());
+    for hot in sorted {
+        let (rect, response) = ui.allocate_exact_size(vec2(width, row_h), Sense::click());
+
+        if ui.is_rect_visible(rect) {
+            let fill = colorhash::ral_categorical(hot.label.as_bytes());
+            let fraction = hot.total_ns as f32 / max_total as f32;
+            let bar = Rect::from_min_size(rect.min, vec2(rect.width() * fraction, rect.height()));
+            let painter = ui.painter().with_clip_rect(rect);
+            painter.rect_filled(rect, 0.0, track_color);
+            painter.rect_filled(bar, 0.0, fill);
+            if response.hovered() {
+                painter.rect_stroke(
+                    bar,
+                    0.0,
+                    colorhash::highlight_stroke(fill),
+                    StrokeKind::Inside,
+                );
+            }
+
+            let total = fmt_duration_ns(hot.total_ns);
+            let total_galley = WidgetText::from(total.as_str()).into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Monospace,
+            );
+            let label_w = (rect.width() - total_galley.size().x - pad * 3.0).max(0.0);
+            let label_galley = WidgetText::from(hot.label.as_str()).into_galley(
+                ui,
+                Some(TextWrapMode::Truncate),
+                label_w,
+                TextStyle::Monospace,
+            );
+
+            // Text sits on the bar where it overlaps and on the track
+            // elsewhere; pick the ink for whichever is under the label start.
+            let label_ink = if bar.width() > pad {
+                colorhash::text_color_on(fill)
+            } else {
+                ui.visuals().text_color()
+            };
+            let total_pos = Align2::RIGHT_CENTER
+                .align_size_within_rect(total_galley.size(), rect.shrink2(vec2(pad, 0.0)))
+                .min;
+            let total_ink = if bar.right() >= total_pos.x {
+                colorhash::text_color_on(fill)
+            } else {
+                ui.visuals().text_color()
+            };
+            let label_pos = pos2(
+                rect.left() + pad,
+                rect.center().y - label_galley.size().y * 0.5,
+            );
+            painter.galley(label_pos, label_galley, label_ink);
+            painter.galley(total_pos, total_galley, total_ink);
+        }
+
+        if response.hovered() {
+            let avg = if hot.count == 0 {
+                0
+            } else {
+                (hot.total_ns / hot.count).min(hot.max_ns)
+            };
+            let mut text = format!(
+                "{}\ncount {}\nmax   {}\navg   {}",
+                hot.label,
+                fmt_count(hot.count),
+                fmt_duration_ns(hot.max_ns),
+                fmt_duration_ns(avg),
+            );
+            if let Some(source) = hot.source.as_deref() {
+                text.push('\n');
+                text.push_str(source);
+            }
+            crate::show_postit_tooltip(ui, &response, &text);
+        }
+
+        rows.push((hot, response));
+    }
+    ui.spacing_mut().item_spacing = spacing;
+
+    rows
+}
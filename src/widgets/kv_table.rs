@@ -0,0 +1,84 @@
+use eframe::egui::{pos2, vec2, Rect, Response, Sense, Stroke, TextStyle, TextWrapMode, Ui, WidgetText};
+
+/// Render `label: value` pairs as a two-column table.
+///
+/// The key column is sized to the widest key; keys and values are separated
+/// by a vertical divider and rows by hairlines, drawn in the theme's
+/// `bg_stroke` color so the rules stay lighter than the text. Values wrap
+/// to the remaining width.
+///
+/// ```ignore
+/// widgets::kv_table(ui, &[
+///     ("branch", "main"),
+///     ("commits", &commit_count.to_string()),
+/// ]);
+/// ```
+pub fn kv_table(ui: &mut Ui, rows: &[(&str, &str)]) -> Response {
+    let width = ui.available_width();
+    let ink = ui.visuals().widgets.noninteractive.fg_stroke.color;
+    let grid_stroke = Stroke::new(1.0, ui.visuals().widgets.noninteractive.bg_stroke.color);
+    let pad = vec2(6.0, 2.0);
+
+    let key_galleys: Vec<_> = rows
+        .iter()
+        .map(|(key, _)| {
+            WidgetText::from(*key).into_galley(
+                ui,
+                Some(TextWrapMode::Truncate),
+                width * 0.5,
+                TextStyle::Small,
+            )
+        })
+        .collect();
+    let key_w = key_galleys
+        .iter()
+        .map(|galley| galley.size().x)
+        .fold(0.0, f32::max);
+    let divider_offset = key_w + pad.x * 2.0;
+    let value_w = (width - divider_offset - pad.x * 2.0).max(0.0);
+
+    let value_galleys: Vec<_> = rows
+        .iter()
+        .map(|(_, value)| {
+            WidgetText::from(*value).into_galley(
+                ui,
+                Some(TextWrapMode::Wrap),
+                value_w,
+                TextStyle::Small,
+            )
+        })
+        .collect();
+    let row_heights: Vec<f32> = key_galleys
+        .iter()
+        .zip(&value_galleys)
+        .map(|(key, value)| key.size().y.max(value.size().y) + pad.y * 2.0)
+        .collect();
+    let height: f32 = row_heights.iter().sum();
+
+    let (rect, response) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+    if !ui.is_rect_visible(rect) || rows.is_empty() {
+        return response;
+    }
+
+    let painter = ui.painter();
+    let divider_x = rect.left() + divider_offset;
+    painter.vline(divider_x, rect.y_range(), grid_stroke);
+
+    let mut y = rect.top();
+    for (i, ((key, value), row_height)) in key_galleys
+        .into_iter()
+        .zip(value_galleys)
+        .zip(row_heights)
+        .enumerate()
+    {
+        let row_rect = Rect::from_min_max(pos2(rect.left(), y), pos2(rect.right(), y + row_height));
+        if i > 0 {
+            painter.hline(row_rect.x_range(), row_rect.top(), grid_stroke);
+        }
+        painter.galley(row_rect.left_top() + pad, key, ink);
+        painter.galley(pos2(divider_x + pad.x, row_rect.top() + pad.y), value, ink);
+        y += row_height;
+    }
+
+    response
+}
@@ -5,8 +5,9 @@ use eframe::egui;
 
 /// Show a toggle button that spawns `action` in the background on click.
 ///
-/// While the task is running the LED pulses; the returned reference gives
-/// access to the current (or most recently completed) value.
+/// While the task is running the LED pulses (or stays lit under reduced
+/// motion); the returned reference gives access to the current (or most
+/// recently completed) value.
 pub fn load_button<'a, T: Send + 'static>(
     ui: &mut egui::Ui,
     value: &'a mut ComputedState<T>,
@@ -18,7 +19,9 @@ pub fn load_button<'a, T: Send + 'static>(
     let style = GorbieToggleButtonStyle::from(ui.style().as_ref());
     let light_on = crate::themes::button_light_on();
     let off = style.rail_bg;
-    let light = if running {
+    let light = if running && crate::reduced_motion(ui.ctx()) {
+        light_on
+    } else if running {
         let t = ui.input(|input| input.time) as f32;
         let wave = (t * std::f32::consts::TAU * 0.8).sin() * 0.5 + 0.5;
         let intensity = wave;
@@ -1,21 +1,28 @@
 use eframe::egui;
 use egui_commonmark::CommonMarkCache;
 use egui_commonmark::CommonMarkViewer;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use std::cell::Cell;
 use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::rc::Rc;
 
 thread_local! {
     static GORBIE_MD_CACHE: RefCell<CommonMarkCache> = RefCell::new(CommonMarkCache::default());
     static GORBIE_MD_THEMES_INSTALLED: Cell<bool> = Cell::new(false);
+    static GORBIE_MD_DOCUMENTS: RefCell<DocumentCache> = RefCell::new(DocumentCache::default());
 }
 
 const RAL_THEME_LIGHT: &str = "gorbie-ral-light";
 const RAL_THEME_DARK: &str = "gorbie-ral-dark";
-const RAL_THEME_LIGHT_BYTES: &[u8] = include_bytes!(concat!(
+pub(super) const RAL_THEME_LIGHT_BYTES: &[u8] = include_bytes!(concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/assets/syntax/ral_light.tmTheme"
 ));
-const RAL_THEME_DARK_BYTES: &[u8] = include_bytes!(concat!(
+pub(super) const RAL_THEME_DARK_BYTES: &[u8] = include_bytes!(concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/assets/syntax/ral_dark.tmTheme"
 ));
@@ -36,11 +43,646 @@ fn ensure_ral_syntax_themes(cache: &mut CommonMarkCache) {
     });
 }
 
+/// A text width of roughly 66 characters per line at the body font size,
+/// for use with [`markdown_with_width`].
+pub const READABLE_TEXT_WIDTH: f32 = 560.0;
+
 /// Render a markdown string into the UI with RAL-themed syntax highlighting.
 ///
-/// Uses a thread-local [`CommonMarkCache`] so repeated calls within the same
-/// frame are cheap.
+/// Prose is drawn by commonmark. Top-level fenced and indented code blocks
+/// are drawn with [`code_block`](super::code_block) in between. Documents
+/// are split once and kept in a thread-local cache for as long as they keep
+/// being drawn, so redrawing is cheap.
 pub fn markdown(ui: &mut egui::Ui, text: &str) {
+    markdown_with_width(ui, text, f32::INFINITY);
+}
+
+/// Like [`markdown`], but caps the text block at `max_text_width` and
+/// centers it in the available width, leaving the surrounding margin empty.
+///
+/// Widths at or above the available width render exactly like
+/// [`markdown`]. [`READABLE_TEXT_WIDTH`] gives comfortable line lengths
+/// for prose.
+pub fn markdown_with_width(ui: &mut egui::Ui, text: &str, max_text_width: f32) {
+    let available = ui.available_width();
+    if max_text_width >= available {
+        render_markdown(ui, text, "");
+        return;
+    }
+    let margin = (available - max_text_width) * 0.5;
+    ui.horizontal_top(|ui| {
+        ui.add_space(margin);
+        ui.vertical(|ui| {
+            ui.set_width(max_text_width);
+            render_markdown(ui, text, "");
+        });
+    });
+}
+
+/// Like [`markdown`], but highlights every occurrence of `term` in the
+/// theme's selection color and returns how many there are, for finding
+/// text inside a long prose card. Matching ignores ASCII case, and spaces
+/// in `term` match any whitespace, so matches may span soft line breaks
+/// and formatting changes.
+///
+/// Top-level paragraphs and headings are searched; lists, quotes, tables,
+/// definition lists and code blocks are not. Highlighted text keeps its
+/// formatting and links. An empty `term` renders exactly like [`markdown`].
+/// To drive it from the notebook-wide search bar:
+///
+/// ```ignore
+/// let search = ctx.search();
+/// let found = widgets::markdown_with_search(ctx, text, search.query());
+/// ```
+pub fn markdown_with_search(ui: &mut egui::Ui, text: &str, term: &str) -> usize {
+    render_markdown(ui, text, term)
+}
+
+/// Renders `text`, highlighting `term` in its paragraphs and headings;
+/// returns the number of matches.
+///
+/// Everything between the split-out blocks goes to commonmark in one piece,
+/// so only code blocks, definition lists and paragraphs with a match
+/// interrupt it.
+fn render_markdown(ui: &mut egui::Ui, text: &str, term: &str) -> usize {
+    let document = document(ui.ctx(), text);
+    let mut matches = 0;
+    let mut prose_start = 0;
+    for (index, block) in document.blocks.iter().enumerate() {
+        let ranges = match &block.kind {
+            BlockKind::Text { .. } if term.trim().is_empty() => continue,
+            BlockKind::Text { runs, .. } => {
+                let ranges = match_ranges(&plain_text(runs), term);
+                if ranges.is_empty() {
+                    continue;
+                }
+                ranges
+            }
+            _ => Vec::new(),
+        };
+        document.prose(ui, prose_start..block.range.start, index);
+        ui.push_id(("block", index), |ui| match &block.kind {
+            BlockKind::Code { language, code } => super::code_block(ui, code, language),
+            BlockKind::Definitions(items) => document.definitions(ui, items),
+            BlockKind::Text { runs, heading } => render_highlighted(ui, runs, *heading, &ranges),
+        });
+        matches += ranges.len();
+        prose_start = block.range.end;
+    }
+    document.prose(ui, prose_start..text.len(), document.blocks.len());
+    matches
+}
+
+/// Estimates the height `text` takes when drawn by [`markdown`] at `width`,
+/// without drawing it: each block's text is laid out in its font, without
+/// inline formatting, and a table's cells as one line per row.
+pub(crate) fn estimate_height(ui: &egui::Ui, text: &str, width: f32) -> f32 {
+    let document = document(ui.ctx(), text);
+    let mut heights = Vec::new();
+    let mut block = String::new();
+    let mut style = egui::TextStyle::Body;
+    for event in document.events.iter() {
+        match event {
+            Event::Text(text) | Event::Code(text) | Event::InlineMath(text) => block.push_str(text),
+            Event::SoftBreak => block.push(' '),
+            Event::HardBreak => block.push('\n'),
+            Event::Start(
+                Tag::Emphasis
+                | Tag::Strong
+                | Tag::Strikethrough
+                | Tag::Link { .. }
+                | Tag::Image { .. },
+            )
+            | Event::End(
+                TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Link
+                | TagEnd::Image,
+            ) => {}
+            Event::End(TagEnd::TableCell) => block.push(' '),
+            Event::Start(tag) => {
+                heights.extend(block_height(ui, &mut block, &style, width));
+                style = match tag {
+                    Tag::Heading {
+                        level: HeadingLevel::H1 | HeadingLevel::H2,
+                        ..
+                    } => egui::TextStyle::Heading,
+                    Tag::CodeBlock(_) => egui::TextStyle::Monospace,
+                    _ => egui::TextStyle::Body,
+                };
+            }
+            Event::End(_) => {
+                heights.extend(block_height(ui, &mut block, &style, width));
+                style = egui::TextStyle::Body;
+            }
+            Event::Rule => heights.push(ui.spacing().item_spacing.y * 2.0),
+            _ => {}
+        }
+    }
+    heights.extend(block_height(ui, &mut block, &style, width));
+    let gap = ui.text_style_height(&egui::TextStyle::Body) * 0.5;
+    heights.iter().sum::<f32>() + gap * heights.len().saturating_sub(1) as f32
+}
+
+/// Lays out the text collected for one block and clears it; `None` when
+/// there is none.
+fn block_height(
+    ui: &egui::Ui,
+    block: &mut String,
+    style: &egui::TextStyle,
+    width: f32,
+) -> Option<f32> {
+    if block.trim().is_empty() {
+        block.clear();
+        return None;
+    }
+    let font = style.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let text = std::mem::take(block);
+    Some(match style {
+        // Code doesn't wrap, and its block pads it above and below.
+        egui::TextStyle::Monospace => {
+            ui.painter().layout_no_wrap(text, font, color).size().y + ui.text_style_height(style)
+        }
+        _ => ui.painter().layout(text, font, color, width).size().y,
+    })
+}
+
+/// Byte ranges of `term` in `text`, ASCII case-insensitive, with any run
+/// of whitespace in the term matching any run in the text (so a match may
+/// span a line break).
+fn match_ranges(text: &str, term: &str) -> Vec<std::ops::Range<usize>> {
+    let words: Vec<String> = term
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let lower = text.to_ascii_lowercase();
+    let mut ranges = Vec::new();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(&words[0]) {
+        let start = from + pos;
+        let mut end = start + words[0].len();
+        let mut matched = true;
+        for word in &words[1..] {
+            let rest = &lower[end..];
+            let gap = rest.len() - rest.trim_start().len();
+            if gap == 0 || !rest[gap..].starts_with(word.as_str()) {
+                matched = false;
+                break;
+            }
+            end += gap + word.len();
+        }
+        if matched {
+            ranges.push(start..end);
+            from = end;
+        } else {
+            from = start + lower[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    ranges
+}
+
+/// A markdown document split for drawing.
+struct Document {
+    text: String,
+    /// The document's link reference definitions, one per line. They are
+    /// appended to every piece handed to commonmark, so references resolve
+    /// across the split.
+    references: String,
+    blocks: Vec<Block>,
+    events: Vec<Event<'static>>,
+}
+
+/// A top-level block drawn outside of commonmark, or a paragraph or
+/// heading that search may highlight.
+struct Block {
+    range: Range<usize>,
+    kind: BlockKind,
+}
+
+enum BlockKind {
+    Code {
+        language: String,
+        code: String,
+    },
+    Definitions(Vec<DefinitionItem>),
+    Text {
+        runs: Vec<Run>,
+        heading: Option<HeadingLevel>,
+    },
+}
+
+/// A term of a definition list with the source of its definitions, each
+/// without its `:` marker and continuation indent.
+#[derive(Debug, PartialEq)]
+struct DefinitionItem {
+    term: String,
+    definitions: Vec<String>,
+}
+
+impl Document {
+    fn parse(text: &str) -> Self {
+        let mut parser = Parser::new_ext(text, parser_options()).into_offset_iter();
+        let events: Vec<(Event<'static>, Range<usize>)> = parser
+            .by_ref()
+            .map(|(event, range)| (event.into_static(), range))
+            .collect();
+        let references = parser
+            .reference_definitions()
+            .iter()
+            .map(|(_, definition)| text[definition.span.clone()].trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut blocks = Vec::new();
+        let mut index = 0;
+        while index < events.len() {
+            let (event, range) = &events[index];
+            let end = match event {
+                Event::Start(_) => closing(&events, index),
+                _ => index,
+            };
+            let inner = &events[index + 1..end];
+            let kind = match event {
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or(""),
+                        CodeBlockKind::Indented => "",
+                    };
+                    let code = inner
+                        .iter()
+                        .filter_map(|(event, _)| match event {
+                            Event::Text(chunk) => Some(chunk.as_ref()),
+                            _ => None,
+                        })
+                        .collect();
+                    Some(BlockKind::Code {
+                        language: language.to_owned(),
+                        code,
+                    })
+                }
+                Event::Start(Tag::DefinitionList) => {
+                    Some(BlockKind::Definitions(definition_items(text, inner)))
+                }
+                Event::Start(Tag::Paragraph) => Some(BlockKind::Text {
+                    runs: inlines(inner.iter().map(|(event, _)| event)),
+                    heading: None,
+                }),
+                Event::Start(Tag::Heading { level, .. }) => Some(BlockKind::Text {
+                    runs: inlines(inner.iter().map(|(event, _)| event)),
+                    heading: Some(*level),
+                }),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                blocks.push(Block {
+                    range: range.clone(),
+                    kind,
+                });
+            }
+            index = end + 1;
+        }
+
+        Document {
+            text: text.to_owned(),
+            references,
+            blocks,
+            events: events.into_iter().map(|(event, _)| event).collect(),
+        }
+    }
+
+    /// Draws `source` through commonmark with the reference definitions
+    /// appended.
+    fn commonmark(&self, ui: &mut egui::Ui, source: &str) {
+        if self.references.is_empty() || source.len() == self.text.len() {
+            render_commonmark(ui, source);
+        } else {
+            render_commonmark(ui, &format!("{source}\n\n{}", self.references));
+        }
+    }
+
+    /// Draws the prose in `range` of the text, unless it is blank.
+    fn prose(&self, ui: &mut egui::Ui, range: Range<usize>, index: usize) {
+        let prose = &self.text[range];
+        if !prose.trim().is_empty() {
+            ui.push_id(("prose", index), |ui| self.commonmark(ui, prose));
+        }
+    }
+
+    /// Draws each term in bold on its own line with its definitions
+    /// indented below.
+    fn definitions(&self, ui: &mut egui::Ui, items: &[DefinitionItem]) {
+        for (index, item) in items.iter().enumerate() {
+            ui.push_id(index, |ui| {
+                self.commonmark(ui, &format!("**{}**", item.term));
+                ui.indent("definitions", |ui| {
+                    for (index, definition) in item.definitions.iter().enumerate() {
+                        ui.push_id(index, |ui| self.commonmark(ui, definition));
+                    }
+                });
+            });
+        }
+    }
+}
+
+/// The index of the end event closing the start event at `start`.
+fn closing(events: &[(Event<'static>, Range<usize>)], start: usize) -> usize {
+    let mut depth = 0;
+    for (index, (event, _)) in events.iter().enumerate().skip(start) {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) if depth == 1 => return index,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+    events.len() - 1
+}
+
+/// The terms and definitions of a definition list, from the events between
+/// its start and end.
+fn definition_items(text: &str, events: &[(Event<'static>, Range<usize>)]) -> Vec<DefinitionItem> {
+    let mut items: Vec<DefinitionItem> = Vec::new();
+    let mut depth = 0;
+    for (event, range) in events {
+        match event {
+            Event::Start(tag) => {
+                match tag {
+                    Tag::DefinitionListTitle if depth == 0 => items.push(DefinitionItem {
+                        term: text[range.clone()].trim().to_owned(),
+                        definitions: Vec::new(),
+                    }),
+                    Tag::DefinitionListDefinition if depth == 0 => {
+                        let source = text[range.clone()].trim_start();
+                        let source = source.strip_prefix(':').unwrap_or(source);
+                        let definition = source.lines().map(str::trim).collect::<Vec<_>>();
+                        if let Some(item) = items.last_mut() {
+                            item.definitions
+                                .push(definition.join("\n").trim().to_owned());
+                        }
+                    }
+                    _ => {}
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Split documents drawn in the last pass, keyed by a hash of their text.
+#[derive(Default)]
+struct DocumentCache {
+    pass: u64,
+    documents: HashMap<u64, (u64, Rc<Document>)>,
+}
+
+fn parser_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_DEFINITION_LIST
+}
+
+/// `text` split for drawing, parsed on first use. Documents not drawn
+/// during the previous pass are dropped from the cache.
+fn document(ctx: &egui::Context, text: &str) -> Rc<Document> {
+    let pass = ctx.cumulative_pass_nr();
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    let key = hasher.finish();
+    GORBIE_MD_DOCUMENTS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.pass != pass {
+            let previous = cache.pass;
+            cache.documents.retain(|_, (used, _)| *used >= previous);
+            cache.pass = pass;
+        }
+        let (used, document) = cache
+            .documents
+            .entry(key)
+            .or_insert_with(|| (pass, Rc::new(Document::parse(text))));
+        *used = pass;
+        document.clone()
+    })
+}
+
+/// Inline formatting in effect for a run of text.
+#[derive(Clone, Default)]
+struct InlineStyle {
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+    code: bool,
+    weak: bool,
+    link: Option<String>,
+}
+
+/// A run of paragraph or heading text in one style.
+struct Run {
+    text: String,
+    style: InlineStyle,
+}
+
+/// The text runs of a paragraph or heading, from the events between its
+/// start and end. Images become their alt text.
+fn inlines<'e>(events: impl Iterator<Item = &'e Event<'static>>) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut styles = vec![InlineStyle::default()];
+    for event in events {
+        let style = styles.last().cloned().unwrap_or_default();
+        let mut push = |text: &str, style: InlineStyle| {
+            runs.push(Run {
+                text: text.to_owned(),
+                style,
+            });
+        };
+        match event {
+            Event::Text(text) => push(text, style),
+            Event::Code(code) | Event::InlineHtml(code) | Event::InlineMath(code) => push(
+                code,
+                InlineStyle {
+                    code: true,
+                    ..style
+                },
+            ),
+            Event::SoftBreak => push(" ", style),
+            Event::HardBreak => push("\n", style),
+            Event::FootnoteReference(label) => push(
+                &format!("[{label}]"),
+                InlineStyle {
+                    weak: true,
+                    ..style
+                },
+            ),
+            Event::Start(tag) => styles.push(match tag {
+                Tag::Emphasis => InlineStyle {
+                    emphasis: true,
+                    ..style
+                },
+                Tag::Strong => InlineStyle {
+                    strong: true,
+                    ..style
+                },
+                Tag::Strikethrough => InlineStyle {
+                    strikethrough: true,
+                    ..style
+                },
+                Tag::Link { dest_url, .. } => InlineStyle {
+                    link: Some(dest_url.to_string()),
+                    ..style
+                },
+                Tag::Image { .. } => InlineStyle {
+                    weak: true,
+                    ..style
+                },
+                _ => style,
+            }),
+            Event::End(_) => {
+                styles.pop();
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// The runs' text joined, as it reads on screen.
+fn plain_text(runs: &[Run]) -> String {
+    runs.iter().map(|run| run.text.as_str()).collect()
+}
+
+/// Draws a paragraph or heading as one label, with `ranges` of its plain
+/// text on the selection color; links open on click.
+fn render_highlighted(
+    ui: &mut egui::Ui,
+    runs: &[Run],
+    heading: Option<HeadingLevel>,
+    ranges: &[Range<usize>],
+) {
+    let highlight = ui.visuals().selection.bg_fill;
+    let mut job = egui::text::LayoutJob::default();
+    for (text, style, matched) in split_at_matches(runs, ranges) {
+        let mut rich = rich_text(ui, text, style, heading);
+        if matched {
+            rich = rich.background_color(highlight);
+        }
+        rich.append_to(
+            &mut job,
+            ui.style(),
+            egui::FontSelection::Default,
+            egui::Align::Min,
+        );
+    }
+    job.wrap.max_width = ui.available_width();
+    let galley = ui.ctx().fonts_mut(|fonts| fonts.layout_job(job));
+    if !runs.iter().any(|run| run.style.link.is_some()) {
+        ui.add(egui::Label::new(galley));
+        return;
+    }
+    let response = ui.add(egui::Label::new(galley.clone()).sense(egui::Sense::click()));
+    let Some(pointer) = response.hover_pos() else {
+        return;
+    };
+    let cursor = galley.cursor_from_pos(pointer - response.rect.min);
+    if let Some(url) = link_at(runs, cursor.index) {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        if response.clicked() {
+            ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+        }
+    }
+}
+
+/// Splits the runs at the boundaries of `ranges`, byte ranges into the
+/// runs' joined text, so a match may cover several runs. Each piece comes
+/// with its run's style and whether it lies inside a match.
+fn split_at_matches<'r>(
+    runs: &'r [Run],
+    ranges: &[Range<usize>],
+) -> Vec<(&'r str, &'r InlineStyle, bool)> {
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+    for Run { text, style } in runs {
+        let end = offset + text.len();
+        let mut cursor = offset;
+        for range in ranges
+            .iter()
+            .filter(|range| range.start < end && range.end > offset)
+        {
+            let start = range.start.max(offset);
+            let stop = range.end.min(end);
+            if cursor < start {
+                pieces.push((&text[cursor - offset..start - offset], style, false));
+            }
+            pieces.push((&text[start - offset..stop - offset], style, true));
+            cursor = stop;
+        }
+        if cursor < end {
+            pieces.push((&text[cursor - offset..], style, false));
+        }
+        offset = end;
+    }
+    pieces
+}
+
+/// The link target of the run holding character `index`.
+fn link_at(runs: &[Run], index: usize) -> Option<&str> {
+    let mut offset = 0;
+    for run in runs {
+        offset += run.text.chars().count();
+        if index < offset {
+            return run.style.link.as_deref();
+        }
+    }
+    None
+}
+
+fn rich_text(
+    ui: &egui::Ui,
+    text: &str,
+    style: &InlineStyle,
+    heading: Option<HeadingLevel>,
+) -> egui::RichText {
+    let mut rich = egui::RichText::new(text);
+    rich = match heading {
+        Some(HeadingLevel::H1) => rich.heading().strong(),
+        Some(HeadingLevel::H2) => {
+            let heading = egui::TextStyle::Heading.resolve(ui.style()).size;
+            let body = egui::TextStyle::Body.resolve(ui.style()).size;
+            rich.size((heading + body) * 0.5).strong()
+        }
+        Some(_) => rich.strong(),
+        None => rich,
+    };
+    if style.code {
+        rich = rich.code();
+    }
+    if style.strong {
+        rich = rich.strong();
+    }
+    if style.emphasis {
+        rich = rich.italics();
+    }
+    if style.strikethrough {
+        rich = rich.strikethrough();
+    }
+    if style.weak {
+        rich = rich.weak();
+    }
+    if style.link.is_some() {
+        rich = rich.color(ui.visuals().hyperlink_color).underline();
+    }
+    rich
+}
+
+pub(super) fn render_commonmark(ui: &mut egui::Ui, text: &str) {
     // Use a thread-local cache (no locking) and render the formatted markdown.
     GORBIE_MD_CACHE.with(|c| {
         let mut cache = c.borrow_mut();
@@ -54,16 +696,97 @@ pub fn markdown(ui: &mut egui::Ui, text: &str) {
 
 /// Render a formatted markdown string as a full-width grid cell.
 ///
-/// Accepts `format!`-style arguments:
-/// ```ignore
+/// Accepts the full `format!` syntax — positional, named, and inline
+/// captured arguments — so interpolated markdown is as terse as static
+/// markdown:
+/// ```no_run
+/// # use GORBIE::prelude::*;
+/// # fn card(ctx: &mut CardCtx<'_>) {
+/// let count = 42;
 /// md!(ctx, "**Count:** {}", count);
+/// # }
 /// ```
 #[macro_export]
 macro_rules! md {
-    ($ctx:expr, $fmt:expr $(, $args:expr)*) => {
+    ($ctx:expr, $($fmt:tt)+) => {
         {
-            let text = format!($fmt $(, $args)*);
+            let text = format!($($fmt)+);
             $ctx.grid(|g| g.full(|ctx| ctx.markdown(&text)));
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_out_top_level_code_blocks() {
+        let text = "# Title\n\n```rust ignore\nfn main() {}\n```\n\n    indented\n\n- item\n\n  ```\n  nested\n  ```\n\nSee [x].\n\n[x]: http://x\n";
+        let document = Document::parse(text);
+        let code: Vec<(&str, &str)> = document
+            .blocks
+            .iter()
+            .filter_map(|block| match &block.kind {
+                BlockKind::Code { language, code } => Some((language.as_str(), code.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(code, vec![("rust", "fn main() {}\n"), ("", "indented\n")]);
+        assert_eq!(document.blocks.len(), 4);
+        assert_eq!(document.references, "[x]: http://x");
+    }
+
+    #[test]
+    fn finds_terms_across_soft_breaks_and_markup() {
+        let events: Vec<Event<'static>> = Parser::new_ext(
+            "It *converges*\n  after [many](http://x) sweeps.",
+            parser_options(),
+        )
+        .map(Event::into_static)
+        .collect();
+        let runs = inlines(events[1..events.len() - 1].iter());
+        assert_eq!(plain_text(&runs), "It converges after many sweeps.");
+        assert_eq!(link_at(&runs, 2), None);
+        assert_eq!(link_at(&runs, 20), Some("http://x"));
+        let ranges = match_ranges(&plain_text(&runs), "CONVERGES  after");
+        assert_eq!(ranges, vec![3..18]);
+        let pieces: Vec<(&str, bool)> = split_at_matches(&runs, &ranges)
+            .into_iter()
+            .map(|(text, _, matched)| (text, matched))
+            .collect();
+        assert_eq!(
+            pieces,
+            vec![
+                ("It ", false),
+                ("converges", true),
+                (" ", true),
+                ("after", true),
+                (" ", false),
+                ("many", false),
+                (" sweeps.", false),
+            ]
+        );
+        assert!(match_ranges("snake_case", "").is_empty());
+    }
+
+    #[test]
+    fn splits_out_definition_lists() {
+        let document = Document::parse("Intro\n\nTerm\n: First\n  continued\n: Second\n");
+        let Some(BlockKind::Definitions(items)) = document.blocks.last().map(|block| &block.kind)
+        else {
+            panic!("expected a definition list");
+        };
+        assert_eq!(
+            items,
+            &vec![DefinitionItem {
+                term: "Term".to_owned(),
+                definitions: vec!["First\ncontinued".to_owned(), "Second".to_owned()],
+            }]
+        );
+        assert!(!Document::parse("# Title\n: not a term\n")
+            .blocks
+            .iter()
+            .any(|block| matches!(block.kind, BlockKind::Definitions(_))));
+    }
+}
@@ -0,0 +1,154 @@
+use eframe::egui::{self, Stroke, TextStyle};
+use egui_plot::{Legend, Line, Plot, PlotPoints, PlotResponse, PlotUi, Polygon};
+
+/// An [`egui_plot::Plot`] preset to match the notebook theme.
+///
+/// This drops the plot's own background (which otherwise shows as a dark
+/// slab in the industrial dark theme), sets a transparent monospace legend,
+/// and keeps the usual builder open so callers still set height. Show it
+/// with [`show_styled_plot`], which styles the axes and grid:
+///
+/// ```ignore
+/// let plot = widgets::styled_plot("cost").height(320.0);
+/// widgets::show_styled_plot(ui, plot, |plot_ui| plot_ui.line(Line::new("best", points)));
+/// ```
+pub fn styled_plot(name: &str) -> Plot<'static> {
+    Plot::new(("gorbie_styled_plot", name))
+        .show_background(false)
+        .legend(
+            Legend::default()
+                .text_style(TextStyle::Monospace)
+                .background_alpha(0.0),
+        )
+}
+
+/// Shows `plot` with its axes and grid taken from the active visuals.
+///
+/// egui_plot sets axis labels in the body font and derives grid lines
+/// from the text color; here both come from the theme's weak text color
+/// and the labels are monospace, like the rest of the notebook's numbers.
+/// Text added inside `build` follows the same style.
+pub fn show_styled_plot<'a, R>(
+    ui: &mut egui::Ui,
+    plot: Plot<'a>,
+    build: impl FnOnce(&mut PlotUi<'a>) -> R,
+) -> PlotResponse<R> {
+    ui.scope(|ui| {
+        let style = ui.style_mut();
+        let monospace = TextStyle::Monospace.resolve(style);
+        style.text_styles.insert(TextStyle::Body, monospace);
+        style.visuals.override_text_color = Some(style.visuals.weak_text_color());
+        plot.show(ui, build)
+    })
+    .inner
+}
+
+/// Series colors for [`stacked_area`], from RAL classic.
+const SERIES_RAL: [u16; 7] = [1003, 2010, 3001, 4008, 5005, 6032, 3014];
+
+/// A stacked area plot: one band per category, each filled between the
+/// running sum of the categories before it and that sum plus its own
+/// values, e.g. time per category across runs.
+///
+/// Series are stacked on the union of their x values, interpolating
+/// linearly and counting as 0 outside their own range; negative values
+/// are clamped to 0. Clicking a legend entry hides its band (the bands
+/// above keep their place). Built on [`styled_plot`], 320 px high.
+pub fn stacked_area(
+    ui: &mut egui::Ui,
+    title: &str,
+    categories: &[&str],
+    points: &[Vec<[f64; 2]>],
+) -> egui::Response {
+    let (xs, tops) = stack_series(points);
+    show_styled_plot(ui, styled_plot(title).height(320.0), |plot_ui| {
+        let mut bottom = vec![0.0; xs.len()];
+        for (idx, (name, top)) in categories.iter().zip(&tops).enumerate() {
+            let color = crate::themes::ral(SERIES_RAL[idx % SERIES_RAL.len()]);
+            // egui only fills convex shapes, so each band is drawn as
+            // one trapezoid per segment; sharing the band's name keeps
+            // them a single legend entry.
+            for i in 1..xs.len() {
+                let quad = vec![
+                    [xs[i - 1], bottom[i - 1]],
+                    [xs[i], bottom[i]],
+                    [xs[i], top[i]],
+                    [xs[i - 1], top[i - 1]],
+                ];
+                plot_ui.polygon(
+                    Polygon::new(*name, PlotPoints::from(quad))
+                        .fill_color(color.gamma_multiply(0.6))
+                        .stroke(Stroke::NONE),
+                );
+            }
+            let edge: Vec<[f64; 2]> = xs.iter().zip(top).map(|(&x, &y)| [x, y]).collect();
+            plot_ui.line(Line::new(*name, PlotPoints::from(edge)).color(color));
+            bottom.clone_from(top);
+        }
+    })
+    .response
+}
+
+/// Resamples `series` onto the sorted union of their x values and returns
+/// those xs with, per series, the cumulative top of its band.
+fn stack_series(series: &[Vec<[f64; 2]>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let mut xs: Vec<f64> = series
+        .iter()
+        .flatten()
+        .map(|point| point[0])
+        .filter(|x| x.is_finite())
+        .collect();
+    xs.sort_by(f64::total_cmp);
+    xs.dedup();
+
+    let mut running = vec![0.0; xs.len()];
+    let tops = series
+        .iter()
+        .map(|points| {
+            let mut points: Vec<[f64; 2]> = points
+                .iter()
+                .copied()
+                .filter(|p| p[0].is_finite())
+                .collect();
+            points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+            for (sum, &x) in running.iter_mut().zip(&xs) {
+                *sum += sample(&points, x).max(0.0);
+            }
+            running.clone()
+        })
+        .collect();
+    (xs, tops)
+}
+
+/// Linear interpolation of sorted `points` at `x`; 0 outside their range
+/// or where the value isn't finite.
+fn sample(points: &[[f64; 2]], x: f64) -> f64 {
+    let after = points.partition_point(|p| p[0] < x);
+    let value = match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+        (_, Some(next)) if next[0] == x => next[1],
+        (Some(prev), Some(next)) => {
+            let t = (x - prev[0]) / (next[0] - prev[0]);
+            prev[1] + (next[1] - prev[1]) * t
+        }
+        _ => 0.0,
+    };
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_on_union_of_xs_clamping_negatives() {
+        let (xs, tops) =
+            stack_series(&[vec![[0.0, 1.0], [2.0, 3.0]], vec![[1.0, -5.0], [2.0, 1.0]]]);
+        assert_eq!(xs, vec![0.0, 1.0, 2.0]);
+        assert_eq!(tops[0], vec![1.0, 2.0, 3.0]);
+        assert_eq!(tops[1], vec![1.0, 2.0, 4.0]);
+    }
+}
@@ -354,6 +354,68 @@ impl Widget for ProgressBar {
     }
 }
 
+/// Plain determinate progress bar: a square, ink-outlined slot filled with
+/// the accent color up to `fraction` (clamped to `[0, 1]`), with optional
+/// `text` centered on top. The text is knocked out in the window fill
+/// where it overlaps the filled part so it stays readable at any progress.
+///
+/// Use [`ProgressBar`] for the segmented meter with scales and zones.
+pub fn progress_bar(ui: &mut Ui, fraction: f32, text: Option<&str>) -> Response {
+    let fraction = if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    };
+    let height = 2.0 * crate::card_ctx::GRID_ROW_MODULE;
+    let width = ui.available_size_before_wrap().x.at_least(96.0);
+    let (rect, response) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+
+    let enabled = ui.is_enabled();
+    let label = text.map(str::to_owned);
+    response.widget_info(move || {
+        let mut info = match label.as_deref() {
+            Some(label) => WidgetInfo::labeled(WidgetType::ProgressIndicator, enabled, label),
+            None => WidgetInfo::new(WidgetType::ProgressIndicator),
+        };
+        info.value = Some((fraction as f64 * 100.0).floor());
+        info
+    });
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.visuals();
+        let ink = visuals.widgets.noninteractive.fg_stroke.color;
+        let accent = visuals.selection.stroke.color;
+        let background = visuals.window_fill;
+        let painter = ui.painter();
+
+        let filled = Rect::from_min_max(
+            rect.min,
+            pos2(rect.left() + rect.width() * fraction, rect.bottom()),
+        );
+        painter.rect_filled(rect, 0.0, background);
+        painter.rect_filled(filled, 0.0, accent);
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, ink), egui::StrokeKind::Inside);
+
+        if let Some(text) = text {
+            let font_id = TextStyle::Small.resolve(ui.style());
+            let empty = Rect::from_min_max(pos2(filled.right(), rect.top()), rect.max);
+            for (clip, color) in [(empty, ink), (filled, background)] {
+                if clip.is_positive() {
+                    painter.with_clip_rect(clip).text(
+                        rect.center(),
+                        Align2::CENTER_CENTER,
+                        text,
+                        font_id.clone(),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    response
+}
+
 impl crate::themes::Styled for ProgressBar {
     type Style = GorbieProgressBarStyle;
 
@@ -0,0 +1,92 @@
+use eframe::egui::{
+    self, pos2, vec2, CursorIcon, Rect, Response, Sense, TextStyle, TextWrapMode, Ui, WidgetText,
+};
+
+/// A vertical list of selectable options, each with a title and a one-line
+/// description.
+///
+/// More informative than a combo box for settings whose options need a word
+/// of explanation. The selected row is marked with a bar in the theme accent
+/// color — the same colored-divider language as `CardCtx::section`; the
+/// description is drawn in the weak text color. The returned response is
+/// `changed()` when the selection moves.
+///
+/// ```ignore
+/// widgets::radio_list(ui, &mut pattern, &[
+///     (GraphPattern::Chain, "Chain", "Nodes linked in a single line."),
+///     (GraphPattern::Star, "Star", "Every node linked to a central hub."),
+/// ]);
+/// ```
+pub fn radio_list<T>(ui: &mut Ui, value: &mut T, options: &[(T, &str, &str)]) -> Response
+where
+    T: Clone + PartialEq,
+{
+    let width = ui.available_width();
+    let accent = ui.visuals().selection.stroke.color;
+    let hover_fill = ui.visuals().widgets.hovered.weak_bg_fill;
+    let text_color = ui.visuals().text_color();
+    let weak = ui.visuals().weak_text_color();
+    let pad = vec2(12.0, 4.0);
+    let bar_width = 3.0;
+
+    let mut changed = false;
+    let mut response: Option<Response> = None;
+    let spacing = ui.spacing().item_spacing;
+    ui.spacing_mut().item_spacing.y = 0.0;
+    for (option, title, description) in options {
+        let selected = *value == *option;
+        let max_text_width = (width - pad.x * 2.0).max(0.0);
+        let title_galley = WidgetText::from(*title).strong().into_galley(
+            ui,
+            Some(TextWrapMode::Truncate),
+            max_text_width,
+            TextStyle::Body,
+        );
+        let description_galley = WidgetText::from(*description).into_galley(
+            ui,
+            Some(TextWrapMode::Truncate),
+            max_text_width,
+            TextStyle::Small,
+        );
+        let height = pad.y * 2.0 + title_galley.size().y + description_galley.size().y;
+        let (rect, row) = ui.allocate_exact_size(vec2(width, height), Sense::click());
+        let row = row.on_hover_cursor(CursorIcon::PointingHand);
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter();
+            if row.hovered() && !selected {
+                painter.rect_filled(rect, 0.0, hover_fill);
+            }
+            if selected {
+                let bar = Rect::from_min_max(
+                    rect.left_top(),
+                    pos2(rect.left() + bar_width, rect.bottom()),
+                );
+                painter.rect_filled(bar, 0.0, accent);
+            }
+            let title_pos = rect.left_top() + pad;
+            let description_pos = title_pos + vec2(0.0, title_galley.size().y);
+            let title_color = if selected { accent } else { text_color };
+            painter.galley(title_pos, title_galley, title_color);
+            painter.galley(description_pos, description_galley, weak);
+        }
+
+        if row.clicked() && !selected {
+            *value = option.clone();
+            changed = true;
+        }
+
+        response = Some(match response {
+            Some(response) => response | row,
+            None => row,
+        });
+    }
+    ui.spacing_mut().item_spacing = spacing;
+
+    let mut response = response
+        .unwrap_or_else(|| ui.allocate_response(egui::Vec2::ZERO, Sense::hover()));
+    if changed {
+        response.mark_changed();
+    }
+    response
+}
@@ -0,0 +1,41 @@
+use eframe::egui::{vec2, Response, Sense, TextStyle, TextWrapMode, Ui, WidgetText};
+
+/// Uppercase monospace label over a full-width hairline, for sub-sections
+/// inside a card.
+///
+/// Matches the page header: the label uses the same strong monospace
+/// type, and the rule is drawn in the theme's `bg_stroke` color like the
+/// dividers between cards. Adds card-padding space above the header and a
+/// smaller gap below it, so it can be dropped between blocks of content
+/// without extra `add_space` calls.
+///
+/// ```ignore
+/// widgets::section_header(ui, "Stress over time");
+/// ```
+pub fn section_header(ui: &mut Ui, text: &str) -> Response {
+    let space_above = crate::cards::DEFAULT_CARD_PADDING.top as f32;
+    let space_below = space_above * 0.5;
+    let rule_gap = 2.0;
+
+    ui.add_space(space_above);
+    let width = ui.available_width();
+    let galley = WidgetText::from(text.to_uppercase()).strong().into_galley(
+        ui,
+        Some(TextWrapMode::Truncate),
+        width,
+        TextStyle::Monospace,
+    );
+    let height = galley.size().y + rule_gap + 1.0;
+    let (rect, response) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+
+    if ui.is_rect_visible(rect) {
+        let painter = ui.painter();
+        let text_color = ui.visuals().text_color();
+        let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+        painter.galley(rect.left_top(), galley, text_color);
+        painter.hline(rect.x_range(), rect.bottom() - 0.5, stroke);
+    }
+    ui.add_space(space_below);
+
+    response
+}
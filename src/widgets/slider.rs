@@ -272,6 +272,9 @@ impl<'a> Slider<'a> {
     /// Make this a logarithmic slider.
     /// This is great for when the slider spans a huge range,
     /// e.g. from one to a million.
+    /// Ranges touching or crossing zero are supported: the left end still
+    /// selects exactly zero, and the log scale starts at
+    /// [`smallest_positive`](Self::smallest_positive).
     /// The default is OFF.
     #[inline]
     pub fn logarithmic(mut self, logarithmic: bool) -> Self {
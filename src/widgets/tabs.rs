@@ -0,0 +1,85 @@
+use eframe::egui::{self, vec2, Sense, Shape, TextStyle, TextWrapMode, Ui, WidgetText};
+
+/// A row of tabs over a full-width hairline with the selected tab's body
+/// below, for cards with several views of the same data.
+///
+/// Labels are set like [`section_header`](super::section_header): strong
+/// uppercase monospace, with the selected tab underlined in the accent
+/// color and the others in weak text. `selected` is clamped to the labels
+/// and is the only state, so keep it wherever the card keeps its own
+/// (e.g. a field of its state) to have the tab survive across frames.
+/// Returns whether the selected tab changed this frame.
+///
+/// ```ignore
+/// widgets::tabs(ui, &mut state.tab, &["Flamegraph", "Hotspots"], |ui, tab| match tab {
+///     0 => show_flamegraph(ui),
+///     _ => show_hotspots(ui),
+/// });
+/// ```
+pub fn tabs(
+    ui: &mut Ui,
+    selected: &mut usize,
+    labels: &[&str],
+    mut body: impl FnMut(&mut Ui, usize),
+) -> bool {
+    if labels.is_empty() {
+        return false;
+    }
+    let before = *selected;
+    *selected = (*selected).min(labels.len() - 1);
+
+    let padding = vec2(12.0, 6.0);
+    let space_below = crate::cards::DEFAULT_CARD_PADDING.top as f32 * 0.5;
+    let width = ui.available_width();
+    // The hairline goes under the tabs' accent underline, so reserve its
+    // slot before painting them.
+    let rule = ui.painter().add(Shape::Noop);
+    let row = ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        for (idx, label) in labels.iter().enumerate() {
+            let galley = WidgetText::from(label.to_uppercase()).strong().into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Monospace,
+            );
+            let (rect, response) =
+                ui.allocate_exact_size(galley.size() + padding * 2.0, Sense::click());
+            if response.clicked() {
+                *selected = idx;
+            }
+            response.widget_info(|| {
+                egui::WidgetInfo::selected(
+                    egui::WidgetType::SelectableLabel,
+                    ui.is_enabled(),
+                    idx == *selected,
+                    *label,
+                )
+            });
+            if ui.is_rect_visible(rect) {
+                let visuals = ui.visuals();
+                let active = idx == *selected;
+                let text_color = if active || response.hovered() {
+                    visuals.strong_text_color()
+                } else {
+                    visuals.weak_text_color()
+                };
+                let painter = ui.painter();
+                painter.galley(rect.min + padding, galley, text_color);
+                if active {
+                    let accent = visuals.selection.stroke;
+                    painter.hline(rect.x_range(), rect.bottom() - accent.width * 0.5, accent);
+                }
+            }
+        }
+    });
+    let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+    let bottom = row.response.rect.bottom() - 0.5;
+    let left = row.response.rect.left();
+    ui.painter()
+        .set(rule, Shape::hline(left..=left + width, bottom, stroke));
+    ui.add_space(space_below);
+
+    body(ui, *selected);
+    *selected != before
+}
@@ -16,20 +16,27 @@ pub mod pile_repo;
 pub mod pile_tail;
 #[cfg(feature = "gloss")]
 pub mod pile_overview;
+pub mod query_card;
 
 pub use commit_history::CommitHistoryResponse;
 pub use commit_history::CommitHistoryState;
 pub use commit_history::CommitHistoryWidget;
 pub use entity_inspector::id_full;
 pub use entity_inspector::id_short;
+pub use entity_inspector::DEFAULT_MAX_NODES;
+pub use entity_inspector::EntityEdgeRef;
 pub use entity_inspector::EntityInspectorResponse;
 pub use entity_inspector::EntityInspectorStats;
 pub use entity_inspector::EntityInspectorWidget;
 pub use entity_inspector::EntityOrder;
+pub use entity_inspector::RowOrder;
+pub use entity_inspector::TileStyle;
 pub use pile_repo::PileRepoResponse;
 pub use pile_repo::PileRepoState;
 pub use pile_repo::PileRepoWidget;
 pub use pile_tail::PileTail;
+pub use query_card::QueryCardResponse;
+pub use query_card::QueryCardWidget;
 #[cfg(feature = "gloss")]
 pub use pile_overview::PileOverviewData;
 #[cfg(feature = "gloss")]
@@ -1,4 +1,7 @@
+use std::any::{Any, TypeId};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 #[cfg(feature = "cubecl")]
 use std::sync::mpsc;
 use std::sync::Arc;
@@ -21,15 +24,17 @@ use triblespace::core::blob::encodings::wasmcode::WasmCode;
 use triblespace::core::blob::BlobCache;
 use triblespace::core::id::Id;
 use triblespace::core::query::TriblePattern;
-use triblespace::core::repo::BlobStoreGet;
-use triblespace::core::inline::encodings::hash::Handle;
+use triblespace::core::blob::encodings::simplearchive::SimpleArchive;
+use triblespace::core::repo::{BlobStoreGet, BlobStoreMeta};
+use triblespace::core::inline::encodings::hash::{Blake3, Handle};
 use triblespace::core::inline::encodings::UnknownInline;
-use triblespace::core::inline::Inline;
+use triblespace::core::inline::{Inline, RawInline};
 use triblespace::core::value_formatter::{WasmLimits, WasmValueFormatter};
 use triblespace::prelude::inlineencodings::GenId;
 use triblespace::prelude::{find, pattern, TribleSet, TribleSetFingerprint, View};
 
 use crate::themes;
+use crate::widgets::BlobLoadLimits;
 
 fn hex_prefix(bytes: impl AsRef<[u8]>, prefix_len: usize) -> String {
     let bytes = bytes.as_ref();
@@ -57,8 +62,17 @@ fn try_decode_genid(raw: &[u8; 32]) -> Option<Id> {
     parsed.ok()
 }
 
-fn paint_hatching(painter: &egui::Painter, rect: Rect, color: egui::Color32) {
-    let spacing = 8.0;
+/// How far the hatch lines are faded from the ink toward the tile fill.
+/// Derived from the ink rather than the hairline color, which can all but
+/// vanish against the fill in the dark theme.
+const HATCH_INK_FADE: f32 = 0.6;
+
+/// Hatch line spacing for rows of `row_height`, tighter in compact layouts.
+fn hatch_spacing(row_height: f32) -> f32 {
+    (row_height * 0.4).clamp(4.0, 8.0)
+}
+
+fn paint_hatching(painter: &egui::Painter, rect: Rect, color: egui::Color32, spacing: f32) {
     let stroke = Stroke::new(1.0, color);
 
     let h = rect.height();
@@ -70,19 +84,42 @@ fn paint_hatching(painter: &egui::Painter, rect: Rect, color: egui::Color32) {
 }
 
 #[derive(Clone, Debug)]
-struct EntityRow {
-    attr_id: Id,
+pub(super) struct EntityRow {
+    pub(super) attr_id: Id,
     attr: String,
-    value: String,
+    pub(super) value: String,
     target: Option<Id>,
-    hatched: bool,
+    pub(super) hatched: bool,
+    /// Drawn over the hatching when the value's blob was too large to load.
+    size_note: Option<String>,
+}
+
+/// What a screen reader announces for a painted row: the attribute and
+/// either its value or, for hatched rows, why the value isn't shown.
+fn row_accessible_label(row: &EntityRow) -> String {
+    let value = if row.hatched {
+        row.size_note.as_deref().unwrap_or("value not shown")
+    } else {
+        row.value.as_str()
+    };
+    format!("{}: {value}", row.attr)
+}
+
+/// A reference edge as passed to
+/// [`EntityInspectorWidget::on_edge_click`]: entity `from` has attribute
+/// `attr` pointing at entity `to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityEdgeRef {
+    pub from: Id,
+    pub to: Id,
+    pub attr: Id,
 }
 
 #[derive(Clone, Debug)]
-struct EntityNode {
-    id: Id,
-    title: String,
-    rows: Vec<EntityRow>,
+pub(super) struct EntityNode {
+    pub(super) id: Id,
+    pub(super) title: String,
+    pub(super) rows: Vec<EntityRow>,
 }
 
 #[derive(Clone, Debug)]
@@ -93,23 +130,63 @@ struct EntityEdge {
     attr_id: Id,
 }
 
-#[derive(Debug)]
-struct EntityGraph {
-    nodes: Vec<EntityNode>,
+#[derive(Clone, Debug)]
+pub(super) struct EntityGraph {
+    pub(super) nodes: Vec<EntityNode>,
     edges: Vec<EntityEdge>,
     id_to_index: HashMap<Id, usize>,
+    /// Every attribute present in the data, hidden or not, sorted by label.
+    pub(super) attributes: Vec<(Id, String)>,
+    /// Hash of the nodes, their rows and the edges, taken when the graph
+    /// is built, for caches derived from it.
+    fingerprint: u64,
 }
 
 #[derive(Clone, Debug)]
 struct AttrInfo {
     label: String,
+    /// Whether `label` comes from the metadata rather than the `attr:`
+    /// id fallback.
+    named: bool,
     schema: Option<Id>,
     formatter: Option<Inline<Handle<WasmCode>>>,
+    /// Rank among the attributes the metadata declares a value encoding
+    /// for, by attribute id, if it declares one.
+    declared: Option<usize>,
+}
+
+/// Blob size check, [`BlobLoadLimits::default`] unless configured through
+/// [`EntityInspectorWidget::blob_limits`].
+#[derive(Default)]
+pub(super) struct BlobGuard<'a> {
+    limits: BlobLoadLimits,
+    /// Looks blob lengths up before loading; without it only loaded names
+    /// can be checked.
+    blob_len: Option<Box<dyn Fn(RawInline) -> Option<u64> + 'a>>,
+}
+
+impl BlobGuard<'_> {
+    /// The blob's length if it is known and exceeds the limit.
+    fn oversized(&self, hash: RawInline) -> Option<u64> {
+        let blob_len = self.blob_len.as_ref()?;
+        blob_len(hash).filter(|&len| !self.limits.allows(len))
+    }
+
+    /// `name`, or the "value too large" placeholder if it exceeds the limit.
+    fn checked_name(&self, name: &str) -> String {
+        let len = name.len() as u64;
+        if self.limits.allows(len) {
+            name.to_string()
+        } else {
+            BlobLoadLimits::too_large_text(len)
+        }
+    }
 }
 
 fn build_attr_info<B>(
     metadata: &TribleSet,
     name_cache: &BlobCache<B, LongString, View<str>>,
+    guard: &BlobGuard<'_>,
 ) -> HashMap<Id, AttrInfo>
 where
     B: BlobStoreGet,
@@ -119,8 +196,12 @@ where
         (attr: Id, name_handle: Inline<Handle<LongString>>),
         pattern!(metadata, [{ ?attr @ triblespace::core::metadata::name: ?name_handle }])
     ) {
+        if let Some(len) = guard.oversized(name_handle.raw) {
+            labels.insert(attr, BlobLoadLimits::too_large_text(len));
+            continue;
+        }
         if let Ok(name) = name_cache.get(name_handle) {
-            labels.insert(attr, name.as_ref().to_string());
+            labels.insert(attr, guard.checked_name(name.as_ref()));
         }
     }
     for (usage, attr, name_handle) in find!(
@@ -135,8 +216,12 @@ where
         if labels.contains_key(&attr) {
             continue;
         }
+        if let Some(len) = guard.oversized(name_handle.raw) {
+            labels.insert(attr, BlobLoadLimits::too_large_text(len));
+            continue;
+        }
         if let Ok(name) = name_cache.get(name_handle) {
-            labels.insert(attr, name.as_ref().to_string());
+            labels.insert(attr, guard.checked_name(name.as_ref()));
         }
     }
 
@@ -145,8 +230,15 @@ where
         (attr: Id, schema: Id),
         pattern!(metadata, [{ ?attr @ triblespace::core::metadata::value_encoding: ?schema }])
     ) {
-        schema_by_attr.insert(attr, schema);
+        schema_by_attr.entry(attr).or_insert(schema);
     }
+    // Tribles carry no declaration order, so rank by id: fixed for a
+    // given schema, whatever order the metadata was built in.
+    let mut declared_attrs: Vec<Id> = schema_by_attr.keys().copied().collect();
+    declared_attrs.sort_by(|a, b| {
+        let (a, b): (&[u8], &[u8]) = (a.as_ref(), b.as_ref());
+        a.cmp(b)
+    });
 
     let mut formatter_by_schema = HashMap::<Id, Inline<Handle<WasmCode>>>::new();
     for (schema, formatter) in find!(
@@ -157,17 +249,20 @@ where
     }
 
     let mut out = HashMap::<Id, AttrInfo>::new();
-    for (attr, schema) in schema_by_attr {
-        let label = labels
-            .remove(&attr)
-            .unwrap_or_else(|| format!("attr:{}", id_short(attr)));
+    for (declared, attr) in declared_attrs.into_iter().enumerate() {
+        let schema = schema_by_attr[&attr];
+        let name = labels.remove(&attr);
+        let named = name.is_some();
+        let label = name.unwrap_or_else(|| format!("attr:{}", id_short(attr)));
         let formatter = formatter_by_schema.get(&schema).copied();
         out.insert(
             attr,
             AttrInfo {
                 label,
+                named,
                 schema: Some(schema),
                 formatter,
+                declared: Some(declared),
             },
         );
     }
@@ -175,8 +270,10 @@ where
     for (attr, label) in labels {
         out.entry(attr).or_insert(AttrInfo {
             label,
+            named: true,
             schema: None,
             formatter: None,
+            declared: None,
         });
     }
 
@@ -186,21 +283,43 @@ where
 fn build_entity_graph<B>(
     data: &TribleSet,
     metadata: &TribleSet,
+    hidden_attrs: &HashSet<Id>,
     name_cache: &BlobCache<B, LongString, View<str>>,
     formatter_cache: &BlobCache<B, WasmCode, WasmValueFormatter>,
+    guard: &BlobGuard<'_>,
+    row_order: RowOrder,
+    hide_unnamed: bool,
+    sample: Option<usize>,
 ) -> EntityGraph
 where
     B: BlobStoreGet,
 {
-    let attr_info = build_attr_info(metadata, name_cache);
+    let attr_info = build_attr_info(metadata, name_cache, guard);
     let limits = WasmLimits::default();
+    let sampled = sample.map(|count| {
+        let mut subjects: Vec<Id> = distinct_entities(data).into_iter().collect();
+        subjects.sort_by(|a, b| AsRef::<[u8]>::as_ref(a).cmp(b.as_ref()));
+        subjects.truncate(count);
+        subjects.into_iter().collect::<HashSet<Id>>()
+    });
 
     let schema_genid = <GenId as triblespace::core::metadata::MetaDescribe>::id();
     let mut entity_ids = HashSet::<Id>::new();
+    let mut attr_ids = HashSet::<Id>::new();
     let mut tribles = Vec::<(Id, Id, [u8; 32])>::new();
 
     for (e, a, v) in find!((e: Id, a: Id, v: Inline<UnknownInline>), data.pattern(e, a, v)) {
+        if sampled.as_ref().is_some_and(|sampled| !sampled.contains(&e)) {
+            continue;
+        }
         entity_ids.insert(e);
+        attr_ids.insert(a);
+        if hidden_attrs.contains(&a) {
+            continue;
+        }
+        if hide_unnamed && !attr_info.get(&a).is_some_and(|info| info.named) {
+            continue;
+        }
         if let Some(info) = attr_info.get(&a) {
             if info.schema == Some(schema_genid) {
                 if let Some(target) = try_decode_genid(&v.raw) {
@@ -235,6 +354,8 @@ where
             .map(|info| info.label.clone())
             .unwrap_or_else(|| format!("attr:{}", id_short(attr)));
 
+        let mut size_note = None;
+        let value_blob = guard.oversized(raw);
         let (value_text, target, hatched) = match info {
             Some(info) if info.schema == Some(schema_genid) => {
                 if let Some(target) = try_decode_genid(&raw) {
@@ -243,8 +364,18 @@ where
                     (format!("id:0x{}", hex_prefix(raw, 6)), None, false)
                 }
             }
+            // A handle to an oversized blob is never passed on for display.
+            _ if value_blob.is_some() => {
+                size_note = value_blob.map(BlobLoadLimits::too_large_text);
+                (format!("0x{}", hex_prefix(raw, 6)), None, true)
+            }
             Some(info) => match info
                 .formatter
+                .filter(|handle| {
+                    let oversized = guard.oversized(handle.raw);
+                    size_note = oversized.map(BlobLoadLimits::too_large_text);
+                    oversized.is_none()
+                })
                 .and_then(|handle| formatter_cache.get(handle).ok())
             {
                 Some(formatter) => match formatter.format_value_with_limits(&raw, limits) {
@@ -262,6 +393,7 @@ where
             value: value_text,
             target,
             hatched,
+            size_note,
         });
     }
 
@@ -283,12 +415,20 @@ where
             .unwrap_or_else(|| format!("id:{}", id_short(id)));
 
         let mut rows = raw_rows[idx].clone();
-        rows.sort_by(|a, b| {
-            a.attr
-                .cmp(&b.attr)
-                .then_with(|| a.hatched.cmp(&b.hatched))
-                .then_with(|| a.value.cmp(&b.value))
-        });
+        match row_order {
+            RowOrder::Alphabetical => rows.sort_by(|a, b| {
+                a.attr
+                    .cmp(&b.attr)
+                    .then_with(|| a.hatched.cmp(&b.hatched))
+                    .then_with(|| a.value.cmp(&b.value))
+            }),
+            // Stable sort: rows of one attribute keep their data order.
+            RowOrder::Schema => rows.sort_by_key(|row| {
+                let declared = attr_info.get(&row.attr_id).and_then(|info| info.declared);
+                (declared.is_none(), declared, row.attr.clone())
+            }),
+            RowOrder::InsertionStable => {}
+        }
 
         nodes.push(EntityNode { id, title, rows });
     }
@@ -311,17 +451,56 @@ where
         }
     }
 
+    let mut attributes: Vec<(Id, String)> = attr_ids
+        .into_iter()
+        .map(|attr| {
+            let label = attr_info
+                .get(&attr)
+                .map(|info| info.label.clone())
+                .unwrap_or_else(|| format!("attr:{}", id_short(attr)));
+            (attr, label)
+        })
+        .collect();
+    attributes.sort_by(|a, b| {
+        let a_raw: &[u8] = a.0.as_ref();
+        let b_raw: &[u8] = b.0.as_ref();
+        a.1.cmp(&b.1).then_with(|| a_raw.cmp(b_raw))
+    });
+
+    let fingerprint = graph_fingerprint(&nodes, &edges);
     EntityGraph {
         nodes,
         edges,
         id_to_index,
+        attributes,
+        fingerprint,
     }
 }
 
+fn graph_fingerprint(nodes: &[EntityNode], edges: &[EntityEdge]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for node in nodes {
+        node.id.hash(&mut hasher);
+        node.title.hash(&mut hasher);
+        for row in &node.rows {
+            (&row.attr, &row.value, row.hatched).hash(&mut hasher);
+        }
+    }
+    for edge in edges {
+        (edge.from_entity, edge.from_row, edge.to_entity).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 #[derive(Clone)]
 struct EntityGraphCache {
     data_fingerprint: TribleSetFingerprint,
     metadata_fingerprint: TribleSetFingerprint,
+    hidden_attrs: HashSet<Id>,
+    blob_limits: BlobLoadLimits,
+    row_order: RowOrder,
+    hide_unnamed: bool,
+    sample: Option<usize>,
     graph: Option<Arc<EntityGraph>>,
 }
 
@@ -330,38 +509,64 @@ impl Default for EntityGraphCache {
         Self {
             data_fingerprint: TribleSetFingerprint::EMPTY,
             metadata_fingerprint: TribleSetFingerprint::EMPTY,
+            hidden_attrs: HashSet::new(),
+            blob_limits: BlobLoadLimits::default(),
+            row_order: RowOrder::Alphabetical,
+            hide_unnamed: false,
+            sample: None,
             graph: None,
         }
     }
 }
 
-fn cached_entity_graph<B>(
+pub(super) fn cached_entity_graph<B>(
     ui: &mut Ui,
     cache_id: egui::Id,
     data: &TribleSet,
     metadata: &TribleSet,
+    hidden_attrs: &HashSet<Id>,
     name_cache: &BlobCache<B, LongString, View<str>>,
     formatter_cache: &BlobCache<B, WasmCode, WasmValueFormatter>,
+    guard: &BlobGuard<'_>,
+    row_order: RowOrder,
+    hide_unnamed: bool,
+    sample: Option<usize>,
 ) -> Arc<EntityGraph>
 where
     B: BlobStoreGet,
 {
     let data_fingerprint = data.fingerprint();
     let metadata_fingerprint = metadata.fingerprint();
+    let blob_limits = guard.limits;
     ui.data_mut(|memory| {
         let cache = memory.get_temp_mut_or_default::<EntityGraphCache>(cache_id);
         let needs_rebuild = cache.graph.is_none()
             || cache.data_fingerprint != data_fingerprint
-            || cache.metadata_fingerprint != metadata_fingerprint;
+            || cache.metadata_fingerprint != metadata_fingerprint
+            || cache.hidden_attrs != *hidden_attrs
+            || cache.blob_limits != blob_limits
+            || cache.row_order != row_order
+            || cache.hide_unnamed != hide_unnamed
+            || cache.sample != sample;
         if needs_rebuild {
             cache.graph = Some(Arc::new(build_entity_graph(
                 data,
                 metadata,
+                hidden_attrs,
                 name_cache,
                 formatter_cache,
+                guard,
+                row_order,
+                hide_unnamed,
+                sample,
             )));
             cache.data_fingerprint = data_fingerprint;
             cache.metadata_fingerprint = metadata_fingerprint;
+            cache.hidden_attrs = hidden_attrs.clone();
+            cache.blob_limits = blob_limits;
+            cache.row_order = row_order;
+            cache.hide_unnamed = hide_unnamed;
+            cache.sample = sample;
         }
         cache
             .graph
@@ -371,6 +576,35 @@ where
     })
 }
 
+/// `graph` with [`EntityInspectorWidget::value_transform`] applied,
+/// cached until the graph or the closure type changes.
+fn transformed_graph(
+    ui: &mut Ui,
+    cache_id: egui::Id,
+    graph: Arc<EntityGraph>,
+    transform: &ValueTransform,
+) -> Arc<EntityGraph> {
+    type Cached = (Arc<EntityGraph>, TypeId, Arc<EntityGraph>);
+    let transform_id = cache_id.with("value_transform");
+    if let Some((_, _, cached)) = ui
+        .data(|memory| memory.get_temp::<Cached>(transform_id))
+        .filter(|(source, key, _)| Arc::ptr_eq(source, &graph) && *key == transform.key)
+    {
+        return cached;
+    }
+    let mut transformed = EntityGraph::clone(&graph);
+    for row in transformed.nodes.iter_mut().flat_map(|node| node.rows.iter_mut()) {
+        if !row.hatched && row.target.is_none() {
+            row.value = (transform.apply)(&row.value);
+        }
+    }
+    let transformed = Arc::new(transformed);
+    ui.data_mut(|memory| {
+        memory.insert_temp::<Cached>(transform_id, (graph, transform.key, transformed.clone()))
+    });
+    transformed
+}
+
 #[derive(Clone, Debug)]
 struct ComponentLayout {
     column_free: Vec<Vec<(f32, f32)>>,
@@ -388,6 +622,35 @@ struct GraphLayout {
     components: Vec<ComponentLayout>,
     node_component: Vec<usize>,
     node_column: Vec<usize>,
+    /// Top of the divider above the unconnected-entity grid, if any.
+    singleton_divider_y: Option<f32>,
+    singleton_count: usize,
+    /// Per node, the top of each row relative to the first plus the end of
+    /// the last; empty when values don't wrap and rows are uniform.
+    row_offsets: Vec<Vec<f32>>,
+    /// Canvas height plain masonry would have produced, when the columns
+    /// were balanced instead.
+    unbalanced_height: Option<f32>,
+    /// Header rect and label of each column when grouped by an attribute.
+    group_headers: Vec<(Rect, String)>,
+}
+
+impl GraphLayout {
+    /// Top of `row` relative to the node's first row, and the row height.
+    fn row_span(&self, node: usize, row: usize) -> (f32, f32) {
+        match self.row_offsets.get(node) {
+            Some(offsets) if row + 1 < offsets.len() => {
+                (offsets[row], offsets[row + 1] - offsets[row])
+            }
+            _ => (row as f32 * self.text_row_height, self.text_row_height),
+        }
+    }
+}
+
+/// Width of the key column in an entity table whose inner area is
+/// `inner_width` wide. Values start 8px right of it.
+fn key_column_width(inner_width: f32) -> f32 {
+    (inner_width * 0.42).clamp(56.0, 120.0)
 }
 
 fn build_adjacency(graph: &EntityGraph) -> Vec<Vec<usize>> {
@@ -431,12 +694,126 @@ fn connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
     components
 }
 
+/// Font size assumed when a style resolves to a non-positive size.
+const FALLBACK_FONT_SIZE: f32 = 12.0;
+/// Row height per point of font size for the fallback metrics.
+const FALLBACK_LINE_HEIGHT: f32 = 1.25;
+
+/// Row height of `font`, or a fixed estimate from its size when the fonts
+/// report none (e.g. a test context without the industrial fonts), so the
+/// layout never degenerates.
+fn font_row_height(ui: &Ui, font: &egui::FontId) -> f32 {
+    let measured = ui.fonts_mut(|fonts| fonts.row_height(font));
+    if measured.is_finite() && measured > 0.0 {
+        return measured;
+    }
+    let size = if font.size > 0.0 { font.size } else { FALLBACK_FONT_SIZE };
+    size * FALLBACK_LINE_HEIGHT
+}
+
+/// Layout knobs set through [`EntityInspectorWidget`]'s builder.
+#[derive(Clone, Copy, Debug, Default)]
+struct LayoutOptions {
+    /// Column count, or 0 to fit as many as the width allows.
+    columns: usize,
+    isolate_singletons: bool,
+    wrap_values: bool,
+    balance_columns: bool,
+    /// Attribute whose value picks each entity's column.
+    group_by: Option<Id>,
+}
+
+/// Label for entities without a value for the grouping attribute.
+const UNGROUPED_LABEL: &str = "(none)";
+
+/// Groups entities by their (first) value of `attr`: the group labels,
+/// sorted with [`UNGROUPED_LABEL`] last, and each node's group index.
+fn entity_groups(graph: &EntityGraph, attr: Id) -> (Vec<String>, Vec<usize>) {
+    let values: Vec<Option<&str>> = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            node.rows
+                .iter()
+                .find(|row| row.attr_id == attr && !row.hatched)
+                .map(|row| row.value.as_str())
+        })
+        .collect();
+    let mut labels: Vec<&str> = values.iter().flatten().copied().collect();
+    labels.sort_unstable();
+    labels.dedup();
+    let group_of = values
+        .iter()
+        .map(|value| match value {
+            Some(value) => labels.binary_search(value).expect("label collected above"),
+            None => labels.len(),
+        })
+        .collect();
+    let mut labels: Vec<String> = labels.into_iter().map(str::to_owned).collect();
+    if values.iter().any(Option::is_none) {
+        labels.push(UNGROUPED_LABEL.to_owned());
+    }
+    (labels, group_of)
+}
+
+/// Index of the shortest column, leftmost on ties.
+fn shortest_column(column_bottoms: &[f32]) -> usize {
+    column_bottoms
+        .iter()
+        .enumerate()
+        .min_by(|(a_idx, a_y), (b_idx, b_y)| {
+            a_y.partial_cmp(b_y)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a_idx.cmp(b_idx))
+        })
+        .map_or(0, |(col, _)| col)
+}
+
+/// Assigns each of `tiles` to a column, growing `column_bottoms` as it
+/// goes. Without `balance` tiles are taken in order, each into the
+/// currently-shortest column (masonry); with it the tallest go first
+/// (longest-processing-time), which evens out the column heights.
+fn assign_columns(
+    tiles: &[usize],
+    tile_heights: &[f32],
+    column_bottoms: &mut [f32],
+    row_gap: f32,
+    balance: bool,
+) -> Vec<usize> {
+    let mut queue: Vec<usize> = (0..tiles.len()).collect();
+    if balance {
+        queue.sort_by(|&a, &b| tile_heights[tiles[b]].total_cmp(&tile_heights[tiles[a]]));
+    }
+    let mut columns = vec![0usize; tiles.len()];
+    for slot in queue {
+        let col = shortest_column(column_bottoms);
+        columns[slot] = col;
+        column_bottoms[col] += tile_heights[tiles[slot]] + row_gap;
+    }
+    columns
+}
+
 fn compute_graph_layout(
     ui: &Ui,
     graph: &EntityGraph,
-    forced_columns: usize,
     order: &[usize],
+    pinned: &[usize],
+    options: &LayoutOptions,
 ) -> GraphLayout {
+    let LayoutOptions {
+        columns: forced_columns,
+        isolate_singletons,
+        wrap_values,
+        balance_columns,
+        group_by,
+    } = *options;
+    // Grouping fixes every entity's column, so it overrides the column
+    // count, balancing and the unconnected grid.
+    let groups = group_by.map(|attr| entity_groups(graph, attr));
+    let (forced_columns, isolate_singletons, balance_columns) = match &groups {
+        Some((labels, _)) => (labels.len(), false, false),
+        None => (forced_columns, isolate_singletons, balance_columns),
+    };
     let column_gap = 48.0;
     let outer_x_pad = column_gap;
     let min_tile_width = 160.0;
@@ -471,17 +848,59 @@ fn compute_graph_layout(
     let tile_padding = 8.0;
     let title_font = TextStyle::Monospace.resolve(ui.style());
     let row_font = TextStyle::Small.resolve(ui.style());
-    let header_height = ui.fonts_mut(|fonts| fonts.row_height(&title_font)).ceil() + 6.0;
-    let text_row_height = ui.fonts_mut(|fonts| fonts.row_height(&row_font)).ceil() + 4.0;
+    let header_height = font_row_height(ui, &title_font).ceil() + 6.0;
+    let text_row_height = font_row_height(ui, &row_font).ceil() + 4.0;
 
     let mut tile_heights = vec![0.0f32; graph.nodes.len()];
+    let mut row_offsets = Vec::new();
+    if wrap_values {
+        let inner_width = tile_width - tile_padding * 2.0;
+        let value_width = (inner_width - key_column_width(inner_width) - 8.0).max(1.0);
+        row_offsets = graph
+            .nodes
+            .iter()
+            .map(|node| {
+                let mut offsets = Vec::with_capacity(node.rows.len() + 1);
+                let mut y = 0.0f32;
+                for row in &node.rows {
+                    offsets.push(y);
+                    let height = if row.hatched {
+                        text_row_height
+                    } else {
+                        let text_height = ui.fonts_mut(|fonts| {
+                            fonts
+                                .layout(
+                                    row.value.clone(),
+                                    row_font.clone(),
+                                    egui::Color32::PLACEHOLDER,
+                                    value_width,
+                                )
+                                .size()
+                                .y
+                        });
+                        (text_height.ceil() + 4.0).max(text_row_height)
+                    };
+                    y += height;
+                }
+                offsets.push(y);
+                offsets
+            })
+            .collect();
+    }
     for (idx, node) in graph.nodes.iter().enumerate() {
-        let rows = node.rows.len().max(1);
-        tile_heights[idx] = tile_padding * 2.0 + header_height + text_row_height * rows as f32;
+        let rows_height = match row_offsets.get(idx) {
+            Some(offsets) if !node.rows.is_empty() => offsets[node.rows.len()],
+            _ => text_row_height * node.rows.len().max(1) as f32,
+        };
+        tile_heights[idx] = tile_padding * 2.0 + header_height + rows_height;
     }
 
     let row_gap = 24.0;
-    let top_pad = row_gap;
+    let top_pad = if groups.is_some() {
+        row_gap + header_height
+    } else {
+        row_gap
+    };
     let bottom_pad = row_gap;
     let clearance = 4.0;
 
@@ -493,20 +912,47 @@ fn compute_graph_layout(
     let mut column_bottoms = vec![top_pad; column_count];
     let mut column_nodes = vec![Vec::<usize>::new(); column_count];
 
-    // Baseline placement: pack lexicographically by entity id, with a simple "masonry" heuristic
-    // (always place the next tile in the currently-shortest column).
-    for &node_idx in order {
-        let (col, y) = column_bottoms
-            .iter()
-            .copied()
-            .enumerate()
-            .min_by(|(a_idx, a_y), (b_idx, b_y)| {
-                a_y.partial_cmp(b_y)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-                    .then_with(|| a_idx.cmp(b_idx))
-            })
-            .unwrap_or((0, top_pad));
+    let mut is_singleton = vec![false; node_count];
+    if isolate_singletons {
+        for (idx, neighbours) in build_adjacency(graph).iter().enumerate() {
+            is_singleton[idx] = neighbours.is_empty();
+        }
+    }
 
+    // Pinned entities stack at the top of the first column, then the rest are spread over the
+    // columns by `assign_columns` and stacked within each column in `order`.
+    let tiles: Vec<usize> = order
+        .iter()
+        .copied()
+        .filter(|&idx| !is_singleton[idx] && !pinned.contains(&idx))
+        .collect();
+    let mut pinned_bottoms = column_bottoms.clone();
+    for &idx in pinned {
+        pinned_bottoms[0] += tile_heights[idx] + row_gap;
+    }
+    // The height plain masonry would reach, to report what balancing gained.
+    let unbalanced_bottom = balance_columns.then(|| {
+        let mut bottoms = pinned_bottoms.clone();
+        assign_columns(&tiles, &tile_heights, &mut bottoms, row_gap, false);
+        bottoms.into_iter().fold(top_pad, f32::max)
+    });
+    let tile_columns = match &groups {
+        Some((_, group_of)) => tiles.iter().map(|&idx| group_of[idx]).collect(),
+        None => assign_columns(
+            &tiles,
+            &tile_heights,
+            &mut pinned_bottoms,
+            row_gap,
+            balance_columns,
+        ),
+    };
+    let pinned_column = |idx: usize| groups.as_ref().map_or(0, |(_, group_of)| group_of[idx]);
+    let placed = pinned
+        .iter()
+        .map(|&idx| (idx, pinned_column(idx)))
+        .chain(tiles.iter().copied().zip(tile_columns));
+    for (node_idx, col) in placed {
+        let y = column_bottoms[col];
         let x = outer_x_pad + col as f32 * (tile_width + column_gap);
         let rect = Rect::from_min_size(pos2(x, y), vec2(tile_width, tile_heights[node_idx]));
         tile_rects[node_idx] = rect;
@@ -521,8 +967,49 @@ fn compute_graph_layout(
         }
     }
 
-    let content_height = column_bottoms.into_iter().fold(top_pad, f32::max);
+    let mut content_height = column_bottoms.into_iter().fold(top_pad, f32::max);
+    let main_height = content_height;
+
+    // Unconnected entities go into a compact grid below a labeled divider,
+    // row by row in `order`, aligned to the main columns.
+    let singletons: Vec<usize> = order
+        .iter()
+        .copied()
+        .filter(|&idx| is_singleton[idx] && !pinned.contains(&idx))
+        .collect();
+    let mut singleton_divider_y = None;
+    if !singletons.is_empty() {
+        let divider_y = if content_height > top_pad {
+            content_height + row_gap
+        } else {
+            top_pad
+        };
+        singleton_divider_y = Some(divider_y);
+        let grid_gap = row_gap * 0.5;
+        let mut y = divider_y + header_height + grid_gap;
+        for grid_row in singletons.chunks(column_count) {
+            let mut row_height = 0.0f32;
+            for (col, &node_idx) in grid_row.iter().enumerate() {
+                let x = outer_x_pad + col as f32 * (tile_width + column_gap);
+                let rect =
+                    Rect::from_min_size(pos2(x, y), vec2(tile_width, tile_heights[node_idx]));
+                tile_rects[node_idx] = rect;
+                node_column[node_idx] = col;
+                column_nodes[col].push(node_idx);
+                row_height = row_height.max(tile_heights[node_idx]);
+            }
+            y += row_height + grid_gap;
+        }
+        content_height = y - grid_gap;
+    }
+
     let canvas_height = (content_height + bottom_pad).max(top_pad + bottom_pad);
+    let unbalanced_height = unbalanced_bottom.map(|bottom| {
+        // Undo the trailing gap like the real columns, then shift by the
+        // difference in where the singleton grid would have started.
+        let bottom = if bottom > top_pad { bottom - row_gap } else { bottom };
+        canvas_height + (bottom - main_height)
+    });
 
     let canvas_width = outer_x_pad * 2.0
         + tile_width * column_count as f32
@@ -553,6 +1040,19 @@ fn compute_graph_layout(
 
     let component_layout = ComponentLayout { column_free };
 
+    let group_headers = match groups {
+        Some((labels, _)) => labels
+            .into_iter()
+            .enumerate()
+            .map(|(col, label)| {
+                let x = outer_x_pad + col as f32 * (tile_width + column_gap);
+                let min = pos2(x, row_gap * 0.5);
+                (Rect::from_min_size(min, vec2(tile_width, header_height)), label)
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
     GraphLayout {
         canvas_size: vec2(canvas_width, canvas_height),
         column_count,
@@ -564,6 +1064,11 @@ fn compute_graph_layout(
         components: vec![component_layout],
         node_component,
         node_column,
+        singleton_divider_y,
+        singleton_count: singletons.len(),
+        row_offsets,
+        unbalanced_height,
+        group_headers,
     }
 }
 
@@ -576,6 +1081,10 @@ pub struct EntityInspectorStats {
     pub canvas_width: f32,
     pub canvas_height: f32,
     pub tile_coverage: f32,
+    /// How much [`tile_coverage`](Self::tile_coverage) rose over plain
+    /// masonry through [`EntityInspectorWidget::balance_columns`]; 0 when
+    /// balancing is off.
+    pub balance_coverage_gain: f32,
     pub total_edge_len: f32,
     pub avg_edge_len: f32,
     pub max_edge_len: f32,
@@ -589,6 +1098,58 @@ pub struct EntityInspectorStats {
     pub linear_avg: f32,
 }
 
+/// Order of the attribute rows within each entity table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RowOrder {
+    /// By attribute name, then value.
+    #[default]
+    Alphabetical,
+    /// Attributes with a value encoding in the metadata first, in one
+    /// fixed order (by attribute id) so every entity of a type lists its
+    /// fields the same way; `TribleSet`s keep no declaration order to
+    /// follow. Undeclared attributes follow alphabetically.
+    Schema,
+    /// In the order the data set yields its tribles. `TribleSet`s are
+    /// ordered by content, so this is stable across frames and checkouts.
+    InsertionStable,
+}
+
+/// How entity tiles are framed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TileStyle {
+    /// Sharp corners and no shadow, matching the industrial theme.
+    #[default]
+    Square,
+    /// Slightly rounded corners over a soft drop shadow.
+    Rounded,
+}
+
+/// Corner radius of [`TileStyle::Rounded`] tiles. Edges attach to the tile
+/// sides at least `tile_padding` away from the corners, so any radius up
+/// to the padding leaves their anchors on the straight part of the border.
+const ROUNDED_TILE_RADIUS: u8 = 6;
+
+impl TileStyle {
+    fn corner_radius(self) -> egui::CornerRadius {
+        match self {
+            TileStyle::Square => egui::CornerRadius::ZERO,
+            TileStyle::Rounded => egui::CornerRadius::same(ROUNDED_TILE_RADIUS),
+        }
+    }
+
+    fn shadow(self) -> Option<egui::epaint::Shadow> {
+        match self {
+            TileStyle::Square => None,
+            TileStyle::Rounded => Some(egui::epaint::Shadow {
+                offset: [0, 2],
+                blur: 8,
+                spread: 0,
+                color: egui::Color32::from_black_alpha(48),
+            }),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EntityOrder {
     Id,
@@ -599,7 +1160,12 @@ pub enum EntityOrder {
 pub struct EntityInspectorResponse {
     pub response: egui::Response,
     pub stats: EntityInspectorStats,
+    /// True when the primary selection or, with
+    /// [`EntityInspectorWidget::multi_select`], the selection set changed.
     pub selection_changed: bool,
+    /// Every selected entity: the multi-selection set, or just the primary
+    /// selection without [`EntityInspectorWidget::multi_select`].
+    pub selected: Vec<Id>,
 }
 
 #[must_use = "Use `EntityInspectorWidget::show(ui)` to render this widget."]
@@ -612,9 +1178,31 @@ where
     name_cache: &'a BlobCache<B, LongString, View<str>>,
     formatter_cache: &'a BlobCache<B, WasmCode, WasmValueFormatter>,
     selection: &'a mut Id,
+    multi_selection: Option<&'a mut Vec<Id>>,
     columns: usize,
     order: EntityOrder,
+    row_order: RowOrder,
+    tile_style: TileStyle,
+    auto_scroll: bool,
+    on_scroll_to: Option<Box<dyn FnMut(Id) + 'a>>,
+    on_edge_click: Option<Box<dyn FnMut(EntityEdgeRef, egui::Modifiers) + 'a>>,
+    edge_palette: Vec<egui::Color32>,
     cache_id: Option<egui::Id>,
+    isolate_singletons: bool,
+    wrap_values: bool,
+    balance_columns: bool,
+    group_by: Option<Id>,
+    hide_unnamed: bool,
+    max_nodes: usize,
+    thumbnails: bool,
+    value_transform: Option<ValueTransform>,
+    blob_guard: BlobGuard<'a>,
+}
+
+/// A display hook for formatted values, identified by its closure type.
+struct ValueTransform {
+    key: TypeId,
+    apply: Box<dyn Fn(&str) -> String>,
 }
 
 impl<'a, B> EntityInspectorWidget<'a, B>
@@ -638,9 +1226,25 @@ where
             name_cache,
             formatter_cache,
             selection,
+            multi_selection: None,
             columns: 0,
             order,
+            row_order: RowOrder::Alphabetical,
+            tile_style: TileStyle::Square,
+            auto_scroll: true,
+            on_scroll_to: None,
+            on_edge_click: None,
+            edge_palette: default_edge_palette(),
             cache_id: None,
+            isolate_singletons: false,
+            wrap_values: false,
+            balance_columns: false,
+            group_by: None,
+            hide_unnamed: false,
+            max_nodes: DEFAULT_MAX_NODES,
+            thumbnails: false,
+            value_transform: None,
+            blob_guard: BlobGuard::default(),
         }
     }
 
@@ -654,12 +1258,181 @@ where
         self
     }
 
+    /// Order of the attribute rows in each entity table. Defaults to
+    /// [`RowOrder::Alphabetical`].
+    pub fn row_order(mut self, row_order: RowOrder) -> Self {
+        self.row_order = row_order;
+        self
+    }
+
+    /// Let ctrl+click (cmd+click on macOS) add tiles to, or remove them
+    /// from, a selection set kept in `selected`, e.g. to compare entities.
+    /// All selected tiles are highlighted; the primary selection follows
+    /// the last tile added. A plain click or followed reference resets the
+    /// set to that one entity.
+    pub fn multi_select(mut self, selected: &'a mut Vec<Id>) -> Self {
+        self.multi_selection = Some(selected);
+        self
+    }
+
+    /// Frame entity tiles with sharp corners (the default) or rounded
+    /// corners and a drop shadow.
+    pub fn tile_style(mut self, tile_style: TileStyle) -> Self {
+        self.tile_style = tile_style;
+        self
+    }
+
+    /// Colors for the reference edges, one per attribute. Attributes get
+    /// distinct colors while there are enough; beyond that the palette
+    /// repeats in lighter and darker variants. Defaults to seven RAL
+    /// signal colors.
+    pub fn edge_palette(mut self, palette: Vec<egui::Color32>) -> Self {
+        self.edge_palette = palette;
+        self
+    }
+
+    /// Scroll the surrounding scroll area to the target tile when a
+    /// reference is followed or the breadcrumbs go back. Defaults to
+    /// `true`; turn it off when the inspector is embedded in a larger
+    /// scrolling layout. The selection updates either way.
+    pub fn auto_scroll(mut self, auto_scroll: bool) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+
+    /// Called with the target entity whenever the inspector navigates to
+    /// it (the moments [`auto_scroll`](Self::auto_scroll) would scroll),
+    /// so the host can navigate its own way.
+    pub fn on_scroll_to(mut self, on_scroll_to: impl FnMut(Id) + 'a) -> Self {
+        self.on_scroll_to = Some(Box::new(on_scroll_to));
+        self
+    }
+
+    /// Called instead of the default edge behaviour when a reference edge
+    /// is clicked, with the edge and the modifiers held, e.g. to open a
+    /// detail panel for the relationship. Without it a click follows the
+    /// edge to its target, or to its source with shift held.
+    pub fn on_edge_click(
+        mut self,
+        on_edge_click: impl FnMut(EntityEdgeRef, egui::Modifiers) + 'a,
+    ) -> Self {
+        self.on_edge_click = Some(Box::new(on_edge_click));
+        self
+    }
+
     pub fn cache_id(mut self, cache_id: egui::Id) -> Self {
         self.cache_id = Some(cache_id);
         self
     }
 
-    pub fn show(self, ui: &mut Ui) -> EntityInspectorResponse {
+    /// Lay out entities without any edges in a compact grid at the bottom
+    /// of the canvas, below a labeled divider, so the main columns only
+    /// hold connected structure. They still count towards
+    /// [`EntityInspectorStats::nodes`].
+    pub fn isolate_singletons(mut self, isolate: bool) -> Self {
+        self.isolate_singletons = isolate;
+        self
+    }
+
+    /// Wrap long attribute values within the value column instead of
+    /// clipping them at the tile edge. Rows grow to fit, so tiles get
+    /// taller.
+    pub fn wrap_values(mut self, wrap: bool) -> Self {
+        self.wrap_values = wrap;
+        self
+    }
+
+    /// Rewrite every formatted value before it is shown, e.g. to add
+    /// thousands separators or cut precision. Applies to values produced by
+    /// a value formatter, not to entity references or unloaded blobs.
+    ///
+    /// Transformed values are cached per graph and per closure type, so
+    /// the closure should not depend on state that changes between frames.
+    pub fn value_transform(mut self, transform: impl Fn(&str) -> String + 'static) -> Self {
+        self.value_transform = Some(ValueTransform {
+            key: transform.type_id(),
+            apply: Box::new(transform),
+        });
+        self
+    }
+
+    /// Spread tiles over the columns tallest-first instead of in order,
+    /// stacking each column's tiles in order again afterwards. Evens out
+    /// column heights when tile heights vary a lot, at the cost of
+    /// neighbours in the order drifting further apart; the gain shows up
+    /// as [`EntityInspectorStats::balance_coverage_gain`].
+    pub fn balance_columns(mut self, balance: bool) -> Self {
+        self.balance_columns = balance;
+        self
+    }
+
+    /// Lay entities out as swimlanes: one labeled column per value of
+    /// `attr`, sorted by value, with entities lacking it in a final
+    /// "(none)" column. Within a column tiles stack in the usual order and
+    /// edges route between columns as before. Overrides
+    /// [`columns`](Self::columns), [`balance_columns`](Self::balance_columns)
+    /// and [`isolate_singletons`](Self::isolate_singletons); `None` (the
+    /// default) keeps the ordered layout.
+    pub fn group_by(mut self, attr: Option<Id>) -> Self {
+        self.group_by = attr;
+        self
+    }
+
+    /// Drop rows (and the edges they start) of attributes the metadata
+    /// doesn't name, instead of labelling them `attr:` plus a short id,
+    /// e.g. for screenshots. Off by default.
+    pub fn hide_unnamed(mut self, hide: bool) -> Self {
+        self.hide_unnamed = hide;
+        self
+    }
+
+    /// Above this many entities, show a warning with "Show first N" and
+    /// "Show anyway" buttons instead of building and laying out the
+    /// canvas, so opening a huge dataset by accident doesn't freeze the
+    /// notebook. The first choice inspects only the first `max_nodes`
+    /// entities by id. Defaults to [`DEFAULT_MAX_NODES`]; `usize::MAX`
+    /// disables the guard.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Show a gallery of small thumbnails above the canvas, one per
+    /// connected component of two or more entities, sketching its shape.
+    /// Clicking a thumbnail selects the component's first entity and
+    /// scrolls to it.
+    pub fn thumbnails(mut self, thumbnails: bool) -> Self {
+        self.thumbnails = thumbnails;
+        self
+    }
+
+    /// Check blob sizes in `reader` against `limits` before loading
+    /// attribute names and value formatters. Oversized names show a "value
+    /// too large" placeholder; values whose formatter is oversized, and
+    /// values that are handles to oversized blobs, are hatched with a size
+    /// note. Without a reader, the default [`BlobLoadLimits`] still cap
+    /// the names shown, but blobs are only checked once loaded.
+    pub fn blob_limits<R>(mut self, limits: BlobLoadLimits, reader: &'a R) -> Self
+    where
+        R: BlobStoreMeta<Blake3>,
+    {
+        // Metadata lookups only use the hash; the encoding is irrelevant.
+        let blob_len = move |hash: RawInline| {
+            let handle = Inline::<Handle<SimpleArchive>>::new(hash);
+            reader
+                .metadata(handle)
+                .ok()
+                .flatten()
+                .map(|meta| meta.length)
+        };
+        self.blob_guard = BlobGuard {
+            limits,
+            blob_len: Some(Box::new(blob_len)),
+        };
+        self
+    }
+
+    pub fn show(mut self, ui: &mut Ui) -> EntityInspectorResponse {
         let data_fingerprint = self.data.fingerprint();
         let metadata_fingerprint = self.metadata.fingerprint();
         let cache_id = self.cache_id.unwrap_or_else(|| {
@@ -668,7 +1441,40 @@ where
                 .with(data_fingerprint)
                 .with(metadata_fingerprint)
         });
-        let graph = {
+        // Checked before building the graph: the build (blob loads,
+        // formatter calls) is what freezes on a huge dataset.
+        let limit_id = cache_id.with("node_limit_choice");
+        let mut sample = None;
+        if self.data.len() > self.max_nodes {
+            let entities = distinct_entities(self.data).len();
+            if entities > self.max_nodes {
+                match ui.data(|memory| memory.get_temp::<NodeLimitChoice>(limit_id)) {
+                    Some(NodeLimitChoice::All) => {}
+                    Some(NodeLimitChoice::Sample) => sample = Some(self.max_nodes),
+                    None => {
+                        let (response, choice) = paint_node_limit(ui, entities, self.max_nodes);
+                        if let Some(choice) = choice {
+                            ui.data_mut(|memory| memory.insert_temp(limit_id, choice));
+                            ui.ctx().request_repaint();
+                        }
+                        let selected = match self.multi_selection.as_deref() {
+                            Some(selected) => selected.clone(),
+                            None => vec![*self.selection],
+                        };
+                        return EntityInspectorResponse {
+                            response,
+                            stats: EntityInspectorStats::default(),
+                            selection_changed: false,
+                            selected,
+                        };
+                    }
+                }
+            }
+        }
+        let hidden_id = cache_id.with("hidden_attrs");
+        let mut hidden_attrs: HashSet<Id> =
+            ui.data(|memory| memory.get_temp(hidden_id).unwrap_or_default());
+        let build_graph = |ui: &mut Ui, hidden_attrs: &HashSet<Id>| {
             #[cfg(feature = "telemetry")]
             let _graph_span = tracing::info_span!("entity_inspector_graph").entered();
             cached_entity_graph(
@@ -676,33 +1482,480 @@ where
                 cache_id,
                 self.data,
                 self.metadata,
+                hidden_attrs,
                 self.name_cache,
                 self.formatter_cache,
+                &self.blob_guard,
+                self.row_order,
+                self.hide_unnamed,
+                sample,
             )
         };
+        let mut graph = build_graph(ui, &hidden_attrs);
+        if attribute_visibility_panel(ui, &graph.attributes, &mut hidden_attrs) {
+            ui.data_mut(|memory| memory.insert_temp(hidden_id, hidden_attrs.clone()));
+            graph = build_graph(ui, &hidden_attrs);
+        }
+        if let Some(transform) = self.value_transform.as_ref() {
+            graph = transformed_graph(ui, cache_id, graph, transform);
+        }
+        if let Some(message) = empty_state_message(self.data, &graph, &hidden_attrs) {
+            let selected = match self.multi_selection.as_deref() {
+                Some(selected) => selected.clone(),
+                None => vec![*self.selection],
+            };
+            return EntityInspectorResponse {
+                response: paint_empty_state(ui, message),
+                stats: EntityInspectorStats {
+                    nodes: graph.nodes.len(),
+                    ..EntityInspectorStats::default()
+                },
+                selection_changed: false,
+                selected,
+            };
+        }
         let selection_before = *self.selection;
+        let multi_before = self.multi_selection.as_deref().cloned();
         if let Some(first) = graph.nodes.first().map(|node| node.id) {
             if !graph.id_to_index.contains_key(self.selection) {
                 *self.selection = first;
             }
         }
+        if let Some(selected) = self.multi_selection.as_deref_mut() {
+            selected.retain(|id| graph.id_to_index.contains_key(id));
+            if selected.is_empty() && graph.id_to_index.contains_key(self.selection) {
+                selected.push(*self.selection);
+            }
+        }
+
+        let trail_id = cache_id.with("navigation_trail");
+        let mut trail: Vec<Id> = ui.data(|memory| memory.get_temp(trail_id).unwrap_or_default());
+        trail.retain(|id| graph.id_to_index.contains_key(id));
+        let mut pending_scroll = None;
+        if let Some(depth) = navigation_breadcrumbs(ui, graph.as_ref(), &trail, *self.selection) {
+            let target = trail[depth];
+            trail.truncate(depth);
+            *self.selection = target;
+            if let Some(selected) = self.multi_selection.as_deref_mut() {
+                selected.clear();
+                selected.push(target);
+            }
+            pending_scroll = graph.id_to_index.get(&target).copied();
+        }
+        if self.thumbnails {
+            if let Some(target) = component_gallery(ui, cache_id, graph.as_ref()) {
+                let target_id = graph.nodes[target].id;
+                *self.selection = target_id;
+                if let Some(selected) = self.multi_selection.as_deref_mut() {
+                    selected.clear();
+                    selected.push(target_id);
+                }
+                pending_scroll = Some(target);
+            }
+        }
+        let pinned_id = cache_id.with("pinned");
+        let mut pinned: Vec<Id> = ui.data(|memory| memory.get_temp(pinned_id).unwrap_or_default());
+        let pinned_before = pinned.clone();
+        pinned.retain(|id| graph.id_to_index.contains_key(id));
+        let pinned_indices: Vec<usize> = pinned.iter().map(|id| graph.id_to_index[id]).collect();
+        let selection_before_paint = *self.selection;
         let (layout, routed_edges, stats) = {
             #[cfg(feature = "telemetry")]
             let _layout_span = tracing::info_span!("entity_inspector_layout").entered();
-            compute_inspector(ui, cache_id, graph.as_ref(), self.columns, self.order)
+            compute_inspector(
+                ui,
+                cache_id,
+                graph.as_ref(),
+                self.order,
+                &pinned_indices,
+                &LayoutOptions {
+                    columns: self.columns,
+                    isolate_singletons: self.isolate_singletons,
+                    wrap_values: self.wrap_values,
+                    balance_columns: self.balance_columns,
+                    group_by: self.group_by,
+                },
+            )
         };
         let response = {
             #[cfg(feature = "telemetry")]
             let _paint_span = tracing::info_span!("entity_inspector_paint").entered();
-            paint_entity_inspector(ui, graph.as_ref(), self.selection, &layout, &routed_edges)
+            let wide = layout.canvas_size.x > ui.available_width();
+            let paint = |ui: &mut Ui| {
+                paint_entity_inspector(
+                    ui,
+                    graph.as_ref(),
+                    self.selection,
+                    self.multi_selection.as_deref_mut(),
+                    &mut pinned,
+                    &layout,
+                    &routed_edges,
+                    pending_scroll,
+                    self.tile_style,
+                    self.auto_scroll,
+                    &self.edge_palette,
+                    self.on_edge_click.as_deref_mut(),
+                )
+            };
+            // Many (forced or grouped) columns can outgrow the card; the
+            // notebook only scrolls vertically, so pan those sideways here.
+            if wide {
+                egui::ScrollArea::horizontal()
+                    .id_salt(cache_id.with("canvas_scroll"))
+                    .auto_shrink([false, true])
+                    .show(ui, paint)
+                    .inner
+            } else {
+                paint(ui)
+            }
+        };
+        let (response, followed) = response;
+        if let Some(on_scroll_to) = self.on_scroll_to.as_mut() {
+            if let Some(node) = followed.or(pending_scroll).and_then(|idx| graph.nodes.get(idx)) {
+                on_scroll_to(node.id);
+            }
+        }
+        if followed.is_some() && *self.selection != selection_before_paint {
+            trail.push(selection_before_paint);
+            if trail.len() > MAX_NAVIGATION_DEPTH {
+                trail.remove(0);
+            }
+        }
+        ui.data_mut(|memory| memory.insert_temp(trail_id, trail));
+        if pinned != pinned_before {
+            ui.data_mut(|memory| memory.insert_temp(pinned_id, pinned));
+            ui.ctx().request_repaint();
+        }
+        let selected = match self.multi_selection.as_deref() {
+            Some(selected) => selected.clone(),
+            None => vec![*self.selection],
         };
-        let selection_changed = *self.selection != selection_before;
+        let selection_changed = *self.selection != selection_before
+            || multi_before.is_some_and(|before| before != selected);
         EntityInspectorResponse {
             response,
             stats,
             selection_changed,
+            selected,
+        }
+    }
+}
+
+const MAX_NAVIGATION_DEPTH: usize = 32;
+
+/// Height of the placeholder shown instead of an empty canvas.
+const EMPTY_STATE_HEIGHT: f32 = 160.0;
+
+/// Why there is nothing to lay out, if so: no entities at all, or every
+/// attribute hidden in the visibility panel.
+fn empty_state_message(
+    data: &TribleSet,
+    graph: &EntityGraph,
+    hidden_attrs: &HashSet<Id>,
+) -> Option<&'static str> {
+    if data.is_empty() || graph.nodes.is_empty() {
+        Some("No entities to display")
+    } else if !hidden_attrs.is_empty() && graph.nodes.iter().all(|node| node.rows.is_empty()) {
+        Some("No attributes match the filter")
+    } else {
+        None
+    }
+}
+
+/// A hairline box across the available width with `message` centered.
+fn paint_empty_state(ui: &mut Ui, message: &str) -> Response {
+    let (rect, response) = ui.allocate_exact_size(
+        vec2(ui.available_width().max(1.0), EMPTY_STATE_HEIGHT),
+        Sense::hover(),
+    );
+    let visuals = ui.visuals();
+    let painter = ui.painter();
+    painter.rect_stroke(
+        rect,
+        0.0,
+        visuals.widgets.noninteractive.bg_stroke,
+        egui::StrokeKind::Inside,
+    );
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        message,
+        TextStyle::Monospace.resolve(ui.style()),
+        visuals.weak_text_color(),
+    );
+    response
+}
+
+/// Entity count above which the inspector asks before laying out; see
+/// [`EntityInspectorWidget::max_nodes`].
+pub const DEFAULT_MAX_NODES: usize = 5_000;
+
+/// What to do with a dataset over [`EntityInspectorWidget::max_nodes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeLimitChoice {
+    /// Lay out the first `max_nodes` entities by id.
+    Sample,
+    /// Lay out every entity.
+    All,
+}
+
+/// Distinct entities that `data` has tribles about.
+fn distinct_entities(data: &TribleSet) -> HashSet<Id> {
+    find!((e: Id, a: Id, v: Inline<UnknownInline>), data.pattern(e, a, v))
+        .map(|(e, _, _)| e)
+        .collect()
+}
+
+/// The [`paint_empty_state`] box, explaining that `entities` exceed `max`
+/// and offering to lay out the first `max` or all of them; returns the
+/// choice clicked.
+fn paint_node_limit(
+    ui: &mut Ui,
+    entities: usize,
+    max: usize,
+) -> (Response, Option<NodeLimitChoice>) {
+    let message = format!("{entities} entities exceed the limit of {max}");
+    let response = paint_empty_state(ui, &message);
+    let width = crate::card_ctx::span_width(3);
+    let gap = crate::card_ctx::GRID_GUTTER;
+    let center = response.rect.center()
+        + vec2(0.0, 2.0 * ui.text_style_height(&TextStyle::Monospace));
+    let size = vec2(width, 2.0 * crate::card_ctx::GRID_ROW_MODULE);
+    let first_rect = Rect::from_center_size(center - vec2((width + gap) * 0.5, 0.0), size);
+    let all_rect = Rect::from_center_size(center + vec2((width + gap) * 0.5, 0.0), size);
+    let first = crate::widgets::Button::new(format!("Show first {max}")).columns(3);
+    let all = crate::widgets::Button::new("Show anyway").columns(3);
+    let first = ui.put(first_rect, first).clicked();
+    let all = ui.put(all_rect, all).clicked();
+    let choice = if first {
+        Some(NodeLimitChoice::Sample)
+    } else if all {
+        Some(NodeLimitChoice::All)
+    } else {
+        None
+    };
+    (response, choice)
+}
+
+/// Pixel size of a component thumbnail.
+const THUMBNAIL_SIZE: [usize; 2] = [96, 64];
+
+/// Most thumbnails the gallery renders; each costs a layout and a texture.
+const MAX_THUMBNAILS: usize = 32;
+
+/// Rendered component thumbnails with the first node of each component.
+#[derive(Clone, Default)]
+struct ThumbnailCache {
+    /// [`EntityGraph::fingerprint`] of the graph they were rendered from.
+    fingerprint: Option<u64>,
+    thumbnails: Vec<(usize, egui::TextureHandle)>,
+    /// Components left out past [`MAX_THUMBNAILS`].
+    omitted: usize,
+}
+
+/// Row of component thumbnails for the largest components, rendered once
+/// per graph. Returns the first node of the component whose thumbnail was
+/// clicked.
+fn component_gallery(ui: &mut Ui, cache_id: egui::Id, graph: &EntityGraph) -> Option<usize> {
+    let thumbnail_id = cache_id.with("component_thumbnails");
+    let mut cache: ThumbnailCache = ui.data(|memory| memory.get_temp(thumbnail_id).unwrap_or_default());
+    if cache.fingerprint != Some(graph.fingerprint) {
+        let mut components: Vec<Vec<usize>> = connected_components(&build_adjacency(graph))
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+        // Stable, so equal sizes keep their order by first node.
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        cache.omitted = components.len().saturating_sub(MAX_THUMBNAILS);
+        components.truncate(MAX_THUMBNAILS);
+        cache.thumbnails = components
+            .into_iter()
+            .enumerate()
+            .map(|(idx, component)| {
+                // Roughly square: the layout packs the component alone
+                // into about sqrt(n) columns.
+                let columns = (component.len() as f32).sqrt().ceil() as usize;
+                let options = LayoutOptions {
+                    columns,
+                    ..LayoutOptions::default()
+                };
+                let layout = compute_graph_layout(ui, graph, &component, &[], &options);
+                let image = rasterize_component(&layout, graph, &component);
+                let texture = ui.ctx().load_texture(
+                    format!("entity_component_{idx}"),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                );
+                (component[0], texture)
+            })
+            .collect();
+        cache.fingerprint = Some(graph.fingerprint);
+    }
+    if cache.thumbnails.is_empty() {
+        return None;
+    }
+
+    let ink = ui.visuals().widgets.noninteractive.fg_stroke.color;
+    let hairline = ui.visuals().widgets.noninteractive.bg_stroke;
+    let size = vec2(THUMBNAIL_SIZE[0] as f32, THUMBNAIL_SIZE[1] as f32);
+    let mut clicked = None;
+    egui::ScrollArea::horizontal()
+        .id_salt(thumbnail_id)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (first, texture) in &cache.thumbnails {
+                    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+                    let stroke = if response.hovered() {
+                        ui.visuals().selection.stroke
+                    } else {
+                        hairline
+                    };
+                    egui::Image::new((texture.id(), size))
+                        .tint(ink)
+                        .paint_at(ui, rect);
+                    ui.painter()
+                        .rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Inside);
+                    let title = &graph.nodes[*first].title;
+                    if response.on_hover_text(title.as_str()).clicked() {
+                        clicked = Some(*first);
+                    }
+                }
+                if cache.omitted > 0 {
+                    ui.label(
+                        egui::RichText::new(format!("+{} more", cache.omitted))
+                            .monospace()
+                            .weak(),
+                    );
+                }
+            });
+        });
+    ui.data_mut(|memory| memory.insert_temp(thumbnail_id, cache));
+    ui.add_space(6.0);
+    clicked
+}
+
+/// Sketches a component's layout in white on transparent: tiles as
+/// translucent blocks, references as straight lines between tile centers.
+/// The gallery tints it with the theme ink.
+fn rasterize_component(layout: &GraphLayout, graph: &EntityGraph, component: &[usize]) -> egui::ColorImage {
+    let [width, height] = THUMBNAIL_SIZE;
+    let mut pixels = vec![egui::Color32::TRANSPARENT; width * height];
+    let bounds = component
+        .iter()
+        .map(|&node| layout.tile_rects[node])
+        .fold(Rect::NOTHING, Rect::union);
+    let margin = 4.0;
+    let scale = ((width as f32 - margin * 2.0) / bounds.width())
+        .min((height as f32 - margin * 2.0) / bounds.height());
+    let offset = vec2(
+        (width as f32 - bounds.width() * scale) * 0.5,
+        (height as f32 - bounds.height() * scale) * 0.5,
+    );
+    let to_px = |p: egui::Pos2| pos2((p.x - bounds.min.x) * scale, (p.y - bounds.min.y) * scale) + offset;
+    let mut plot = |x: i32, y: i32, alpha: u8| {
+        if (0..width as i32).contains(&x) && (0..height as i32).contains(&y) {
+            let pixel = &mut pixels[y as usize * width + x as usize];
+            if pixel.a() < alpha {
+                *pixel = egui::Color32::from_white_alpha(alpha);
+            }
+        }
+    };
+
+    for &node in component {
+        let rect = Rect::from_min_max(to_px(layout.tile_rects[node].min), to_px(layout.tile_rects[node].max));
+        for y in rect.top().round() as i32..rect.bottom().round() as i32 {
+            for x in rect.left().round() as i32..rect.right().round() as i32 {
+                plot(x, y, 96);
+            }
         }
     }
+    let in_component: HashSet<usize> = component.iter().copied().collect();
+    for edge in graph
+        .edges
+        .iter()
+        .filter(|edge| in_component.contains(&edge.from_entity))
+    {
+        let from = to_px(layout.tile_rects[edge.from_entity].center());
+        let to = to_px(layout.tile_rects[edge.to_entity].center());
+        let steps = (to - from).abs().max_elem().ceil().max(1.0) as i32;
+        for step in 0..=steps {
+            let p = from.lerp(to, step as f32 / steps as f32);
+            plot(p.x.round() as i32, p.y.round() as i32, 255);
+        }
+    }
+
+    egui::ColorImage::new(THUMBNAIL_SIZE, pixels)
+}
+
+/// Breadcrumb bar for the entities visited by following references, ending
+/// in the current selection. Returns the trail depth to return to when the
+/// back button or a crumb is clicked.
+fn navigation_breadcrumbs(
+    ui: &mut Ui,
+    graph: &EntityGraph,
+    trail: &[Id],
+    selection: Id,
+) -> Option<usize> {
+    if trail.is_empty() {
+        return None;
+    }
+    let title = |id: &Id| {
+        graph
+            .id_to_index
+            .get(id)
+            .map(|&idx| graph.nodes[idx].title.clone())
+            .unwrap_or_else(|| format!("id:{}", id_short(*id)))
+    };
+    let mut target = None;
+    ui.horizontal_wrapped(|ui| {
+        if ui.add(crate::widgets::Button::new("◂ BACK")).clicked() {
+            target = Some(trail.len() - 1);
+        }
+        for (depth, id) in trail.iter().enumerate() {
+            let crumb = egui::RichText::new(title(id)).monospace();
+            if ui.link(crumb).on_hover_text(id_full(*id)).clicked() {
+                target = Some(depth);
+            }
+            ui.label(egui::RichText::new("›").monospace().weak());
+        }
+        ui.label(egui::RichText::new(title(&selection)).monospace().strong());
+        let (short, full) = (id_short(selection), id_full(selection));
+        crate::widgets::copyable_label_as(ui, &short, &full);
+    });
+    target
+}
+
+/// Collapsible row of toggles, one per attribute. Returns `true` when the
+/// hidden set changed.
+fn attribute_visibility_panel(
+    ui: &mut Ui,
+    attributes: &[(Id, String)],
+    hidden_attrs: &mut HashSet<Id>,
+) -> bool {
+    let mut changed = false;
+    let title = format!("Attributes ({} hidden)", hidden_attrs.len());
+    // Collapsed by default; keyed apart from the title, which changes
+    // with the count.
+    let id_salt = "entity_inspector_attributes";
+    crate::widgets::collapsing_section_with_id(ui, id_salt, &title, false, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            for (attr, label) in attributes {
+                let mut visible = !hidden_attrs.contains(attr);
+                let button = crate::widgets::Button::new(label.as_str()).on(&mut visible);
+                let response = ui.add(button);
+                if response.clicked() {
+                    if visible {
+                        hidden_attrs.remove(attr);
+                    } else {
+                        hidden_attrs.insert(*attr);
+                    }
+                    changed = true;
+                }
+            }
+        });
+    });
+    changed
 }
 
 #[derive(Clone, Debug)]
@@ -724,6 +1977,7 @@ struct EdgeRender {
     points: Vec<egui::Pos2>,
     line_color: egui::Color32,
     start_underline: Option<(egui::Pos2, egui::Pos2)>,
+    attr_id: Id,
     from_entity: usize,
     to_entity: usize,
 }
@@ -756,6 +2010,62 @@ fn attribute_palette_index(attr: Id, palette_len: usize) -> usize {
     (hash as usize) % palette_len
 }
 
+fn default_edge_palette() -> Vec<egui::Color32> {
+    // RAL classic.
+    [1003, 2010, 3001, 4008, 5005, 6032, 3014]
+        .into_iter()
+        .map(themes::ral)
+        .collect()
+}
+
+/// Assigns each attribute with edges its own color.
+///
+/// Attributes claim their hashed palette slot in id order, moving on to
+/// the next free slot on a collision, so colors only repeat once every
+/// slot is taken. Each further round reuses the palette in a lighter or
+/// darker variant.
+fn attribute_colors(
+    routed_edges: &[RoutedEdge],
+    palette: &[egui::Color32],
+) -> HashMap<Id, egui::Color32> {
+    let mut attrs: Vec<Id> = routed_edges.iter().map(|edge| edge.attr_id).collect();
+    attrs.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    attrs.dedup();
+
+    let mut colors = HashMap::with_capacity(attrs.len());
+    if palette.is_empty() {
+        return colors;
+    }
+    for (round, chunk) in attrs.chunks(palette.len()).enumerate() {
+        let mut taken = vec![false; palette.len()];
+        for &attr in chunk {
+            let preferred = attribute_palette_index(attr, palette.len());
+            let slot = (0..palette.len())
+                .map(|step| (preferred + step) % palette.len())
+                .find(|&slot| !taken[slot])
+                .expect("a round never has more attributes than slots");
+            taken[slot] = true;
+            colors.insert(attr, palette_variant(palette[slot], round));
+        }
+    }
+    colors
+}
+
+/// Round 0 is the palette itself; later rounds alternate between lighter
+/// and darker shades.
+fn palette_variant(color: egui::Color32, round: usize) -> egui::Color32 {
+    if round == 0 {
+        return color;
+    }
+    let amount = (0.3 + 0.1 * ((round - 1) / 2) as f32).min(0.6);
+    let target = if round % 2 == 1 {
+        egui::Color32::WHITE
+    } else {
+        egui::Color32::BLACK
+    };
+    themes::blend_oklab(color, target, amount)
+}
+
 fn entity_order(
     ui: &mut Ui,
     cache_id: egui::Id,
@@ -1923,17 +3233,21 @@ fn choose_track_y_between_columns(
     (choose_track_y(&corridors, start_y, end_y), true)
 }
 
-fn row_line_y(layout: &GraphLayout, tile: Rect, row: usize) -> f32 {
-    let row_top = tile.top()
-        + layout.tile_padding
-        + layout.header_height
-        + row as f32 * layout.text_row_height;
+fn row_line_y(layout: &GraphLayout, tile: Rect, node: usize, row: usize) -> f32 {
+    let row_top =
+        tile.top() + layout.tile_padding + layout.header_height + layout.row_span(node, row).0;
     let y = row_top + layout.text_row_height - 2.0 - 1.0;
     y.max(row_top + 2.0)
 }
 
-fn row_anchor(layout: &GraphLayout, tile: Rect, row: usize, on_left: bool) -> egui::Pos2 {
-    let y = row_line_y(layout, tile, row);
+fn row_anchor(
+    layout: &GraphLayout,
+    tile: Rect,
+    node: usize,
+    row: usize,
+    on_left: bool,
+) -> egui::Pos2 {
+    let y = row_line_y(layout, tile, node, row);
     let edge_inset = 0.0;
     let x = if on_left {
         tile.left() + edge_inset
@@ -1946,6 +3260,7 @@ fn row_anchor(layout: &GraphLayout, tile: Rect, row: usize, on_left: bool) -> eg
 fn row_underline_segment(
     layout: &GraphLayout,
     tile: Rect,
+    node: usize,
     row: usize,
     go_left: bool,
 ) -> Option<(egui::Pos2, egui::Pos2)> {
@@ -1954,8 +3269,8 @@ fn row_underline_segment(
         return None;
     }
 
-    let y = row_line_y(layout, tile, row);
-    let key_w = (inner.width() * 0.42).clamp(56.0, 120.0);
+    let y = row_line_y(layout, tile, node, row);
+    let key_w = key_column_width(inner.width());
     let divider_x = (inner.left() + key_w).min(inner.right());
     let inset = 4.0;
     let min_len = 6.0;
@@ -2083,7 +3398,7 @@ fn route_edges(layout: &GraphLayout, graph: &EntityGraph) -> Vec<RoutedEdge> {
         } else {
             to_col < from_col
         };
-        let start = row_anchor(layout, source_rect, edge.from_row, go_left);
+        let start = row_anchor(layout, source_rect, edge.from_entity, edge.from_row, go_left);
         let end_on_left = if same_col { go_left } else { !go_left };
         let end = closest_corner_on_side(target_rect, start, end_on_left);
 
@@ -2268,6 +3583,7 @@ fn route_edges(layout: &GraphLayout, graph: &EntityGraph) -> Vec<RoutedEdge> {
             start_underline: row_underline_segment(
                 layout,
                 draft.source_rect,
+                draft.edge.from_entity,
                 draft.edge.from_row,
                 draft.go_left,
             ),
@@ -2393,11 +3709,24 @@ fn paint_entity_table(
     node: &EntityNode,
     node_idx: usize,
     is_selected: bool,
+    pinned: &mut Vec<Id>,
     layout: &GraphLayout,
     graph: &EntityGraph,
+    tile_style: TileStyle,
 ) -> TableInteraction {
     let id = ui.id().with(("entity_table", node.id));
     let response = ui.interact(rect, id, Sense::click());
+    // Tiles are painted, so screen readers only learn about them through
+    // the interaction responses: the tile is a selectable item named by
+    // its title, each row a child labeled with its attribute and value.
+    response.widget_info(|| {
+        egui::WidgetInfo::selected(
+            egui::WidgetType::SelectableLabel,
+            ui.is_enabled(),
+            is_selected,
+            &node.title,
+        )
+    });
     let table_clicked = response.clicked();
 
     let visuals = ui.visuals();
@@ -2405,18 +3734,22 @@ fn paint_entity_table(
     let ink = visuals.widgets.noninteractive.fg_stroke.color;
     let stroke = Stroke::new(1.0, ink);
     let grid_stroke = Stroke::new(1.0, ink);
-    let hatch_color = visuals.widgets.noninteractive.bg_stroke.color;
+    let hatch_color = crate::themes::blend_oklab(ink, fill, HATCH_INK_FADE);
+    let hatch_spacing = hatch_spacing(layout.text_row_height);
 
     let painter = ui.painter();
-    painter.rect_filled(rect, 0.0, fill);
+    let corner_radius = tile_style.corner_radius();
+    if let Some(shadow) = tile_style.shadow() {
+        painter.add(shadow.as_shape(rect, corner_radius));
+    }
+    painter.rect_filled(rect, corner_radius, fill);
+    painter.rect_stroke(rect, corner_radius, stroke, egui::StrokeKind::Inside);
     if is_selected {
-        painter.rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Inside);
         let inner_rect = rect.shrink(2.0);
         if inner_rect.is_positive() {
-            painter.rect_stroke(inner_rect, 0.0, stroke, egui::StrokeKind::Inside);
+            let inner_radius = corner_radius - egui::CornerRadius::same(2);
+            painter.rect_stroke(inner_rect, inner_radius, stroke, egui::StrokeKind::Inside);
         }
-    } else {
-        painter.rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Inside);
     }
 
     let text_color = ink;
@@ -2428,16 +3761,33 @@ fn paint_entity_table(
         inner.left_top(),
         pos2(inner.right(), inner.top() + layout.header_height),
     );
+    let is_pinned = pinned.contains(&node.id);
+    let pin_side = (layout.header_height * 0.3).round();
+    let title_width = if is_pinned {
+        title_rect.width() - pin_side - 4.0
+    } else {
+        title_rect.width()
+    };
+    let short_title = truncate_title(ui, &node.title, &title_font, title_width);
     painter.text(
         title_rect.left_top(),
         Align2::LEFT_TOP,
-        node.title.as_str(),
+        short_title.as_deref().unwrap_or(&node.title),
         title_font,
         text_color,
     );
+    if is_pinned {
+        // Pin mark: a filled square in the title's top-right corner.
+        let side = pin_side;
+        let mark = Rect::from_min_size(
+            pos2(title_rect.right() - side, title_rect.top() + 2.0),
+            vec2(side, side),
+        );
+        painter.rect_filled(mark, 0.0, ink);
+    }
 
     let row_top = title_rect.bottom();
-    let key_w = (inner.width() * 0.42).clamp(56.0, 120.0);
+    let key_w = key_column_width(inner.width());
     let key_x = inner.left();
     let value_x = (inner.left() + key_w + 8.0).min(inner.right());
 
@@ -2450,30 +3800,43 @@ fn paint_entity_table(
     let mut scroll_target = None;
     let mut select_target = None;
     for (i, row) in node.rows.iter().enumerate() {
-        let y = row_top + i as f32 * layout.text_row_height;
+        let (offset, row_height) = layout.row_span(node_idx, i);
+        let y = row_top + offset;
         let row_rect = Rect::from_min_max(
             pos2(inner.left(), y),
-            pos2(
-                inner.right(),
-                (y + layout.text_row_height).min(inner.bottom()),
-            ),
+            pos2(inner.right(), (y + row_height).min(inner.bottom())),
         );
         if i > 0 {
             painter.hline(row_rect.x_range(), row_rect.top(), grid_stroke);
         }
 
-        if let Some(target) = row.target {
-            if let Some(&target_idx) = graph.id_to_index.get(&target) {
-                let row_id = id.with(("row", i));
-                let row_response = ui
-                    .interact(row_rect, row_id, Sense::click())
-                    .on_hover_cursor(egui::CursorIcon::PointingHand);
-                if row_response.clicked() {
+        // Every row takes clicks so values can be copied from its menu; a
+        // row without a followable reference selects the table instead.
+        let target_idx = row
+            .target
+            .and_then(|target| graph.id_to_index.get(&target).copied());
+        let mut row_response = ui.interact(row_rect, id.with(("row", i)), Sense::click());
+        row_response.widget_info(|| {
+            let kind = if target_idx.is_some() {
+                egui::WidgetType::Link
+            } else {
+                egui::WidgetType::Label
+            };
+            egui::WidgetInfo::labeled(kind, ui.is_enabled(), row_accessible_label(row))
+        });
+        if target_idx.is_some() {
+            row_response = row_response.on_hover_cursor(egui::CursorIcon::PointingHand);
+        }
+        if row_response.clicked() {
+            match target_idx {
+                Some(target_idx) => {
                     scroll_target = Some(target_idx);
                     select_target = Some(target_idx);
                 }
+                None => select_target = Some(node_idx),
             }
         }
+        row_response.context_menu(|ui| row_context_menu(ui, node, row));
 
         painter.text(
             pos2(key_x, row_rect.top() + 1.0),
@@ -2489,9 +3852,21 @@ fn paint_entity_table(
             );
             let hatch_rect = value_rect.shrink(1.0);
             if hatch_rect.is_positive() {
-                paint_hatching(&painter.with_clip_rect(hatch_rect), hatch_rect, hatch_color);
+                paint_hatching(
+                    &painter.with_clip_rect(hatch_rect),
+                    hatch_rect,
+                    hatch_color,
+                    hatch_spacing,
+                );
             }
-        } else {
+            if let Some(note) = row.size_note.as_ref() {
+                let galley = painter.layout_no_wrap(note.clone(), row_font.clone(), text_color);
+                let note_pos = pos2(value_x, row_rect.top() + 1.0);
+                let note_rect = Rect::from_min_size(note_pos, galley.size());
+                painter.rect_filled(note_rect, 0.0, fill);
+                painter.galley(note_pos, galley, text_color);
+            }
+        } else if layout.row_offsets.is_empty() {
             painter.text(
                 pos2(value_x, row_rect.top() + 1.0),
                 Align2::LEFT_TOP,
@@ -2499,11 +3874,42 @@ fn paint_entity_table(
                 row_font.clone(),
                 text_color,
             );
+        } else {
+            let galley = painter.layout(
+                row.value.clone(),
+                row_font.clone(),
+                text_color,
+                (inner.right() - value_x).max(1.0),
+            );
+            painter.galley(pos2(value_x, row_rect.top() + 1.0), galley, text_color);
         }
     }
 
+    response.context_menu(|ui| {
+        let pin_label = if is_pinned {
+            "Unpin"
+        } else {
+            "Pin to first column"
+        };
+        if ui.button(pin_label).clicked() {
+            if is_pinned {
+                pinned.retain(|id| *id != node.id);
+            } else {
+                pinned.push(node.id);
+            }
+            ui.close();
+        }
+        ui.separator();
+        if ui.button("Copy entity id").clicked() {
+            ui.ctx().copy_text(id_full(node.id));
+            ui.close();
+        }
+    });
     if response.hovered() {
-        let full = id_full(node.id);
+        let full = match short_title {
+            Some(_) => format!("{}\n{}", node.title, id_full(node.id)),
+            None => id_full(node.id),
+        };
         let _ = response.on_hover_text(full);
     }
 
@@ -2517,14 +3923,79 @@ fn paint_entity_table(
     }
 }
 
+/// Shortens `title` with an ellipsis so it fits `max_width` in `font`,
+/// cutting at a word boundary when one falls in the back half of the
+/// space. Returns `None` if the title already fits.
+fn truncate_title(ui: &Ui, title: &str, font: &egui::FontId, max_width: f32) -> Option<String> {
+    let width = |text: &str| {
+        ui.fonts_mut(|fonts| {
+            fonts
+                .layout_no_wrap(text.to_owned(), font.clone(), egui::Color32::PLACEHOLDER)
+                .size()
+                .x
+        })
+    };
+    if width(title) <= max_width {
+        return None;
+    }
+
+    // `cuts[k]` ends the prefix of `k` chars; the longest fitting prefix is
+    // found by bisection since layout is the expensive part.
+    let cuts: Vec<usize> = title.char_indices().map(|(idx, _)| idx).collect();
+    let (mut lo, mut hi) = (0, cuts.len() - 1);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if width(&format!("{}…", &title[..cuts[mid]])) <= max_width {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let prefix = &title[..cuts[lo]];
+    let word_end = prefix
+        .rfind(|c: char| c.is_whitespace() || matches!(c, '-' | '_' | '/' | '.' | ':'))
+        .filter(|&end| end >= prefix.len() / 2)
+        .unwrap_or(prefix.len());
+    let prefix = prefix[..word_end].trim_end();
+    Some(format!("{prefix}…"))
+}
+
+/// Right-click menu of an entity table row; the painted text can't be
+/// selected, so this is how values get out.
+fn row_context_menu(ui: &mut Ui, node: &EntityNode, row: &EntityRow) {
+    if ui.button("Copy value").clicked() {
+        ui.ctx().copy_text(row.value.clone());
+        ui.close();
+    }
+    if ui.button("Copy attribute").clicked() {
+        ui.ctx().copy_text(row.attr.clone());
+        ui.close();
+    }
+    if let Some(target) = row.target {
+        if ui.button("Copy target id").clicked() {
+            ui.ctx().copy_text(id_full(target));
+            ui.close();
+        }
+    }
+    ui.separator();
+    if ui.button("Copy entity id").clicked() {
+        ui.ctx().copy_text(id_full(node.id));
+        ui.close();
+    }
+}
+
 fn compute_inspector(
     ui: &mut Ui,
     cache_id: egui::Id,
     graph: &EntityGraph,
-    forced_columns: usize,
     order: EntityOrder,
+    pinned: &[usize],
+    options: &LayoutOptions,
 ) -> (GraphLayout, Vec<RoutedEdge>, EntityInspectorStats) {
-    let order = entity_order(ui, cache_id, graph, order);
+    let mut order = entity_order(ui, cache_id, graph, order);
+    // Pinned entities lead the order regardless of what the annealer finds.
+    order.retain(|idx| !pinned.contains(idx));
+    order.splice(0..0, pinned.iter().copied());
     let mut positions = vec![0usize; graph.nodes.len()];
     for (pos, &idx) in order.iter().enumerate() {
         positions[idx] = pos;
@@ -2540,7 +4011,7 @@ fn compute_inspector(
     } else {
         linear_total / graph.edges.len() as f32
     };
-    let layout = compute_graph_layout(ui, graph, forced_columns, &order);
+    let layout = compute_graph_layout(ui, graph, &order, pinned, options);
     let routed_edges = route_edges(&layout, graph);
     let stats = compute_graph_stats(graph, &layout, &routed_edges, linear_total, linear_avg);
     (layout, routed_edges, stats)
@@ -2566,6 +4037,14 @@ fn compute_graph_stats(
     } else {
         tile_area / canvas_area
     };
+    let balance_coverage_gain = layout.unbalanced_height.map_or(0.0, |height| {
+        let unbalanced_area = layout.canvas_size.x * height;
+        if unbalanced_area <= 0.0 {
+            0.0
+        } else {
+            tile_coverage - tile_area / unbalanced_area
+        }
+    });
 
     let mut total_edge_len = 0.0f32;
     let mut max_edge_len = 0.0f32;
@@ -2599,6 +4078,7 @@ fn compute_graph_stats(
         canvas_width: layout.canvas_size.x,
         canvas_height: layout.canvas_size.y,
         tile_coverage,
+        balance_coverage_gain,
         total_edge_len,
         avg_edge_len: if edges == 0 {
             0.0
@@ -2629,21 +4109,39 @@ fn paint_entity_inspector(
     ui: &mut Ui,
     graph: &EntityGraph,
     selected_id: &mut Id,
+    mut multi_selection: Option<&mut Vec<Id>>,
+    pinned: &mut Vec<Id>,
     layout: &GraphLayout,
     routed_edges: &[RoutedEdge],
-) -> Response {
+    pending_scroll: Option<usize>,
+    tile_style: TileStyle,
+    auto_scroll: bool,
+    edge_palette: &[egui::Color32],
+    on_edge_click: Option<&mut (dyn FnMut(EntityEdgeRef, egui::Modifiers) + '_)>,
+) -> (Response, Option<usize>) {
     let selected_index = graph.id_to_index.get(selected_id).copied();
 
-    let desired_width = ui.available_width();
+    // A canvas wider than the card isn't centered; the caller pans it in a
+    // horizontal scroll area instead.
+    let desired_width = ui.available_width().max(layout.canvas_size.x);
     let (outer_rect, response) =
         ui.allocate_exact_size(vec2(desired_width, layout.canvas_size.y), Sense::hover());
-    if !ui.is_rect_visible(outer_rect) {
-        return response;
-    }
-
-    let offset_x = ((desired_width - layout.canvas_size.x).max(0.0)) * 0.5;
+    let offset_x = (desired_width - layout.canvas_size.x) * 0.5;
     let origin = pos2(outer_rect.left() + offset_x, outer_rect.top());
     let origin_vec = origin.to_vec2();
+    if !ui.is_rect_visible(outer_rect) {
+        // Going back can target an entity while the canvas is scrolled out
+        // of view entirely.
+        if let Some(rect) = pending_scroll
+            .filter(|_| auto_scroll)
+            .and_then(|idx| layout.tile_rects.get(idx))
+        {
+            if rect.is_positive() {
+                ui.scroll_to_rect(rect.translate(origin_vec), Some(egui::Align::Center));
+            }
+        }
+        return (response, None);
+    }
     let tile_rects_ui: Vec<Rect> = layout
         .tile_rects
         .iter()
@@ -2662,16 +4160,7 @@ fn paint_entity_inspector(
 
     let painter = ui.painter().with_clip_rect(outer_rect);
     let line_width: f32 = 2.5;
-    // Line palette (RAL classic).
-    let line_palette = [
-        themes::ral(1003),
-        themes::ral(2010),
-        themes::ral(3001),
-        themes::ral(4008),
-        themes::ral(5005),
-        themes::ral(6032),
-        themes::ral(3014),
-    ];
+    let line_colors = attribute_colors(routed_edges, edge_palette);
     let end_dot_radius = line_width * 2.5;
     let hover_threshold = end_dot_radius.max(line_width * 3.0);
 
@@ -2684,14 +4173,17 @@ fn paint_entity_inspector(
             .map(|p| p + origin_vec)
             .collect::<Vec<_>>();
         let points = round_polyline(&raw, (layout.text_row_height * 0.25).clamp(3.0, 8.0), 4);
-        let palette_index = attribute_palette_index(routed.attr_id, line_palette.len());
-        let line_color = line_palette[palette_index];
+        let line_color = line_colors
+            .get(&routed.attr_id)
+            .copied()
+            .unwrap_or_else(|| ui.visuals().widgets.noninteractive.fg_stroke.color);
         edge_renders.push(EdgeRender {
             points,
             line_color,
             start_underline: routed
                 .start_underline
                 .map(|(a, b)| (a + origin_vec, b + origin_vec)),
+            attr_id: routed.attr_id,
             from_entity: routed.from_entity,
             to_entity: routed.to_entity,
         });
@@ -2739,7 +4231,7 @@ fn paint_entity_inspector(
             let line_color = if is_active {
                 render.line_color
             } else {
-                themes::blend(render.line_color, fade_bg, 0.5)
+                themes::blend_oklab(render.line_color, fade_bg, 0.5)
             };
             let line_stroke = Stroke::new(line_width, line_color);
             paint_subway_edge(&painter, &render.points, line_stroke);
@@ -2771,6 +4263,23 @@ fn paint_entity_inspector(
         }
     }
 
+    if let Some(divider_y) = layout.singleton_divider_y {
+        let ink = ui.visuals().widgets.noninteractive.fg_stroke.color;
+        let left = origin.x + layout.column_gap;
+        let right = origin.x + layout.canvas_size.x - layout.column_gap;
+        let center_y = origin.y + divider_y + layout.header_height * 0.5;
+        let label = painter.text(
+            pos2(left, center_y),
+            Align2::LEFT_CENTER,
+            format!("UNCONNECTED ({})", layout.singleton_count),
+            TextStyle::Small.resolve(ui.style()),
+            ink,
+        );
+        if label.right() + 6.0 < right {
+            painter.hline(label.right() + 6.0..=right, center_y, Stroke::new(1.0, ink));
+        }
+    }
+
     let mut scroll_target = None;
     let mut select_target = None;
     for (idx, node) in graph.nodes.iter().enumerate() {
@@ -2778,8 +4287,21 @@ fn paint_entity_inspector(
         if !rect.is_positive() {
             continue;
         }
-        let is_selected = selected_index == Some(idx);
-        let table = paint_entity_table(ui, rect, node, idx, is_selected, &layout, graph);
+        let is_selected = match multi_selection.as_deref() {
+            Some(selected) => selected.contains(&node.id),
+            None => selected_index == Some(idx),
+        };
+        let table = paint_entity_table(
+            ui,
+            rect,
+            node,
+            idx,
+            is_selected,
+            pinned,
+            &layout,
+            graph,
+            tile_style,
+        );
         if scroll_target.is_none() {
             scroll_target = table.scroll_target;
         }
@@ -2788,31 +4310,94 @@ fn paint_entity_inspector(
         }
     }
 
+    // Group headers stick to the top of the visible area while the canvas
+    // scrolls under them, until the canvas bottom pushes them out.
+    let header_font = TextStyle::Small.resolve(ui.style());
+    let visible_top = ui.clip_rect().top();
+    for (rect, label) in &layout.group_headers {
+        let ink = ui.visuals().widgets.noninteractive.fg_stroke.color;
+        let rect = rect.translate(origin_vec);
+        let lowest = (outer_rect.bottom() - rect.height()).max(rect.top());
+        let rect = rect.translate(vec2(0.0, visible_top.clamp(rect.top(), lowest) - rect.top()));
+        painter.rect_filled(rect, 0.0, ui.visuals().panel_fill);
+        let text = truncate_title(ui, label, &header_font, rect.width())
+            .unwrap_or_else(|| label.clone());
+        painter.text(rect.left_center(), Align2::LEFT_CENTER, text, header_font.clone(), ink);
+        painter.hline(rect.x_range(), rect.bottom(), Stroke::new(1.0, ink));
+    }
+
+    if let Some(render) = hovered_edge.and_then(|idx| edge_renders.get(idx)) {
+        let attr = graph
+            .attributes
+            .iter()
+            .find(|(id, _)| *id == render.attr_id)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| format!("id:{}", id_short(render.attr_id)));
+        let title = |idx: usize| graph.nodes.get(idx).map_or("?", |node| node.title.as_str());
+        let text = format!(
+            "{attr}\n{} -> {}",
+            title(render.from_entity),
+            title(render.to_entity)
+        );
+        crate::show_postit_tooltip(ui, &response, &text);
+    }
+
     if scroll_target.is_none()
         && ui.input(|input| input.pointer.primary_clicked())
         && hovered_edge.is_some()
     {
-        let shift = ui.input(|input| input.modifiers.shift);
-        if let Some(edge_idx) = hovered_edge {
-            if let Some(render) = edge_renders.get(edge_idx) {
-                let target_idx = if shift {
-                    render.from_entity
-                } else {
-                    render.to_entity
-                };
-                scroll_target = Some(target_idx);
-                select_target = Some(target_idx);
+        let modifiers = ui.input(|input| input.modifiers);
+        if let Some(render) = hovered_edge.and_then(|idx| edge_renders.get(idx)) {
+            match on_edge_click {
+                Some(on_edge_click) => on_edge_click(
+                    EntityEdgeRef {
+                        from: graph.nodes[render.from_entity].id,
+                        to: graph.nodes[render.to_entity].id,
+                        attr: render.attr_id,
+                    },
+                    modifiers,
+                ),
+                None => {
+                    let target_idx = if modifiers.shift {
+                        render.from_entity
+                    } else {
+                        render.to_entity
+                    };
+                    scroll_target = Some(target_idx);
+                    select_target = Some(target_idx);
+                }
             }
         }
     }
 
-    if let Some(target_idx) = select_target {
-        if let Some(node) = graph.nodes.get(target_idx) {
-            *selected_id = node.id;
+    if let Some(node) = select_target.and_then(|idx| graph.nodes.get(idx)) {
+        let toggle = scroll_target.is_none() && ui.input(|input| input.modifiers.command);
+        match multi_selection.as_deref_mut() {
+            Some(selected) if toggle => {
+                if let Some(pos) = selected.iter().position(|id| *id == node.id) {
+                    selected.remove(pos);
+                    if *selected_id == node.id {
+                        if let Some(&last) = selected.last() {
+                            *selected_id = last;
+                        }
+                    }
+                } else {
+                    selected.push(node.id);
+                    *selected_id = node.id;
+                }
+            }
+            Some(selected) => {
+                selected.clear();
+                selected.push(node.id);
+                *selected_id = node.id;
+            }
+            None => *selected_id = node.id,
         }
     }
 
-    if let Some(target_idx) = scroll_target {
+    // Reference rows and edge clicks scroll to their target; that is what
+    // counts as following a reference for the navigation trail.
+    if let Some(target_idx) = scroll_target.or(pending_scroll).filter(|_| auto_scroll) {
         if let Some(rect) = tile_rects_ui.get(target_idx).copied() {
             if rect.is_positive() {
                 ui.scroll_to_rect(rect, Some(egui::Align::Center));
@@ -2842,5 +4427,70 @@ fn paint_entity_inspector(
         }
     }
 
-    response
+    (response, scroll_target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use triblespace::core::repo::memoryrepo::MemoryRepo;
+    use triblespace::core::repo::BlobStore;
+    use triblespace::macros::id_hex;
+
+    #[test]
+    fn empty_data_shows_a_sized_empty_state() {
+        let data = TribleSet::new();
+        let mut storage = MemoryRepo::default();
+        let reader = storage.reader().expect("memory blob store reader");
+        let names: BlobCache<_, LongString, View<str>> = BlobCache::new(reader.clone());
+        let formatters: BlobCache<_, WasmCode, WasmValueFormatter> = BlobCache::new(reader);
+        let mut selection = id_hex!("11111111111111111111111111111111");
+
+        let ctx = egui::Context::default();
+        let mut size = egui::Vec2::ZERO;
+        let _ = ctx.run_ui(egui::RawInput::default(), |ui| {
+            let inspector =
+                EntityInspectorWidget::new(&data, &data, &names, &formatters, &mut selection)
+                    .show(ui);
+            size = inspector.response.rect.size();
+        });
+        assert!(size.x > 0.0);
+        assert_eq!(size.y, EMPTY_STATE_HEIGHT);
+    }
+
+    #[test]
+    fn groups_entities_by_attribute_value() {
+        let kind = id_hex!("22222222222222222222222222222222");
+        let node = |hex: &str, kind_value: Option<&str>| EntityNode {
+            id: Id::from_hex(hex).expect("valid id"),
+            title: hex.to_owned(),
+            rows: kind_value
+                .into_iter()
+                .map(|value| EntityRow {
+                    attr_id: kind,
+                    attr: "kind".to_owned(),
+                    value: value.to_owned(),
+                    target: None,
+                    hatched: false,
+                    size_note: None,
+                })
+                .collect(),
+        };
+        let graph = EntityGraph {
+            nodes: vec![
+                node("33333333333333333333333333333333", Some("task")),
+                node("44444444444444444444444444444444", None),
+                node("55555555555555555555555555555555", Some("note")),
+                node("66666666666666666666666666666666", Some("task")),
+            ],
+            edges: Vec::new(),
+            id_to_index: HashMap::new(),
+            attributes: Vec::new(),
+            fingerprint: 0,
+        };
+        let (labels, group_of) = entity_groups(&graph, kind);
+        assert_eq!(labels, ["note", "task", UNGROUPED_LABEL]);
+        assert_eq!(group_of, [1, 2, 0, 1]);
+    }
 }
@@ -241,7 +241,9 @@ impl<'a> PileRepoWidget<'a> {
                     let style = GorbieToggleButtonStyle::from(ui.style().as_ref());
                     let light_on = crate::themes::button_light_on();
                     let off = style.rail_bg;
-                    let light = if opening {
+                    let light = if opening && crate::reduced_motion(ui.ctx()) {
+                        light_on
+                    } else if opening {
                         let t = ui.input(|input| input.time) as f32;
                         let wave = (t * std::f32::consts::TAU * 0.8).sin() * 0.5 + 0.5;
                         crate::themes::blend(off, light_on, wave)
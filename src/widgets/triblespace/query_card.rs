@@ -0,0 +1,231 @@
+use std::collections::{BTreeSet, HashSet};
+
+use eframe::egui;
+use eframe::egui::{Align, Layout, RichText, TextStyle, Ui};
+use triblespace::core::blob::encodings::longstring::LongString;
+use triblespace::core::blob::encodings::wasmcode::WasmCode;
+use triblespace::core::blob::BlobCache;
+use triblespace::core::id::Id;
+use triblespace::core::repo::BlobStoreGet;
+use triblespace::core::value_formatter::WasmValueFormatter;
+use triblespace::prelude::{TribleSet, View};
+
+use super::entity_inspector::{cached_entity_graph, id_short, BlobGuard, RowOrder};
+use crate::widgets::table::{Column, TableBuilder};
+
+/// Most rows the result table shows; the rest are counted in the footer.
+const MAX_RESULT_ROWS: usize = 500;
+
+#[derive(Clone, Default)]
+struct QueryState {
+    attr: Option<Id>,
+    value: Option<String>,
+}
+
+pub struct QueryCardResponse {
+    pub response: egui::Response,
+    /// The entity whose link was clicked this frame, for driving an
+    /// [`EntityInspectorWidget`](super::EntityInspectorWidget) selection.
+    pub clicked: Option<Id>,
+    /// Number of `(entity, value)` rows matching the query.
+    pub matches: usize,
+}
+
+/// Guided query over a `TribleSet`: pick an attribute and optionally one
+/// of its values from dropdowns, and the matching `(entity, value)` pairs
+/// are listed in a table. Attribute names and value formatting come from
+/// the metadata, the same as in the entity inspector.
+#[must_use = "Use `QueryCardWidget::show(ui)` to render this widget."]
+pub struct QueryCardWidget<'a, B>
+where
+    B: BlobStoreGet,
+{
+    data: &'a TribleSet,
+    metadata: &'a TribleSet,
+    name_cache: &'a BlobCache<B, LongString, View<str>>,
+    formatter_cache: &'a BlobCache<B, WasmCode, WasmValueFormatter>,
+    id_salt: Option<egui::Id>,
+}
+
+impl<'a, B> QueryCardWidget<'a, B>
+where
+    B: BlobStoreGet,
+{
+    pub fn new(
+        data: &'a TribleSet,
+        metadata: &'a TribleSet,
+        name_cache: &'a BlobCache<B, LongString, View<str>>,
+        formatter_cache: &'a BlobCache<B, WasmCode, WasmValueFormatter>,
+    ) -> Self {
+        Self {
+            data,
+            metadata,
+            name_cache,
+            formatter_cache,
+            id_salt: None,
+        }
+    }
+
+    /// Distinguishes several query cards in the same `Ui`.
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(egui::Id::new(id_salt));
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> QueryCardResponse {
+        let base_id = ui.id().with(self.id_salt).with("query_card");
+        let graph = cached_entity_graph(
+            ui,
+            base_id.with("graph"),
+            self.data,
+            self.metadata,
+            &HashSet::new(),
+            self.name_cache,
+            self.formatter_cache,
+            &BlobGuard::default(),
+            RowOrder::Alphabetical,
+            false,
+            None,
+        );
+
+        let state_id = base_id.with("state");
+        let mut state: QueryState = ui.data(|d| d.get_temp(state_id)).unwrap_or_default();
+        if state
+            .attr
+            .is_some_and(|attr| !graph.attributes.iter().any(|(id, _)| *id == attr))
+        {
+            state = QueryState::default();
+        }
+
+        let values: BTreeSet<&str> = match state.attr {
+            Some(attr) => graph
+                .nodes
+                .iter()
+                .flat_map(|node| &node.rows)
+                .filter(|row| row.attr_id == attr)
+                .map(|row| row.value.as_str())
+                .collect(),
+            None => BTreeSet::new(),
+        };
+        if state
+            .value
+            .as_deref()
+            .is_some_and(|value| !values.contains(value))
+        {
+            state.value = None;
+        }
+
+        let mut clicked = None;
+        let response = ui
+            .vertical(|ui| {
+                ui.horizontal_wrapped(|ui| {
+                    crate::widgets::row_label(ui, "Attribute:");
+                    let attr_label = state
+                        .attr
+                        .and_then(|attr| graph.attributes.iter().find(|(id, _)| *id == attr))
+                        .map(|(_, label)| label.as_str())
+                        .unwrap_or("<choose>");
+                    egui::ComboBox::from_id_salt(base_id.with("attr"))
+                        .selected_text(attr_label)
+                        .show_ui(ui, |ui| {
+                            for (attr, label) in &graph.attributes {
+                                if ui
+                                    .selectable_label(state.attr == Some(*attr), label)
+                                    .clicked()
+                                {
+                                    state.attr = Some(*attr);
+                                    state.value = None;
+                                }
+                            }
+                        });
+
+                    crate::widgets::row_label(ui, "Value:");
+                    ui.add_enabled_ui(state.attr.is_some(), |ui| {
+                        egui::ComboBox::from_id_salt(base_id.with("value"))
+                            .selected_text(state.value.as_deref().unwrap_or("<any>"))
+                            .show_ui(ui, |ui| {
+                                if ui.selectable_label(state.value.is_none(), "<any>").clicked() {
+                                    state.value = None;
+                                }
+                                for value in &values {
+                                    let selected = state.value.as_deref() == Some(*value);
+                                    if ui.selectable_label(selected, *value).clicked() {
+                                        state.value = Some((*value).to_owned());
+                                    }
+                                }
+                            });
+                    });
+                });
+
+                let Some(attr) = state.attr else {
+                    return 0;
+                };
+                let matches: Vec<(Id, &str, &str)> = graph
+                    .nodes
+                    .iter()
+                    .flat_map(|node| {
+                        node.rows
+                            .iter()
+                            .filter(move |row| row.attr_id == attr)
+                            .map(move |row| (node.id, node.title.as_str(), row.value.as_str()))
+                    })
+                    .filter(|(_, _, value)| state.value.as_deref().is_none_or(|v| v == *value))
+                    .collect();
+
+                ui.add_space(6.0);
+                if matches.is_empty() {
+                    ui.label(RichText::new("No matching entities.").small());
+                    return 0;
+                }
+
+                let row_height = ui.text_style_height(&TextStyle::Body) + 4.0;
+                let shown = matches.len().min(MAX_RESULT_ROWS);
+                TableBuilder::new(ui)
+                    .id_salt(base_id.with("results"))
+                    .resizable(false)
+                    .cell_layout(Layout::left_to_right(Align::Center))
+                    .max_scroll_height(320.0)
+                    .column(Column::auto().at_least(120.0).clip(true))
+                    .column(Column::remainder().clip(true))
+                    .header(row_height, |mut header| {
+                        header.col(|ui| {
+                            ui.label(RichText::new("entity").small());
+                        });
+                        header.col(|ui| {
+                            ui.label(RichText::new("value").small());
+                        });
+                    })
+                    .body(|body| {
+                        body.rows(row_height, shown, |mut row| {
+                            let (id, title, value) = matches[row.index()];
+                            row.col(|ui| {
+                                if ui
+                                    .link(title)
+                                    .on_hover_text(format!("id:{}", id_short(id)))
+                                    .clicked()
+                                {
+                                    clicked = Some(id);
+                                }
+                            });
+                            row.col(|ui| {
+                                ui.label(RichText::new(value).monospace());
+                            });
+                        });
+                    });
+                if shown < matches.len() {
+                    ui.label(
+                        RichText::new(format!("Showing {shown} of {} matches.", matches.len()))
+                            .small(),
+                    );
+                }
+                matches.len()
+            });
+
+        ui.data_mut(|d| d.insert_temp(state_id, state));
+        QueryCardResponse {
+            response: response.response,
+            clicked,
+            matches: response.inner,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use eframe::egui::{Response, Ui};
+
+use crate::dataflow::History;
+
+/// Undo and redo buttons for a [`History`].
+///
+/// Each button is disabled when there is nothing to undo or redo. The
+/// returned response is `changed()` when either button moved the history.
+pub fn undo_redo_buttons<T: Clone + PartialEq>(ui: &mut Ui, history: &mut History<T>) -> Response {
+    ui.horizontal(|ui| {
+        let undo = ui
+            .add_enabled(history.can_undo(), super::Button::new("Undo"))
+            .on_hover_text("Undo the last change.");
+        let redo = ui
+            .add_enabled(history.can_redo(), super::Button::new("Redo"))
+            .on_hover_text("Redo the last undone change.");
+        let changed = (undo.clicked() && history.undo()) | (redo.clicked() && history.redo());
+        let mut response = undo | redo;
+        if changed {
+            response.mark_changed();
+        }
+        response
+    })
+    .inner
+}